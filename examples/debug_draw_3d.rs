@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bodge::{
+    bevy::{DebugDraw3, DebugDraw3Plugin, DebugDrawStyle, DebugDrawable3},
+    geometry::{Aabb3, Plane, Ray3, Sphere, Triangle3},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(DebugDraw3Plugin)
+        .add_startup_system(setup)
+        .add_system(draw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(6., 5., 8.).looking_at(Vec3::ZERO, Vec3::Y),
+        ..Default::default()
+    });
+}
+
+fn draw(mut debug_draw: ResMut<DebugDraw3>, time: Res<Time>) {
+    let angle = time.elapsed_seconds();
+    Plane::new(Vec3::Y, -1.).draw(
+        debug_draw.as_mut(),
+        DebugDrawStyle::new_outline(Color::GRAY),
+    );
+    Aabb3::new(Vec3::ZERO, Vec3::ONE).draw(debug_draw.as_mut(), DebugDrawStyle::new(Color::ORANGE));
+    Sphere::new(Vec3::new(angle.cos(), 0.5, angle.sin()) * 2.5, 1.).draw(
+        debug_draw.as_mut(),
+        DebugDrawStyle::new_outline(Color::CYAN).with_segments(24),
+    );
+    Triangle3::new(
+        Vec3::new(-2., 0., -2.),
+        Vec3::new(-1., 2., -2.),
+        Vec3::new(0., 0., -2.),
+    )
+    .draw(debug_draw.as_mut(), DebugDrawStyle::new(Color::GREEN));
+    Ray3::new(
+        Vec3::new(0., -1., 0.),
+        Vec3::new(angle.sin(), 1., angle.cos()),
+    )
+    .draw(debug_draw.as_mut(), DebugDrawStyle::new(Color::WHITE));
+}
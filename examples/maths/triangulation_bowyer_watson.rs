@@ -174,7 +174,7 @@ fn visual_bowyer_watson(
         let mut bad_triangles = vec![];
         for (triangle_index, triangle) in triangulation.iter().enumerate() {
             let circumcircle = triangle.circumcircle().unwrap();
-            if circumcircle.contains_point(*point) {
+            if triangle.circumcircle_contains_point(*point) {
                 bad_triangles.push(triangle_index);
                 if stepper.show_step() {
                     Circle::new(triangle.a(), 15.)
@@ -13,12 +13,23 @@ pub struct DebugDrawStage;
 
 impl Plugin for DebugDrawPlugin {
     fn build(&self, app: &mut App) {
-        app.add_stage_after(CoreStage::Update, DebugDrawStage, SystemStage::parallel())
-            .init_resource::<DebugDraw>()
+        add_debug_draw_stage(app);
+        app.init_resource::<DebugDraw>()
             .add_system_to_stage(DebugDrawStage, debug_renderer);
     }
 }
 
+// shared with the 3D plugin, so either may be added first or on its own
+pub(super) fn add_debug_draw_stage(app: &mut App) {
+    if app
+        .schedule
+        .get_stage::<SystemStage>(DebugDrawStage)
+        .is_none()
+    {
+        app.add_stage_after(CoreStage::Update, DebugDrawStage, SystemStage::parallel());
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct DebugDraw {
     meshes: Vec<DebugDrawMesh>,
@@ -28,6 +39,11 @@ impl DebugDraw {
     pub fn draw_mesh(&mut self, mesh: DebugDrawMesh) {
         self.meshes.push(mesh);
     }
+
+    /// The meshes drawn so far this frame, in the order they were drawn.
+    pub fn meshes(&self) -> &[DebugDrawMesh] {
+        &self.meshes
+    }
 }
 
 #[derive(Default, Debug, Clone)]
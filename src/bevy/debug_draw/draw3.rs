@@ -0,0 +1,139 @@
+use std::mem::take;
+
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+use super::{add_debug_draw_stage, DebugDrawStage};
+
+/// Renders [`DebugDraw3`], kept apart from [`DebugDrawPlugin`](super::DebugDrawPlugin) since it
+/// needs the PBR materials a 2D app may not have.
+pub struct DebugDraw3Plugin;
+
+impl Plugin for DebugDraw3Plugin {
+    fn build(&self, app: &mut App) {
+        add_debug_draw_stage(app);
+        app.init_resource::<DebugDraw3>()
+            .add_system_to_stage(DebugDrawStage, debug_renderer_3);
+    }
+}
+
+/// The 3D counterpart of [`DebugDraw`](super::DebugDraw), rendered with the depth buffer rather
+/// than by sorting on [`DebugDrawStyle::depth`](super::DebugDrawStyle::depth).
+#[derive(Resource, Default)]
+pub struct DebugDraw3 {
+    meshes: Vec<DebugDrawMesh3>,
+}
+
+impl DebugDraw3 {
+    pub fn draw_mesh(&mut self, mesh: DebugDrawMesh3) {
+        self.meshes.push(mesh);
+    }
+}
+
+/// Vertices shared by a list of line indices and a list of triangle indices.
+#[derive(Default, Debug, Clone)]
+pub struct DebugDrawMesh3 {
+    pub vertices: Vec<DebugDrawVertex3>,
+    pub lines: Vec<u32>,
+    pub triangles: Vec<u32>,
+}
+
+impl DebugDrawMesh3 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn merge_with(&mut self, other: &DebugDrawMesh3) {
+        let base_index = self.vertices.len() as u32;
+        self.vertices.extend(other.vertices.iter());
+        for index in other.lines.iter() {
+            self.lines.push(base_index + *index);
+        }
+        for index in other.triangles.iter() {
+            self.triangles.push(base_index + *index);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DebugDrawVertex3 {
+    pub position: Vec3,
+    pub color: Color,
+}
+
+#[derive(Component)]
+struct DebugDrawObject3;
+
+fn debug_renderer_3(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut debug_render: ResMut<DebugDraw3>,
+    debug_query: Query<Entity, With<DebugDrawObject3>>,
+) {
+    for debug_entity in debug_query.iter() {
+        commands.entity(debug_entity).despawn();
+    }
+
+    let mut merged_mesh = DebugDrawMesh3::new();
+    for debug_render_mesh in take(&mut debug_render.meshes).into_iter() {
+        merged_mesh.merge_with(&debug_render_mesh);
+    }
+    if merged_mesh.vertices.is_empty() {
+        return;
+    }
+
+    let DebugDrawMesh3 {
+        vertices,
+        lines,
+        triangles,
+    } = merged_mesh;
+
+    let mut positions: Vec<[f32; 3]> = vec![];
+    let mut normals: Vec<[f32; 3]> = vec![];
+    let mut uvs: Vec<[f32; 2]> = vec![];
+    let mut colors: Vec<[f32; 4]> = vec![];
+
+    for vertex in vertices.iter() {
+        positions.push(vertex.position.to_array());
+        normals.push([0., 0., 0.]);
+        uvs.push([0., 0.]);
+        colors.push([
+            vertex.color.r(),
+            vertex.color.g(),
+            vertex.color.b(),
+            vertex.color.a(),
+        ]);
+    }
+
+    let material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        unlit: true,
+        cull_mode: None,
+        ..Default::default()
+    });
+    for (topology, indices) in [
+        (PrimitiveTopology::LineList, lines),
+        (PrimitiveTopology::TriangleList, triangles),
+    ] {
+        if indices.is_empty() {
+            continue;
+        }
+        let mut mesh = Mesh::new(topology);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions.clone());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals.clone());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs.clone());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors.clone());
+
+        commands
+            .spawn(PbrBundle {
+                mesh: meshes.add(mesh),
+                material: material.clone(),
+                ..Default::default()
+            })
+            .insert(DebugDrawObject3);
+    }
+}
@@ -2,7 +2,12 @@ use std::f32::consts::TAU;
 
 use bevy::prelude::*;
 
-use crate::geometry::{Aabb, Circle, Line2, LineRay2, LineSegment2, Polyline, Triangle2};
+use crate::{
+    collision::Quadtree,
+    geometry::{
+        Aabb, Circle, Line2, LineRay2, LineSegment2, Obb, Polyline, Triangle2, VertexList2,
+    },
+};
 
 use super::{draw::DebugDrawMesh, DebugDraw, DebugDrawStyle, DebugDrawVertex};
 
@@ -21,6 +26,19 @@ impl DebugDrawable for Aabb {
         if !style.visible {
             return DebugDrawMesh::new();
         }
+        if style.outline {
+            let corners = self.corners();
+            let mut lines = DebugDrawMesh::new();
+            lines.depth = style.depth;
+            for corner_index in 0..corners.len() {
+                let next_corner_index = (corner_index + 1) % corners.len();
+                lines.merge_with(
+                    &LineSegment2::new(corners[corner_index], corners[next_corner_index])
+                        .to_mesh(style),
+                );
+            }
+            return lines;
+        }
         DebugDrawMesh {
             vertices: vec![
                 DebugDrawVertex {
@@ -46,6 +64,38 @@ impl DebugDrawable for Aabb {
     }
 }
 
+impl DebugDrawable for Obb {
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh {
+        if !style.visible {
+            return DebugDrawMesh::new();
+        }
+        let corners = self.corners();
+        if style.outline {
+            let mut lines = DebugDrawMesh::new();
+            lines.depth = style.depth;
+            for corner_index in 0..corners.len() {
+                let next_corner_index = (corner_index + 1) % corners.len();
+                lines.merge_with(
+                    &LineSegment2::new(corners[corner_index], corners[next_corner_index])
+                        .to_mesh(style),
+                );
+            }
+            return lines;
+        }
+        DebugDrawMesh {
+            vertices: corners
+                .iter()
+                .map(|corner| DebugDrawVertex {
+                    position: *corner,
+                    color: style.color,
+                })
+                .collect(),
+            indices: vec![0, 1, 2, 0, 2, 3],
+            depth: style.depth,
+        }
+    }
+}
+
 impl DebugDrawable for Circle {
     fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh {
         if !style.visible {
@@ -189,3 +239,37 @@ impl DebugDrawable for Polyline {
         lines
     }
 }
+
+impl DebugDrawable for VertexList2 {
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh {
+        if !style.visible {
+            return DebugDrawMesh::new();
+        }
+        let mut mesh = DebugDrawMesh::new();
+        mesh.depth = style.depth;
+        if style.outline {
+            for edge in self.edges().iter() {
+                mesh.merge_with(&edge.to_mesh(style));
+            }
+        } else {
+            for triangle in self.triangulate().iter() {
+                mesh.merge_with(&triangle.to_mesh(style));
+            }
+        }
+        mesh
+    }
+}
+
+impl<T> DebugDrawable for Quadtree<T> {
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh {
+        if !style.visible {
+            return DebugDrawMesh::new();
+        }
+        let mut lines = DebugDrawMesh::new();
+        lines.depth = style.depth;
+        for (cell, _) in self.nodes().iter() {
+            lines.merge_with(&cell.to_mesh(style.with_outline(true)));
+        }
+        lines
+    }
+}
@@ -0,0 +1,180 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+
+use crate::geometry::{Aabb3, LineSegment3, Plane, Ray3, Sphere, Triangle3};
+
+use super::{DebugDraw3, DebugDrawMesh3, DebugDrawStyle, DebugDrawVertex3};
+
+const INFINITE_LENGTH: f32 = 10000.;
+const PLANE_HALF_SIZE: f32 = 10.;
+
+/// The 3D counterpart of [`DebugDrawable`](super::DebugDrawable). Lines are always drawn one
+/// pixel wide, so [`DebugDrawStyle::thickness`] and [`DebugDrawStyle::depth`] are ignored.
+pub trait DebugDrawable3 {
+    fn draw(&self, debug_draw: &mut DebugDraw3, style: DebugDrawStyle) {
+        debug_draw.draw_mesh(self.to_mesh(style));
+    }
+
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh3;
+}
+
+fn vertices(positions: &[Vec3], color: Color) -> Vec<DebugDrawVertex3> {
+    positions
+        .iter()
+        .map(|position| DebugDrawVertex3 {
+            position: *position,
+            color,
+        })
+        .collect()
+}
+
+impl DebugDrawable3 for LineSegment3 {
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh3 {
+        if !style.visible {
+            return DebugDrawMesh3::new();
+        }
+        DebugDrawMesh3 {
+            vertices: vertices(&[self.start, self.end], style.color),
+            lines: vec![0, 1],
+            triangles: vec![],
+        }
+    }
+}
+
+impl DebugDrawable3 for Ray3 {
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh3 {
+        if !style.visible {
+            return DebugDrawMesh3::new();
+        }
+        self.segment(INFINITE_LENGTH).to_mesh(style)
+    }
+}
+
+impl DebugDrawable3 for Triangle3 {
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh3 {
+        if !style.visible {
+            return DebugDrawMesh3::new();
+        }
+        let mut mesh = DebugDrawMesh3 {
+            vertices: vertices(&self.vertices, style.color),
+            lines: vec![],
+            triangles: vec![],
+        };
+        if style.outline {
+            mesh.lines = vec![0, 1, 1, 2, 2, 0];
+        } else {
+            mesh.triangles = vec![0, 1, 2];
+        }
+        mesh
+    }
+}
+
+impl DebugDrawable3 for Aabb3 {
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh3 {
+        if !style.visible {
+            return DebugDrawMesh3::new();
+        }
+        let mut mesh = DebugDrawMesh3 {
+            vertices: vertices(&self.corners(), style.color),
+            lines: vec![],
+            triangles: vec![],
+        };
+        if style.outline {
+            // corners differing in a single bit share an edge
+            for corner in 0..8u32 {
+                for bit in [1, 2, 4] {
+                    if corner & bit == 0 {
+                        mesh.lines.extend([corner, corner | bit]);
+                    }
+                }
+            }
+        } else {
+            for (bit, other_bits) in [(1, [2, 4]), (2, [4, 1]), (4, [1, 2])] {
+                for side in [0, bit] {
+                    let quad = [
+                        side,
+                        side | other_bits[0],
+                        side | other_bits[0] | other_bits[1],
+                        side | other_bits[1],
+                    ];
+                    mesh.triangles
+                        .extend([quad[0], quad[1], quad[2], quad[0], quad[2], quad[3]]);
+                }
+            }
+        }
+        mesh
+    }
+}
+
+impl DebugDrawable3 for Sphere {
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh3 {
+        if !style.visible {
+            return DebugDrawMesh3::new();
+        }
+        let segments = style.segments.max(3) as u32;
+        let radius = self.radius * 0.5;
+        let mut mesh = DebugDrawMesh3::new();
+        if style.outline {
+            // one great circle around each axis
+            for (u, v) in [(Vec3::X, Vec3::Y), (Vec3::Y, Vec3::Z), (Vec3::Z, Vec3::X)] {
+                let base_index = mesh.vertices.len() as u32;
+                for segment in 0..segments {
+                    let (sin, cos) = (segment as f32 / segments as f32 * TAU).sin_cos();
+                    mesh.vertices.push(DebugDrawVertex3 {
+                        position: self.center + (u * cos + v * sin) * radius,
+                        color: style.color,
+                    });
+                    mesh.lines
+                        .extend([base_index + segment, base_index + (segment + 1) % segments]);
+                }
+            }
+        } else {
+            let rings = (segments / 2).max(2);
+            for ring in 0..=rings {
+                let (ring_sin, ring_cos) = (ring as f32 / rings as f32 * PI).sin_cos();
+                for segment in 0..segments {
+                    let (sin, cos) = (segment as f32 / segments as f32 * TAU).sin_cos();
+                    mesh.vertices.push(DebugDrawVertex3 {
+                        position: self.center
+                            + Vec3::new(ring_sin * cos, ring_cos, ring_sin * sin) * radius,
+                        color: style.color,
+                    });
+                }
+            }
+            for ring in 0..rings {
+                for segment in 0..segments {
+                    let next_segment = (segment + 1) % segments;
+                    let (a, b) = (ring * segments + segment, ring * segments + next_segment);
+                    let (c, d) = (a + segments, b + segments);
+                    mesh.triangles.extend([a, c, b, b, c, d]);
+                }
+            }
+        }
+        mesh
+    }
+}
+
+impl DebugDrawable3 for Plane {
+    fn to_mesh(&self, style: DebugDrawStyle) -> DebugDrawMesh3 {
+        if !style.visible {
+            return DebugDrawMesh3::new();
+        }
+        // planes are endless, so draw a patch around the point closest to the origin
+        let center = self.closest_point(Vec3::ZERO);
+        let (u, v) = self.normal.any_orthonormal_pair();
+        let corners = [u + v, v - u, -u - v, u - v].map(|corner| center + corner * PLANE_HALF_SIZE);
+        let mut mesh = DebugDrawMesh3 {
+            vertices: vertices(&corners, style.color),
+            lines: vec![],
+            triangles: vec![],
+        };
+        if style.outline {
+            mesh.lines = vec![0, 1, 1, 2, 2, 3, 3, 0];
+        } else {
+            mesh.triangles = vec![0, 1, 2, 0, 2, 3];
+        }
+        mesh.merge_with(&LineSegment3::new(center, center + self.normal).to_mesh(style));
+        mesh
+    }
+}
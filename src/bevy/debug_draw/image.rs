@@ -0,0 +1,224 @@
+use bevy::prelude::*;
+
+use super::{DebugDraw, DebugDrawMesh};
+
+/// An RGBA image that debug draw meshes are rasterized into on the CPU, for looking at them
+/// without a GPU, such as in snapshot tests.
+///
+/// Triangles cover the pixels whose centers they contain, with vertex colors blended over what is
+/// already there. Blending works on the sRGB values directly, so translucent overlaps can differ
+/// slightly from what the renderer shows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugDrawImage {
+    /// The world position shown in the middle of the image.
+    pub center: Vec2,
+    /// World units per pixel.
+    pub scale: f32,
+    width: u32,
+    height: u32,
+    // rows from the top down
+    pixels: Vec<[u8; 4]>,
+}
+
+impl DebugDrawImage {
+    pub fn new(width: u32, height: u32, background: Color) -> DebugDrawImage {
+        DebugDrawImage {
+            center: Vec2::ZERO,
+            scale: 1.,
+            width,
+            height,
+            pixels: vec![to_bytes(background.as_rgba_f32()); (width * height) as usize],
+        }
+    }
+
+    pub fn with_view(&self, center: Vec2, scale: f32) -> DebugDrawImage {
+        DebugDrawImage {
+            center,
+            scale,
+            ..self.clone()
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The pixel `x` from the left and `y` from the top.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        bodge_assert!(x < self.width && y < self.height);
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    /// Every pixel row by row from the top left, four bytes each.
+    pub fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    pub fn clear(&mut self, background: Color) {
+        self.pixels.fill(to_bytes(background.as_rgba_f32()));
+    }
+
+    pub fn draw_mesh(&mut self, mesh: &DebugDrawMesh) {
+        bodge_assert!(self.center.is_finite());
+        bodge_assert!(self.scale.is_finite() && self.scale > 0.);
+        let (center, scale) = (self.center, self.scale);
+        let half_size = Vec2::new(self.width as f32, self.height as f32) * 0.5;
+        // pixel space, with y pointing down
+        let to_pixels =
+            |position: Vec2| (position - center) / scale * Vec2::new(1., -1.) + half_size;
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [0, 1, 2].map(|corner| mesh.vertices[triangle[corner] as usize]);
+            self.fill_triangle(
+                vertices.map(|vertex| to_pixels(vertex.position)),
+                vertices.map(|vertex| vertex.color.as_rgba_f32()),
+            );
+        }
+    }
+
+    /// Draws the meshes from shallowest to deepest, the order the debug renderer merges them in.
+    pub fn draw_meshes(&mut self, meshes: &[DebugDrawMesh]) {
+        let mut order: Vec<&DebugDrawMesh> = meshes.iter().collect();
+        order.sort_by(|a, b| a.depth.total_cmp(&b.depth));
+        for mesh in order {
+            self.draw_mesh(mesh);
+        }
+    }
+
+    /// Draws every mesh drawn so far this frame.
+    pub fn draw_debug_draw(&mut self, debug_draw: &DebugDraw) {
+        self.draw_meshes(debug_draw.meshes());
+    }
+
+    #[cfg(feature = "png")]
+    pub fn write_png<W: std::io::Write>(&self, writer: W) -> Result<(), png::EncodingError> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.bytes())
+    }
+
+    #[cfg(feature = "png")]
+    pub fn save_png<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), png::EncodingError> {
+        self.write_png(std::io::BufWriter::new(std::fs::File::create(path)?))
+    }
+
+    fn fill_triangle(&mut self, positions: [Vec2; 3], colors: [[f32; 4]; 3]) {
+        let [a, mut b, mut c] = positions;
+        let [color_a, mut color_b, mut color_c] = colors;
+        let area = (b - a).perp_dot(c - a);
+        if area == 0. || !area.is_finite() {
+            return;
+        }
+        if area < 0. {
+            (b, c) = (c, b);
+            (color_b, color_c) = (color_c, color_b);
+        }
+        let area = area.abs();
+        let min = a.min(b).min(c).floor().max(Vec2::ZERO);
+        let max = a
+            .max(b)
+            .max(c)
+            .ceil()
+            .min(Vec2::new(self.width as f32, self.height as f32));
+        // a pixel center right on an edge goes to only one of the two triangles sharing it
+        let covers = |start: Vec2, end: Vec2, weight: f32| {
+            let direction = end - start;
+            weight > 0.
+                || (weight == 0. && (direction.y > 0. || (direction.y == 0. && direction.x > 0.)))
+        };
+        for y in min.y as u32..max.y as u32 {
+            for x in min.x as u32..max.x as u32 {
+                let point = Vec2::new(x as f32, y as f32) + 0.5;
+                let weights = [
+                    (c - b).perp_dot(point - b),
+                    (a - c).perp_dot(point - c),
+                    (b - a).perp_dot(point - a),
+                ];
+                if !(covers(b, c, weights[0])
+                    && covers(c, a, weights[1])
+                    && covers(a, b, weights[2]))
+                {
+                    continue;
+                }
+                let color: [f32; 4] = std::array::from_fn(|channel| {
+                    (color_a[channel] * weights[0]
+                        + color_b[channel] * weights[1]
+                        + color_c[channel] * weights[2])
+                        / area
+                });
+                let pixel = &mut self.pixels[(y * self.width + x) as usize];
+                let below = pixel.map(|byte| byte as f32 / 255.);
+                let alpha = color[3].clamp(0., 1.);
+                let mut blended: [f32; 4] = std::array::from_fn(|channel| {
+                    color[channel] * alpha + below[channel] * (1. - alpha)
+                });
+                blended[3] = alpha + below[3] * (1. - alpha);
+                *pixel = to_bytes(blended);
+            }
+        }
+    }
+}
+
+fn to_bytes(color: [f32; 4]) -> [u8; 4] {
+    color.map(|channel| (channel.clamp(0., 1.) * 255.).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bevy::{DebugDrawStyle, DebugDrawable},
+        geometry::{Aabb, Triangle2},
+    };
+
+    use super::*;
+
+    fn count(image: &DebugDrawImage, color: [u8; 4]) -> usize {
+        image.pixels.iter().filter(|pixel| **pixel == color).count()
+    }
+
+    #[test]
+    fn debug_draw_image_fills_pixels() {
+        let mut image = DebugDrawImage::new(8, 8, Color::WHITE);
+        // two triangles sharing a diagonal, which must not be blended twice
+        let square = Aabb::new(Vec2::ZERO, Vec2::splat(4.));
+        image.draw_mesh(&square.to_mesh(DebugDrawStyle::new(Color::rgba(0., 0., 0., 0.5))));
+        assert_eq!(count(&image, [128, 128, 128, 255]), 16);
+        assert_eq!(count(&image, [255, 255, 255, 255]), 64 - 16);
+        assert_eq!(image.pixel(2, 2), [128, 128, 128, 255]);
+        assert_eq!(image.pixel(1, 2), [255, 255, 255, 255]);
+
+        // y points up in the world and down in the image
+        let mut image = DebugDrawImage::new(8, 8, Color::NONE).with_view(Vec2::splat(4.), 1.);
+        let triangle = Triangle2::new(Vec2::new(0., 8.), Vec2::new(8., 8.), Vec2::new(0., 4.));
+        image.draw_mesh(&triangle.to_mesh(DebugDrawStyle::new(Color::RED)));
+        assert_eq!(image.pixel(0, 0), [255, 0, 0, 255]);
+        assert_eq!(image.pixel(0, 7), [0, 0, 0, 0]);
+        assert_eq!(count(&image, [255, 0, 0, 255]), 7 + 5 + 3 + 1);
+    }
+
+    #[test]
+    fn debug_draw_image_follows_depth() {
+        let mut debug_draw = DebugDraw::default();
+        let square = Aabb::new(Vec2::ZERO, Vec2::splat(4.));
+        square.draw(
+            &mut debug_draw,
+            DebugDrawStyle::new(Color::BLUE).with_depth(1.),
+        );
+        square.draw(&mut debug_draw, DebugDrawStyle::new(Color::RED));
+        let mut image = DebugDrawImage::new(8, 8, Color::BLACK);
+        image.draw_debug_draw(&debug_draw);
+        assert_eq!(count(&image, [0, 0, 255, 255]), 16);
+        assert_eq!(count(&image, [255, 0, 0, 255]), 0);
+
+        #[cfg(feature = "png")]
+        {
+            let mut png = vec![];
+            image.write_png(&mut png).unwrap();
+            assert_eq!(png[..8], [137, 80, 78, 71, 13, 10, 26, 10]);
+        }
+    }
+}
@@ -1,7 +1,13 @@
 mod draw;
+mod draw3;
 mod drawable;
+mod drawable3;
+mod image;
 mod style;
 
 pub use draw::*;
+pub use draw3::*;
 pub use drawable::*;
+pub use drawable3::*;
+pub use image::*;
 pub use style::*;
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DebugDrawStyle {
     pub visible: bool,
     pub color: Color,
@@ -63,3 +64,25 @@ impl DebugDrawStyle {
         DebugDrawStyle { depth, ..*self }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_draw_style_deserialize() {
+        let style = DebugDrawStyle::new_outline(Color::rgba(1., 0.5, 0., 0.25))
+            .with_thickness(2.)
+            .with_depth(3.);
+        let deserialized: DebugDrawStyle = ron::from_str(&ron::to_string(&style).unwrap()).unwrap();
+        assert_eq!(deserialized.color, style.color);
+        assert_eq!(
+            (
+                deserialized.outline,
+                deserialized.thickness,
+                deserialized.depth
+            ),
+            (true, 2., 3.)
+        );
+    }
+}
@@ -0,0 +1,608 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use glam::Vec2;
+
+use crate::{
+    geometry::{Aabb, LineRay2},
+    utility::SortableWith,
+};
+
+use super::Colliding;
+
+/// Identifies an item stored in an [`AabbTree`]. Keys of removed items may be handed out again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AabbTreeKey(usize);
+
+#[derive(Debug, Clone)]
+enum AabbTreeNodeKind<T> {
+    Leaf { aabb: Aabb, item: T },
+    Branch { children: [usize; 2] },
+    Free,
+}
+
+#[derive(Debug, Clone)]
+struct AabbTreeNode<T> {
+    // fattened for leaves, the union of both children for branches
+    aabb: Aabb,
+    parent: Option<usize>,
+    height: usize,
+    kind: AabbTreeNodeKind<T>,
+}
+
+/// A dynamic bounding volume hierarchy over [`Aabb`]s.
+///
+/// Leaves store a fattened copy of each [`Aabb`] so small movements don't restructure the tree,
+/// and branches are kept balanced with tree rotations.
+#[derive(Debug, Clone)]
+pub struct AabbTree<T> {
+    margin: f32,
+    nodes: Vec<AabbTreeNode<T>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<T> AabbTree<T> {
+    pub fn new(margin: f32) -> AabbTree<T> {
+        bodge_assert!(margin.is_finite());
+        bodge_assert!(margin >= 0.);
+        AabbTree {
+            margin,
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            len: 0,
+        }
+    }
+
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of levels below the root, zero for a single leaf.
+    pub fn height(&self) -> usize {
+        self.root.map_or(0, |root| self.nodes[root].height)
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.root = None;
+        self.len = 0;
+    }
+
+    pub fn insert(&mut self, aabb: Aabb, item: T) -> AabbTreeKey {
+        let leaf = self.allocate(AabbTreeNode {
+            aabb: aabb.expanded(self.margin),
+            parent: None,
+            height: 0,
+            kind: AabbTreeNodeKind::Leaf { aabb, item },
+        });
+        self.insert_leaf(leaf);
+        self.len += 1;
+        AabbTreeKey(leaf)
+    }
+
+    pub fn remove(&mut self, key: AabbTreeKey) -> Option<T> {
+        if !self.is_leaf(key.0) {
+            return None;
+        }
+        self.remove_leaf(key.0);
+        self.len -= 1;
+        self.free.push(key.0);
+        match std::mem::replace(&mut self.nodes[key.0].kind, AabbTreeNodeKind::Free) {
+            AabbTreeNodeKind::Leaf { item, .. } => Some(item),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Moves an item to a new [`Aabb`], returning true if it left its fattened bounds and had
+    /// to be reinserted.
+    pub fn update(&mut self, key: AabbTreeKey, aabb: Aabb) -> bool {
+        if !self.is_leaf(key.0) {
+            bodge_assert!(false, "updated a key that is not in the aabb tree");
+            return false;
+        }
+        if let AabbTreeNodeKind::Leaf { aabb: tight, .. } = &mut self.nodes[key.0].kind {
+            *tight = aabb;
+        }
+        if self.nodes[key.0].aabb.contains_aabb(aabb) {
+            return false;
+        }
+        self.remove_leaf(key.0);
+        self.nodes[key.0].aabb = aabb.expanded(self.margin);
+        self.insert_leaf(key.0);
+        true
+    }
+
+    pub fn get(&self, key: AabbTreeKey) -> Option<&T> {
+        match &self.nodes.get(key.0)?.kind {
+            AabbTreeNodeKind::Leaf { item, .. } => Some(item),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: AabbTreeKey) -> Option<&mut T> {
+        match &mut self.nodes.get_mut(key.0)?.kind {
+            AabbTreeNodeKind::Leaf { item, .. } => Some(item),
+            _ => None,
+        }
+    }
+
+    pub fn aabb(&self, key: AabbTreeKey) -> Option<Aabb> {
+        match &self.nodes.get(key.0)?.kind {
+            AabbTreeNodeKind::Leaf { aabb, .. } => Some(*aabb),
+            _ => None,
+        }
+    }
+
+    pub fn fat_aabb(&self, key: AabbTreeKey) -> Option<Aabb> {
+        if self.is_leaf(key.0) {
+            Some(self.nodes[key.0].aabb)
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (AabbTreeKey, &T)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| match &node.kind {
+                AabbTreeNodeKind::Leaf { item, .. } => Some((AabbTreeKey(index), item)),
+                _ => None,
+            })
+    }
+
+    /// Bounds of every branch and leaf along with its depth, root first.
+    pub fn nodes(&self) -> Vec<(Aabb, usize)> {
+        let mut nodes = vec![];
+        let mut stack: Vec<(usize, usize)> = self.root.iter().map(|root| (*root, 0)).collect();
+        while let Some((index, depth)) = stack.pop() {
+            nodes.push((self.nodes[index].aabb, depth));
+            if let AabbTreeNodeKind::Branch { children } = self.nodes[index].kind {
+                stack.push((children[1], depth + 1));
+                stack.push((children[0], depth + 1));
+            }
+        }
+        nodes
+    }
+
+    pub fn query_point(&self, point: Vec2) -> Vec<AabbTreeKey> {
+        bodge_assert!(point.is_finite());
+        self.query(
+            |aabb| aabb.contains_point(point),
+            |aabb| aabb.contains_point(point),
+        )
+    }
+
+    pub fn query_aabb(&self, aabb: Aabb) -> Vec<AabbTreeKey> {
+        self.query(|fat| fat.colliding(&aabb), |tight| tight.colliding(&aabb))
+    }
+
+    /// Items whose [`Aabb`] the ray passes through within `max_distance`, nearest first.
+    pub fn query_ray(&self, ray: LineRay2, max_distance: f32) -> Vec<AabbTreeKey> {
+        bodge_assert!(max_distance >= 0.);
+        let hit = |aabb: &Aabb| {
+            aabb.ray_entry_distance(ray)
+                .filter(|distance| *distance <= max_distance)
+        };
+        let mut hits: Vec<SortableWith<f32, AabbTreeKey>> = self
+            .query(|aabb| hit(aabb).is_some(), |aabb| hit(aabb).is_some())
+            .into_iter()
+            .map(|key| SortableWith(hit(&self.aabb(key).unwrap()).unwrap(), key))
+            .collect();
+        hits.sort();
+        hits.into_iter().map(|hit| hit.1).collect()
+    }
+
+    /// The item whose [`Aabb`] is closest to a point, with distance zero for points inside.
+    pub fn nearest(&self, point: Vec2) -> Option<AabbTreeKey> {
+        bodge_assert!(point.is_finite());
+        let distance = |aabb: &Aabb| aabb.closest_point(point).distance(point);
+        let mut best: Option<(f32, AabbTreeKey)> = None;
+        let mut heap = BinaryHeap::new();
+        if let Some(root) = self.root {
+            heap.push(Reverse(SortableWith(
+                distance(&self.nodes[root].aabb),
+                root,
+            )));
+        }
+        while let Some(Reverse(SortableWith(node_distance, index))) = heap.pop() {
+            if best.is_some_and(|(best_distance, _)| node_distance >= best_distance) {
+                break;
+            }
+            match &self.nodes[index].kind {
+                AabbTreeNodeKind::Leaf { aabb, .. } => {
+                    let leaf_distance = distance(aabb);
+                    if best.is_none_or(|(best_distance, _)| leaf_distance < best_distance) {
+                        best = Some((leaf_distance, AabbTreeKey(index)));
+                    }
+                }
+                AabbTreeNodeKind::Branch { children } => {
+                    for child in children.iter() {
+                        heap.push(Reverse(SortableWith(
+                            distance(&self.nodes[*child].aabb),
+                            *child,
+                        )));
+                    }
+                }
+                AabbTreeNodeKind::Free => unreachable!(),
+            }
+        }
+        best.map(|(_, key)| key)
+    }
+
+    /// Pairs of items whose [`Aabb`]s overlap, each reported once with the lower key first.
+    pub fn pairs(&self) -> Vec<(AabbTreeKey, AabbTreeKey)> {
+        let mut pairs = vec![];
+        for (index, node) in self.nodes.iter().enumerate() {
+            if let AabbTreeNodeKind::Leaf { aabb, .. } = &node.kind {
+                for other in self.query_aabb(*aabb) {
+                    if other.0 > index {
+                        pairs.push((AabbTreeKey(index), other));
+                    }
+                }
+            }
+        }
+        pairs
+    }
+
+    fn query(
+        &self,
+        mut visit_node: impl FnMut(&Aabb) -> bool,
+        mut visit_leaf: impl FnMut(&Aabb) -> bool,
+    ) -> Vec<AabbTreeKey> {
+        let mut found = vec![];
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !visit_node(&node.aabb) {
+                continue;
+            }
+            match &node.kind {
+                AabbTreeNodeKind::Leaf { aabb, .. } => {
+                    if visit_leaf(aabb) {
+                        found.push(AabbTreeKey(index));
+                    }
+                }
+                AabbTreeNodeKind::Branch { children } => stack.extend(children.iter()),
+                AabbTreeNodeKind::Free => unreachable!(),
+            }
+        }
+        found.sort();
+        found
+    }
+
+    fn is_leaf(&self, index: usize) -> bool {
+        matches!(
+            self.nodes.get(index).map(|node| &node.kind),
+            Some(AabbTreeNodeKind::Leaf { .. })
+        )
+    }
+
+    fn children(&self, index: usize) -> [usize; 2] {
+        match self.nodes[index].kind {
+            AabbTreeNodeKind::Branch { children } => children,
+            _ => unreachable!(),
+        }
+    }
+
+    fn set_children(&mut self, index: usize, children: [usize; 2]) {
+        self.nodes[index].kind = AabbTreeNodeKind::Branch { children };
+    }
+
+    fn allocate(&mut self, node: AabbTreeNode<T>) -> usize {
+        if let Some(index) = self.free.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn replace_child(&mut self, parent: Option<usize>, old_child: usize, new_child: usize) {
+        if let Some(parent) = parent {
+            let mut children = self.children(parent);
+            if children[0] == old_child {
+                children[0] = new_child;
+            } else {
+                children[1] = new_child;
+            }
+            self.set_children(parent, children);
+        } else {
+            self.root = Some(new_child);
+        }
+    }
+
+    fn refit(&mut self, index: usize) {
+        let [child_1, child_2] = self.children(index);
+        self.nodes[index].height = 1 + self.nodes[child_1].height.max(self.nodes[child_2].height);
+        self.nodes[index].aabb = self.nodes[child_1].aabb.union(self.nodes[child_2].aabb);
+    }
+
+    fn refit_ancestors(&mut self, mut index: Option<usize>) {
+        while let Some(current) = index {
+            let current = self.balance(current);
+            self.refit(current);
+            index = self.nodes[current].parent;
+        }
+    }
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            self.nodes[leaf].parent = None;
+            return;
+        };
+
+        // descend towards the sibling with the lowest surface area cost
+        let leaf_aabb = self.nodes[leaf].aabb;
+        let mut index = root;
+        while let AabbTreeNodeKind::Branch { children } = self.nodes[index].kind {
+            let perimeter = self.nodes[index].aabb.perimeter();
+            let combined_perimeter = self.nodes[index].aabb.union(leaf_aabb).perimeter();
+            let cost = combined_perimeter * 2.;
+            let inheritance_cost = (combined_perimeter - perimeter) * 2.;
+            let child_cost = |child: usize| {
+                let child_node = &self.nodes[child];
+                let union_perimeter = child_node.aabb.union(leaf_aabb).perimeter();
+                match child_node.kind {
+                    AabbTreeNodeKind::Leaf { .. } => union_perimeter + inheritance_cost,
+                    _ => union_perimeter - child_node.aabb.perimeter() + inheritance_cost,
+                }
+            };
+            let cost_1 = child_cost(children[0]);
+            let cost_2 = child_cost(children[1]);
+            if cost < cost_1 && cost < cost_2 {
+                break;
+            }
+            index = if cost_1 < cost_2 {
+                children[0]
+            } else {
+                children[1]
+            };
+        }
+
+        let sibling = index;
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate(AabbTreeNode {
+            aabb: leaf_aabb.union(self.nodes[sibling].aabb),
+            parent: old_parent,
+            height: self.nodes[sibling].height + 1,
+            kind: AabbTreeNodeKind::Branch {
+                children: [sibling, leaf],
+            },
+        });
+        self.replace_child(old_parent, sibling, new_parent);
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+        self.refit_ancestors(Some(new_parent));
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) {
+        let Some(parent) = self.nodes[leaf].parent else {
+            self.root = None;
+            return;
+        };
+        let grandparent = self.nodes[parent].parent;
+        let children = self.children(parent);
+        let sibling = if children[0] == leaf {
+            children[1]
+        } else {
+            children[0]
+        };
+        self.replace_child(grandparent, parent, sibling);
+        self.nodes[sibling].parent = grandparent;
+        self.nodes[parent].kind = AabbTreeNodeKind::Free;
+        self.free.push(parent);
+        self.nodes[leaf].parent = None;
+        self.refit_ancestors(grandparent);
+    }
+
+    // rotates a taller grandchild up if the subtree at `index` is unbalanced, returning the
+    // index of the subtree's new root
+    fn balance(&mut self, a: usize) -> usize {
+        if self.nodes[a].height < 2 {
+            return a;
+        }
+        let [b, c] = self.children(a);
+        let balance = self.nodes[c].height as isize - self.nodes[b].height as isize;
+        if balance > 1 {
+            self.rotate_up(a, c, 1)
+        } else if balance < -1 {
+            self.rotate_up(a, b, 0)
+        } else {
+            a
+        }
+    }
+
+    // swaps `child`, the `side` child of `a`, into a's place and hands one of its own children
+    // down to `a`
+    fn rotate_up(&mut self, a: usize, child: usize, side: usize) -> usize {
+        let [grandchild_1, grandchild_2] = self.children(child);
+        let parent = self.nodes[a].parent;
+        self.nodes[child].parent = parent;
+        self.nodes[a].parent = Some(child);
+        self.replace_child(parent, a, child);
+
+        let (keep, give) = if self.nodes[grandchild_1].height > self.nodes[grandchild_2].height {
+            (grandchild_1, grandchild_2)
+        } else {
+            (grandchild_2, grandchild_1)
+        };
+        let mut a_children = self.children(a);
+        a_children[side] = give;
+        self.set_children(a, a_children);
+        self.nodes[give].parent = Some(a);
+        self.set_children(child, [a, keep]);
+        self.refit(a);
+        self.refit(child);
+        child
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(position: Vec2, size: f32) -> Aabb {
+        Aabb::new(position, Vec2::splat(size))
+    }
+
+    fn scattered(count: usize) -> Vec<Aabb> {
+        (0..count)
+            .map(|index| {
+                let position = Vec2::new(
+                    (index as f32 * 7.31).sin() * 80.,
+                    (index as f32 * 3.17).cos() * 80.,
+                );
+                square(position, 0.5 + (index % 9) as f32)
+            })
+            .collect()
+    }
+
+    fn validate<T>(tree: &AabbTree<T>) {
+        let mut leaves = 0;
+        for (index, node) in tree.nodes.iter().enumerate() {
+            match node.kind {
+                AabbTreeNodeKind::Leaf { aabb, .. } => {
+                    leaves += 1;
+                    assert!(node.aabb.contains_aabb(aabb));
+                    assert_eq!(node.height, 0);
+                }
+                AabbTreeNodeKind::Branch { children } => {
+                    for child in children.iter() {
+                        assert_eq!(tree.nodes[*child].parent, Some(index));
+                        // bounds are stored as center and size, so allow for rounding
+                        assert!(node
+                            .aabb
+                            .expanded(1e-3)
+                            .contains_aabb(tree.nodes[*child].aabb));
+                    }
+                    let (height_1, height_2) = (
+                        tree.nodes[children[0]].height,
+                        tree.nodes[children[1]].height,
+                    );
+                    assert_eq!(node.height, 1 + height_1.max(height_2));
+                    assert!(height_1.abs_diff(height_2) <= 1);
+                }
+                AabbTreeNodeKind::Free => {}
+            }
+        }
+        assert_eq!(leaves, tree.len());
+        if let Some(root) = tree.root {
+            assert_eq!(tree.nodes[root].parent, None);
+        }
+    }
+
+    #[test]
+    fn aabb_tree_stays_balanced() {
+        let mut tree = AabbTree::new(0.5);
+        let mut keys = vec![];
+        for index in 0..256 {
+            keys.push(tree.insert(square(Vec2::new(index as f32 * 3., 0.), 1.), index));
+            validate(&tree);
+        }
+        assert!(tree.height() <= 12, "height {}", tree.height());
+        for key in keys.iter().step_by(2) {
+            assert!(tree.remove(*key).is_some());
+            validate(&tree);
+        }
+        assert_eq!(tree.len(), 128);
+        for (index, key) in keys.iter().skip(1).step_by(2).enumerate() {
+            tree.update(*key, square(Vec2::new(0., index as f32 * 3.), 1.));
+            validate(&tree);
+        }
+    }
+
+    #[test]
+    fn aabb_tree_update_uses_fat_bounds() {
+        let mut tree = AabbTree::new(1.);
+        let key = tree.insert(square(Vec2::ZERO, 2.), ());
+        tree.insert(square(Vec2::new(10., 0.), 2.), ());
+        assert!(!tree.update(key, square(Vec2::new(0.5, 0.), 2.)));
+        assert_eq!(tree.aabb(key), Some(square(Vec2::new(0.5, 0.), 2.)));
+        assert!(tree.update(key, square(Vec2::new(3., 0.), 2.)));
+        assert_eq!(tree.fat_aabb(key), Some(square(Vec2::new(3., 0.), 4.)));
+    }
+
+    #[test]
+    fn aabb_tree_queries_match_brute_force() {
+        let aabbs = scattered(300);
+        let mut tree = AabbTree::new(0.25);
+        let keys: Vec<AabbTreeKey> = aabbs
+            .iter()
+            .enumerate()
+            .map(|(index, aabb)| tree.insert(*aabb, index))
+            .collect();
+        validate(&tree);
+
+        let mut brute_force_pairs = vec![];
+        for (index, aabb) in aabbs.iter().enumerate() {
+            for (other_index, other_aabb) in aabbs.iter().enumerate().skip(index + 1) {
+                if aabb.colliding(other_aabb) {
+                    brute_force_pairs.push((keys[index], keys[other_index]));
+                }
+            }
+        }
+        assert!(!brute_force_pairs.is_empty());
+        assert_eq!(tree.pairs(), brute_force_pairs);
+
+        for step in 0..50 {
+            let point = Vec2::new(step as f32 * 3. - 75., (step as f32).sin() * 60.);
+            let mut expected: Vec<AabbTreeKey> = aabbs
+                .iter()
+                .enumerate()
+                .filter(|(_, aabb)| aabb.contains_point(point))
+                .map(|(index, _)| keys[index])
+                .collect();
+            expected.sort();
+            assert_eq!(tree.query_point(point), expected);
+
+            let region = square(point, 12.);
+            let mut expected: Vec<AabbTreeKey> = aabbs
+                .iter()
+                .enumerate()
+                .filter(|(_, aabb)| aabb.colliding(&region))
+                .map(|(index, _)| keys[index])
+                .collect();
+            expected.sort();
+            assert_eq!(tree.query_aabb(region), expected);
+
+            let nearest = tree.nearest(point).unwrap();
+            let nearest_distance = |aabb: &Aabb| aabb.closest_point(point).distance(point);
+            let best = aabbs
+                .iter()
+                .map(nearest_distance)
+                .fold(f32::INFINITY, f32::min);
+            assert_eq!(nearest_distance(&tree.aabb(nearest).unwrap()), best);
+
+            let ray = LineRay2::new(point, Vec2::from_angle(step as f32));
+            let hits = tree.query_ray(ray, 50.);
+            let expected = aabbs
+                .iter()
+                .filter(|aabb| {
+                    aabb.ray_entry_distance(ray)
+                        .is_some_and(|distance| distance <= 50.)
+                })
+                .count();
+            assert_eq!(hits.len(), expected);
+            for pair in hits.windows(2) {
+                assert!(
+                    tree.aabb(pair[0]).unwrap().ray_entry_distance(ray)
+                        <= tree.aabb(pair[1]).unwrap().ray_entry_distance(ray)
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,12 @@
 use glam::Vec2;
 
-use crate::geometry::{Circle, LineSegment2};
+use crate::geometry::{
+    Aabb, Circle, Line2, LineIntersection, LineRay2, LineSegment2, Obb, Parametric, Polyline,
+    Triangle2, VertexList2,
+};
+
+/// How far apart a point may be from a line, ray, segment or other point and still be colliding.
+pub const POINT_TOLERANCE: f32 = 1e-4;
 
 pub trait Colliding<T>
 where
@@ -9,32 +15,783 @@ where
     fn colliding(&self, other: &T) -> bool;
 }
 
-impl Colliding<Circle> for Circle {
-    fn colliding(&self, other: &Circle) -> bool {
-        let combined_radius = (self.radius + other.radius) * 0.5;
-        self.center.distance_squared(other.center) < combined_radius * combined_radius
-    }
+macro_rules! impl_colliding {
+    ($a:ty, $b:ty, $function:ident) => {
+        impl Colliding<$b> for $a {
+            fn colliding(&self, other: &$b) -> bool {
+                $function(self, other)
+            }
+        }
+    };
 }
 
-impl Colliding<LineSegment2> for Circle {
-    fn colliding(&self, other: &LineSegment2) -> bool {
-        // TODO: optimize?
-        other.closest_point(self.center).distance(self.center) < self.radius * 0.5
-    }
+macro_rules! impl_colliding_symmetric {
+    ($a:ty, $b:ty, $function:ident) => {
+        impl_colliding!($a, $b, $function);
+
+        impl Colliding<$a> for $b {
+            fn colliding(&self, other: &$a) -> bool {
+                $function(other, self)
+            }
+        }
+    };
 }
 
-impl Colliding<Circle> for LineSegment2 {
-    fn colliding(&self, other: &Circle) -> bool {
-        other.colliding(self)
+pub(crate) use impl_colliding;
+pub(crate) use impl_colliding_symmetric;
+
+// The narrowphase for one precision, shared by the single and double precision shapes so the two
+// can't drift apart. Segment pairs are decided exactly by their `intersection`, other lines, rays
+// and segments meet through `Parametric::intersection`.
+// Expects `POINT_TOLERANCE` in `$scalar` to be in scope where it is used.
+macro_rules! narrowphase {
+    (
+        vector: $vector:ident,
+        scalar: $scalar:ident,
+        circle: $circle:ident,
+        line: $line:ident,
+        line_ray: $line_ray:ident,
+        line_segment: $line_segment:ident,
+        polyline: $polyline:ident,
+        aabb: $aabb:ident,
+        triangle: $triangle:ident,
+        vertex_list: $vertex_list:ident $(,)?
+    ) => {
+        impl Colliding<$circle> for $circle {
+            fn colliding(&self, other: &$circle) -> bool {
+                let combined_radius = (self.radius + other.radius) * 0.5;
+                self.center.distance_squared(other.center) < combined_radius * combined_radius
+            }
+        }
+
+        impl Colliding<$line_segment> for $circle {
+            fn colliding(&self, other: &$line_segment) -> bool {
+                // TODO: optimize?
+                other.closest_point(self.center).distance(self.center) < self.radius * 0.5
+            }
+        }
+
+        impl Colliding<$circle> for $line_segment {
+            fn colliding(&self, other: &$circle) -> bool {
+                other.colliding(self)
+            }
+        }
+
+        impl Colliding<$aabb> for $aabb {
+            fn colliding(&self, other: &$aabb) -> bool {
+                let (min, max) = (self.min(), self.max());
+                let (other_min, other_max) = (other.min(), other.max());
+                min.x < other_max.x
+                    && max.x > other_min.x
+                    && min.y < other_max.y
+                    && max.y > other_min.y
+            }
+        }
+
+        type Linear = Parametric<$vector, $scalar>;
+
+        fn linear_circle(linear: &Linear, circle: &$circle) -> bool {
+            linear.closest_point(circle.center).distance(circle.center) < circle.radius * 0.5
+        }
+
+        // shapes made up of lines, rays or segments
+        trait Curve {
+            fn linears(&self) -> Vec<Linear>;
+        }
+
+        impl Curve for $line_segment {
+            fn linears(&self) -> Vec<Linear> {
+                vec![(*self).into()]
+            }
+        }
+
+        impl Curve for $line_ray {
+            fn linears(&self) -> Vec<Linear> {
+                vec![(*self).into()]
+            }
+        }
+
+        impl Curve for $line {
+            fn linears(&self) -> Vec<Linear> {
+                vec![(*self).into()]
+            }
+        }
+
+        impl Curve for $polyline {
+            fn linears(&self) -> Vec<Linear> {
+                if self.0.len() == 1 {
+                    vec![$line_segment::new(self.0[0], self.0[0]).into()]
+                } else {
+                    self.line_segments().into_iter().map(Linear::from).collect()
+                }
+            }
+        }
+
+        // filled shapes bounded by straight edges
+        trait Polygon {
+            fn polygon_vertices(&self) -> Vec<$vector>;
+            fn polygon_contains(&self, point: $vector) -> bool;
+
+            fn polygon_edges(&self) -> Vec<Linear> {
+                let vertices = self.polygon_vertices();
+                (0..vertices.len())
+                    .map(|index| {
+                        $line_segment::new(vertices[index], vertices[(index + 1) % vertices.len()])
+                            .into()
+                    })
+                    .collect()
+            }
+        }
+
+        impl Polygon for $aabb {
+            fn polygon_vertices(&self) -> Vec<$vector> {
+                self.corners().to_vec()
+            }
+
+            fn polygon_contains(&self, point: $vector) -> bool {
+                self.contains_point(point)
+            }
+        }
+
+        impl Polygon for $triangle {
+            fn polygon_vertices(&self) -> Vec<$vector> {
+                self.vertices.to_vec()
+            }
+
+            fn polygon_contains(&self, point: $vector) -> bool {
+                self.contains_point(point)
+            }
+        }
+
+        impl Polygon for $vertex_list {
+            fn polygon_vertices(&self) -> Vec<$vector> {
+                self.0.clone()
+            }
+
+            fn polygon_contains(&self, point: $vector) -> bool {
+                self.contains_point(point)
+            }
+        }
+
+        fn point_point(a: &$vector, b: &$vector) -> bool {
+            a.distance_squared(*b) <= POINT_TOLERANCE * POINT_TOLERANCE
+        }
+
+        fn point_circle(point: &$vector, circle: &$circle) -> bool {
+            circle.contains_point(*point)
+        }
+
+        fn point_curve<C: Curve>(point: &$vector, curve: &C) -> bool {
+            curve
+                .linears()
+                .iter()
+                .any(|linear| linear.colliding_point(*point))
+        }
+
+        fn point_polygon<P: Polygon>(point: &$vector, polygon: &P) -> bool {
+            polygon.polygon_contains(*point)
+        }
+
+        fn circle_curve<C: Curve>(circle: &$circle, curve: &C) -> bool {
+            curve
+                .linears()
+                .iter()
+                .any(|linear| linear_circle(linear, circle))
+        }
+
+        fn circle_polygon<P: Polygon>(circle: &$circle, polygon: &P) -> bool {
+            polygon.polygon_contains(circle.center)
+                || polygon
+                    .polygon_edges()
+                    .iter()
+                    .any(|edge| linear_circle(edge, circle))
+        }
+
+        fn segment_segment(a: &$line_segment, b: &$line_segment) -> bool {
+            a.intersection(*b) != LineIntersection::None
+        }
+
+        fn curve_curve<A: Curve, B: Curve>(a: &A, b: &B) -> bool {
+            let linears_b = b.linears();
+            a.linears().iter().any(|linear_a| {
+                linears_b
+                    .iter()
+                    .any(|linear_b| linear_a.intersects(linear_b))
+            })
+        }
+
+        fn curve_polygon<C: Curve, P: Polygon>(curve: &C, polygon: &P) -> bool {
+            let linears = curve.linears();
+            if linears
+                .first()
+                .is_some_and(|linear| polygon.polygon_contains(linear.origin))
+            {
+                return true;
+            }
+            let edges = polygon.polygon_edges();
+            linears
+                .iter()
+                .any(|linear| edges.iter().any(|edge| linear.intersects(edge)))
+        }
+
+        fn polygon_polygon<A: Polygon, B: Polygon>(a: &A, b: &B) -> bool {
+            let vertices_a = a.polygon_vertices();
+            let vertices_b = b.polygon_vertices();
+            if vertices_a.is_empty() || vertices_b.is_empty() {
+                return false;
+            }
+            if b.polygon_contains(vertices_a[0]) || a.polygon_contains(vertices_b[0]) {
+                return true;
+            }
+            let edges_b = b.polygon_edges();
+            a.polygon_edges()
+                .iter()
+                .any(|edge_a| edges_b.iter().any(|edge_b| edge_a.intersects(edge_b)))
+        }
+    };
+}
+
+pub(crate) use narrowphase;
+
+narrowphase! {
+    vector: Vec2,
+    scalar: f32,
+    circle: Circle,
+    line: Line2,
+    line_ray: LineRay2,
+    line_segment: LineSegment2,
+    polyline: Polyline,
+    aabb: Aabb,
+    triangle: Triangle2,
+    vertex_list: VertexList2,
+}
+
+impl Polygon for Obb {
+    fn polygon_vertices(&self) -> Vec<Vec2> {
+        self.corners().to_vec()
+    }
+
+    fn polygon_contains(&self, point: Vec2) -> bool {
+        self.contains_point(point)
     }
 }
 
-impl Colliding<LineSegment2> for LineSegment2 {
-    fn colliding(&self, other: &LineSegment2) -> bool {
-        fn ccw(a: Vec2, b: Vec2, c: Vec2) -> bool {
-            (c.y - a.y) * (b.x - a.x) > (b.y - a.y) * (c.x - a.x)
+impl_colliding!(Vec2, Vec2, point_point);
+impl_colliding_symmetric!(Vec2, Circle, point_circle);
+impl_colliding_symmetric!(Vec2, LineSegment2, point_curve);
+impl_colliding_symmetric!(Vec2, LineRay2, point_curve);
+impl_colliding_symmetric!(Vec2, Line2, point_curve);
+impl_colliding_symmetric!(Vec2, Polyline, point_curve);
+impl_colliding_symmetric!(Vec2, Aabb, point_polygon);
+impl_colliding_symmetric!(Vec2, Obb, point_polygon);
+impl_colliding_symmetric!(Vec2, Triangle2, point_polygon);
+impl_colliding_symmetric!(Vec2, VertexList2, point_polygon);
+
+impl_colliding_symmetric!(Circle, LineRay2, circle_curve);
+impl_colliding_symmetric!(Circle, Line2, circle_curve);
+impl_colliding_symmetric!(Circle, Polyline, circle_curve);
+impl_colliding_symmetric!(Circle, Aabb, circle_polygon);
+impl_colliding_symmetric!(Circle, Obb, circle_polygon);
+impl_colliding_symmetric!(Circle, Triangle2, circle_polygon);
+impl_colliding_symmetric!(Circle, VertexList2, circle_polygon);
+
+impl_colliding!(LineSegment2, LineSegment2, segment_segment);
+impl_colliding_symmetric!(LineSegment2, LineRay2, curve_curve);
+impl_colliding_symmetric!(LineSegment2, Line2, curve_curve);
+impl_colliding_symmetric!(LineSegment2, Polyline, curve_curve);
+impl_colliding!(LineRay2, LineRay2, curve_curve);
+impl_colliding_symmetric!(LineRay2, Line2, curve_curve);
+impl_colliding_symmetric!(LineRay2, Polyline, curve_curve);
+impl_colliding!(Line2, Line2, curve_curve);
+impl_colliding_symmetric!(Line2, Polyline, curve_curve);
+impl_colliding!(Polyline, Polyline, curve_curve);
+
+impl_colliding_symmetric!(LineSegment2, Aabb, curve_polygon);
+impl_colliding_symmetric!(LineSegment2, Obb, curve_polygon);
+impl_colliding_symmetric!(LineSegment2, Triangle2, curve_polygon);
+impl_colliding_symmetric!(LineSegment2, VertexList2, curve_polygon);
+impl_colliding_symmetric!(LineRay2, Aabb, curve_polygon);
+impl_colliding_symmetric!(LineRay2, Obb, curve_polygon);
+impl_colliding_symmetric!(LineRay2, Triangle2, curve_polygon);
+impl_colliding_symmetric!(LineRay2, VertexList2, curve_polygon);
+impl_colliding_symmetric!(Line2, Aabb, curve_polygon);
+impl_colliding_symmetric!(Line2, Obb, curve_polygon);
+impl_colliding_symmetric!(Line2, Triangle2, curve_polygon);
+impl_colliding_symmetric!(Line2, VertexList2, curve_polygon);
+impl_colliding_symmetric!(Polyline, Aabb, curve_polygon);
+impl_colliding_symmetric!(Polyline, Obb, curve_polygon);
+impl_colliding_symmetric!(Polyline, Triangle2, curve_polygon);
+impl_colliding_symmetric!(Polyline, VertexList2, curve_polygon);
+
+impl_colliding_symmetric!(Aabb, Obb, polygon_polygon);
+impl_colliding_symmetric!(Aabb, Triangle2, polygon_polygon);
+impl_colliding_symmetric!(Aabb, VertexList2, polygon_polygon);
+impl_colliding!(Obb, Obb, polygon_polygon);
+impl_colliding_symmetric!(Obb, Triangle2, polygon_polygon);
+impl_colliding_symmetric!(Obb, VertexList2, polygon_polygon);
+impl_colliding!(Triangle2, Triangle2, polygon_polygon);
+impl_colliding_symmetric!(Triangle2, VertexList2, polygon_polygon);
+impl_colliding!(VertexList2, VertexList2, polygon_polygon);
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::TAU;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    const TRIALS: usize = 300;
+    const SPACING: f32 = 0.1;
+    const MARGIN: f32 = 0.25;
+    const WINDOW: f32 = 40.;
+
+    // brute force description of a shape, independent of the code under test
+    enum Oracle {
+        Point(Vec2),
+        Circle(Vec2, f32),
+        Polygon(Vec<Vec2>),
+        // origin, unit direction, min t, max t
+        Curve(Vec<(Vec2, Vec2, f32, f32)>),
+    }
+
+    impl Oracle {
+        fn segments(vertices: &[Vec2], closed: bool) -> Vec<(Vec2, Vec2, f32, f32)> {
+            let count = if closed {
+                vertices.len()
+            } else {
+                vertices.len() - 1
+            };
+            (0..count)
+                .map(|index| {
+                    let start = vertices[index];
+                    let end = vertices[(index + 1) % vertices.len()];
+                    (
+                        start,
+                        (end - start).normalize_or_zero(),
+                        0.,
+                        start.distance(end),
+                    )
+                })
+                .collect()
+        }
+
+        fn pieces(&self) -> Vec<(Vec2, Vec2, f32, f32)> {
+            match self {
+                Oracle::Polygon(vertices) => Oracle::segments(vertices, true),
+                Oracle::Curve(pieces) => pieces.clone(),
+                _ => vec![],
+            }
+        }
+
+        fn samples(&self) -> Vec<Vec2> {
+            match self {
+                Oracle::Point(point) => vec![*point],
+                Oracle::Circle(center, radius) => {
+                    let count = (TAU * radius / SPACING).ceil() as usize;
+                    (0..count)
+                        .map(|index| {
+                            *center + Vec2::from_angle(index as f32 / count as f32 * TAU) * *radius
+                        })
+                        .collect()
+                }
+                _ => {
+                    let mut samples = vec![];
+                    for (origin, direction, min, max) in self.pieces() {
+                        let (min, max) = (min.max(-WINDOW), max.min(WINDOW));
+                        let count = ((max - min) / SPACING).ceil().max(1.) as usize;
+                        for index in 0..=count {
+                            let t = min + (max - min) * index as f32 / count as f32;
+                            samples.push(origin + direction * t);
+                        }
+                    }
+                    samples
+                }
+            }
+        }
+
+        fn unbounded(&self) -> bool {
+            self.pieces()
+                .iter()
+                .any(|(_, _, min, max)| min.is_infinite() || max.is_infinite())
+        }
+
+        fn polygon_contains(vertices: &[Vec2], point: Vec2) -> bool {
+            let mut winding = 0.;
+            for index in 0..vertices.len() {
+                let start = vertices[index] - point;
+                let end = vertices[(index + 1) % vertices.len()] - point;
+                winding += start.angle_between(end);
+            }
+            winding.abs() > 1.
         }
-        ccw(self.start, other.start, other.end) != ccw(self.end, other.start, other.end)
-            && ccw(self.start, self.end, other.start) != ccw(self.start, self.end, other.end)
+
+        fn boundary_distance(&self, point: Vec2) -> f32 {
+            match self {
+                Oracle::Point(other) => point.distance(*other),
+                Oracle::Circle(center, radius) => (point.distance(*center) - radius).abs(),
+                _ => self
+                    .pieces()
+                    .iter()
+                    .map(|(origin, direction, min, max)| {
+                        let t = (point - *origin).dot(*direction).clamp(*min, *max);
+                        point.distance(*origin + *direction * t)
+                    })
+                    .fold(f32::INFINITY, f32::min),
+            }
+        }
+
+        fn contains(&self, point: Vec2) -> bool {
+            match self {
+                Oracle::Circle(center, radius) => point.distance(*center) < *radius,
+                Oracle::Polygon(vertices) => Oracle::polygon_contains(vertices, point),
+                _ => false,
+            }
+        }
+
+        fn distance(&self, point: Vec2) -> f32 {
+            if self.contains(point) {
+                0.
+            } else {
+                self.boundary_distance(point)
+            }
+        }
+
+        fn depth(&self, point: Vec2) -> f32 {
+            if self.contains(point) {
+                self.boundary_distance(point)
+            } else {
+                0.
+            }
+        }
+
+        fn crosses(&self, start: Vec2, end: Vec2) -> bool {
+            self.pieces().iter().any(|(origin, direction, min, max)| {
+                let side_start = (start - *origin).perp_dot(*direction);
+                let side_end = (end - *origin).perp_dot(*direction);
+                if side_start.abs() < 1e-3 || side_end.abs() < 1e-3 || side_start * side_end > 0. {
+                    return false;
+                }
+                let crossing = start + (end - start) * (side_start / (side_start - side_end));
+                let t = (crossing - *origin).dot(*direction);
+                t > min + MARGIN && t < max - MARGIN
+            })
+        }
+    }
+
+    fn expected(a: &Oracle, b: &Oracle) -> Option<bool> {
+        let samples_a = a.samples();
+        let samples_b = b.samples();
+        let separation = samples_a
+            .iter()
+            .map(|sample| b.distance(*sample))
+            .chain(samples_b.iter().map(|sample| a.distance(*sample)))
+            .fold(f32::INFINITY, f32::min);
+        // samples of unbounded shapes stop at the window, so two of them may meet beyond it
+        if separation > MARGIN && !(a.unbounded() && b.unbounded()) {
+            return Some(false);
+        }
+        if samples_a.iter().any(|sample| b.depth(*sample) > MARGIN)
+            || samples_b.iter().any(|sample| a.depth(*sample) > MARGIN)
+            || samples_a
+                .windows(2)
+                .any(|window| b.crosses(window[0], window[1]))
+            || samples_b
+                .windows(2)
+                .any(|window| a.crosses(window[0], window[1]))
+        {
+            return Some(true);
+        }
+        None
+    }
+
+    trait Sample: std::fmt::Debug {
+        fn sample(rng: &mut StdRng) -> Self;
+        fn oracle(&self) -> Oracle;
+    }
+
+    fn random_point(rng: &mut StdRng, center: Vec2, extent: f32) -> Vec2 {
+        center
+            + Vec2::new(
+                rng.gen_range(-extent..extent),
+                rng.gen_range(-extent..extent),
+            )
+    }
+
+    fn random_axis(rng: &mut StdRng) -> Vec2 {
+        Vec2::from_angle(rng.gen_range(0. ..TAU))
+    }
+
+    impl Sample for Vec2 {
+        fn sample(rng: &mut StdRng) -> Vec2 {
+            random_point(rng, Vec2::ZERO, 10.)
+        }
+
+        fn oracle(&self) -> Oracle {
+            Oracle::Point(*self)
+        }
+    }
+
+    impl Sample for Circle {
+        fn sample(rng: &mut StdRng) -> Circle {
+            Circle::new(random_point(rng, Vec2::ZERO, 10.), rng.gen_range(2. ..12.))
+        }
+
+        fn oracle(&self) -> Oracle {
+            Oracle::Circle(self.center, self.radius * 0.5)
+        }
+    }
+
+    impl Sample for LineSegment2 {
+        fn sample(rng: &mut StdRng) -> LineSegment2 {
+            let start = random_point(rng, Vec2::ZERO, 10.);
+            LineSegment2::new(start, random_point(rng, start, 8.))
+        }
+
+        fn oracle(&self) -> Oracle {
+            Oracle::Curve(Oracle::segments(&[self.start, self.end], false))
+        }
+    }
+
+    impl Sample for LineRay2 {
+        fn sample(rng: &mut StdRng) -> LineRay2 {
+            LineRay2::new(random_point(rng, Vec2::ZERO, 10.), random_axis(rng))
+        }
+
+        fn oracle(&self) -> Oracle {
+            Oracle::Curve(vec![(self.start, self.axis, 0., f32::INFINITY)])
+        }
+    }
+
+    impl Sample for Line2 {
+        fn sample(rng: &mut StdRng) -> Line2 {
+            Line2::new_from_point_axis(random_point(rng, Vec2::ZERO, 10.), random_axis(rng))
+        }
+
+        fn oracle(&self) -> Oracle {
+            let normal = Vec2::new(self.a, self.b);
+            let origin = normal * self.c / normal.length_squared();
+            Oracle::Curve(vec![(
+                origin,
+                normal.perp().normalize(),
+                f32::NEG_INFINITY,
+                f32::INFINITY,
+            )])
+        }
+    }
+
+    impl Sample for Polyline {
+        fn sample(rng: &mut StdRng) -> Polyline {
+            let mut point = random_point(rng, Vec2::ZERO, 10.);
+            let mut polyline = Polyline(vec![point]);
+            for _ in 0..rng.gen_range(1..5) {
+                point = random_point(rng, point, 6.);
+                polyline.0.push(point);
+            }
+            polyline
+        }
+
+        fn oracle(&self) -> Oracle {
+            Oracle::Curve(Oracle::segments(&self.0, false))
+        }
+    }
+
+    impl Sample for Aabb {
+        fn sample(rng: &mut StdRng) -> Aabb {
+            Aabb::new(
+                random_point(rng, Vec2::ZERO, 10.),
+                Vec2::new(rng.gen_range(0.5..10.), rng.gen_range(0.5..10.)),
+            )
+        }
+
+        fn oracle(&self) -> Oracle {
+            Oracle::Polygon(self.corners().to_vec())
+        }
+    }
+
+    impl Sample for Obb {
+        fn sample(rng: &mut StdRng) -> Obb {
+            Obb::new(
+                random_point(rng, Vec2::ZERO, 10.),
+                Vec2::new(rng.gen_range(0.5..10.), rng.gen_range(0.5..10.)),
+                rng.gen_range(0. ..TAU),
+            )
+        }
+
+        fn oracle(&self) -> Oracle {
+            Oracle::Polygon(self.corners().to_vec())
+        }
+    }
+
+    impl Sample for Triangle2 {
+        fn sample(rng: &mut StdRng) -> Triangle2 {
+            let center = random_point(rng, Vec2::ZERO, 10.);
+            Triangle2::new(
+                random_point(rng, center, 8.),
+                random_point(rng, center, 8.),
+                random_point(rng, center, 8.),
+            )
+        }
+
+        fn oracle(&self) -> Oracle {
+            Oracle::Polygon(self.vertices.to_vec())
+        }
+    }
+
+    impl Sample for VertexList2 {
+        fn sample(rng: &mut StdRng) -> VertexList2 {
+            // star shaped around a center, so always simple
+            let center = random_point(rng, Vec2::ZERO, 10.);
+            let count = rng.gen_range(3..9);
+            let mut angles: Vec<f32> = (0..count).map(|_| rng.gen_range(0. ..TAU)).collect();
+            angles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            VertexList2(
+                angles
+                    .into_iter()
+                    .map(|angle| center + Vec2::from_angle(angle) * rng.gen_range(1. ..7.))
+                    .collect(),
+            )
+        }
+
+        fn oracle(&self) -> Oracle {
+            Oracle::Polygon(self.0.clone())
+        }
+    }
+
+    fn check<A, B>(seed: u64, expect_positives: bool)
+    where
+        A: Sample + Colliding<B>,
+        B: Sample + Colliding<A>,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (mut positives, mut negatives) = (0, 0);
+        let mut unbounded = false;
+        for _ in 0..TRIALS {
+            let a = A::sample(&mut rng);
+            let b = B::sample(&mut rng);
+            let colliding = a.colliding(&b);
+            assert_eq!(colliding, b.colliding(&a), "not symmetric: {:?} {:?}", a, b);
+            let (oracle_a, oracle_b) = (a.oracle(), b.oracle());
+            unbounded = oracle_a.unbounded() && oracle_b.unbounded();
+            if let Some(expected) = expected(&oracle_a, &oracle_b) {
+                assert_eq!(colliding, expected, "{:?} {:?}", a, b);
+                if expected {
+                    positives += 1;
+                } else {
+                    negatives += 1;
+                }
+            }
+        }
+        let names = (std::any::type_name::<A>(), std::any::type_name::<B>());
+        assert!(negatives > 0 || unbounded, "no negatives for {:?}", names);
+        assert!(
+            positives > 0 || !expect_positives,
+            "no positives for {:?}",
+            names
+        );
+    }
+
+    macro_rules! check_matrix {
+        ($($name:ident: $a:ty => [$($b:ty: $positives:expr),*];)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let mut seed = 0;
+                    $(
+                        seed += 1;
+                        check::<$a, $b>(seed, $positives);
+                    )*
+                }
+            )*
+        };
+    }
+
+    check_matrix! {
+        colliding_point: Vec2 => [
+            Vec2: false, Circle: true, LineSegment2: false, LineRay2: false, Line2: false,
+            Polyline: false, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+        colliding_circle: Circle => [
+            Vec2: true, Circle: true, LineSegment2: true, LineRay2: true, Line2: true,
+            Polyline: true, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+        colliding_line_segment: LineSegment2 => [
+            Vec2: false, Circle: true, LineSegment2: true, LineRay2: true, Line2: true,
+            Polyline: true, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+        colliding_line_ray: LineRay2 => [
+            Vec2: false, Circle: true, LineSegment2: true, LineRay2: true, Line2: true,
+            Polyline: true, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+        colliding_line: Line2 => [
+            Vec2: false, Circle: true, LineSegment2: true, LineRay2: true, Line2: true,
+            Polyline: true, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+        colliding_polyline: Polyline => [
+            Vec2: false, Circle: true, LineSegment2: true, LineRay2: true, Line2: true,
+            Polyline: true, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+        colliding_aabb: Aabb => [
+            Vec2: true, Circle: true, LineSegment2: true, LineRay2: true, Line2: true,
+            Polyline: true, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+        colliding_obb: Obb => [
+            Vec2: true, Circle: true, LineSegment2: true, LineRay2: true, Line2: true,
+            Polyline: true, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+        colliding_triangle: Triangle2 => [
+            Vec2: true, Circle: true, LineSegment2: true, LineRay2: true, Line2: true,
+            Polyline: true, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+        colliding_vertex_list: VertexList2 => [
+            Vec2: true, Circle: true, LineSegment2: true, LineRay2: true, Line2: true,
+            Polyline: true, Aabb: true, Obb: true, Triangle2: true, VertexList2: true
+        ];
+    }
+
+    #[test]
+    fn colliding_point_on_curves() {
+        let point = Vec2::new(2., 2.);
+        assert!(point.colliding(&LineSegment2::new(Vec2::ZERO, Vec2::new(4., 4.))));
+        assert!(point.colliding(&LineRay2::new(Vec2::ZERO, Vec2::ONE)));
+        assert!(point.colliding(&Line2::new_from_points(Vec2::ZERO, Vec2::ONE)));
+        assert!(point.colliding(&Polyline(vec![Vec2::X, Vec2::ZERO, Vec2::new(3., 3.)])));
+        assert!(!point.colliding(&LineRay2::new(Vec2::new(3., 3.), Vec2::ONE)));
+        assert!(point.colliding(&Vec2::new(2., 2.)));
+    }
+
+    #[test]
+    fn colliding_collinear_curves() {
+        let line_ray = LineRay2::new(Vec2::ZERO, Vec2::X);
+        assert!(line_ray.colliding(&LineSegment2::new(Vec2::new(5., 0.), Vec2::new(6., 0.))));
+        assert!(!line_ray.colliding(&LineSegment2::new(Vec2::new(-5., 0.), Vec2::new(-6., 0.))));
+        assert!(line_ray.colliding(&LineRay2::new(Vec2::new(-5., 0.), Vec2::X)));
+        assert!(!line_ray.colliding(&LineRay2::new(Vec2::new(-5., 0.), -Vec2::X)));
+        assert!(line_ray.colliding(&Line2::new(0., 1., 0.)));
+        assert!(!line_ray.colliding(&Line2::new(0., 1., 1.)));
+
+        let segment = LineSegment2::new(Vec2::ZERO, Vec2::new(4., 0.));
+        let overlapping = LineSegment2::new(Vec2::new(3., 0.), Vec2::new(6., 0.));
+        assert_eq!(
+            segment.intersection(overlapping),
+            LineIntersection::Coincident
+        );
+        assert!(segment.colliding(&overlapping));
+        assert!(!segment.colliding(&LineSegment2::new(Vec2::new(5., 0.), Vec2::new(6., 0.))));
+    }
+
+    #[test]
+    fn colliding_contained_shapes() {
+        let outer = VertexList2(vec![
+            Vec2::new(-10., -10.),
+            Vec2::new(10., -10.),
+            Vec2::new(10., 10.),
+            Vec2::new(-10., 10.),
+        ]);
+        assert!(outer.colliding(&Aabb::new(Vec2::ZERO, Vec2::ONE)));
+        assert!(outer.colliding(&Circle::new(Vec2::ZERO, 1.)));
+        assert!(outer.colliding(&LineSegment2::new(Vec2::ZERO, Vec2::ONE)));
+        assert!(outer.colliding(&Polyline(vec![Vec2::ZERO, Vec2::ONE, Vec2::X])));
+        assert!(Triangle2::new(Vec2::ZERO, Vec2::X, Vec2::Y).colliding(&outer));
     }
 }
@@ -0,0 +1,406 @@
+use glam::{Vec2, Vec3};
+
+use crate::geometry::{Aabb3, LineSegment3, Parametric, Plane, Ray3, Sphere, Triangle2, Triangle3};
+
+use super::{impl_colliding, impl_colliding_symmetric, Colliding, POINT_TOLERANCE};
+
+type Linear3 = Parametric<Vec3, f32>;
+
+impl Linear3 {
+    fn start(&self) -> Vec3 {
+        self.origin + self.direction * self.min
+    }
+
+    // Ericson, Real-Time Collision Detection 5.1.9, with the ranges left open
+    fn closest_points(&self, other: &Linear3) -> (Vec3, Vec3) {
+        let (a, b) = (
+            self.direction.length_squared(),
+            other.direction.length_squared(),
+        );
+        if a == 0. {
+            return (self.origin, other.closest_point(self.origin));
+        }
+        if b == 0. {
+            return (self.closest_point(other.origin), other.origin);
+        }
+        let offset = self.origin - other.origin;
+        let c = self.direction.dot(offset);
+        let f = other.direction.dot(offset);
+        let d = self.direction.dot(other.direction);
+        let denominator = a * b - d * d;
+        let mut s = if denominator > 0. {
+            ((d * f - c * b) / denominator).clamp(self.min, self.max)
+        } else {
+            self.min
+        };
+        let mut t = (d * s + f) / b;
+        if t < other.min || t > other.max {
+            t = t.clamp(other.min, other.max);
+            s = ((d * t - c) / a).clamp(self.min, self.max);
+        }
+        (
+            self.origin + self.direction * s,
+            other.origin + other.direction * t,
+        )
+    }
+
+    fn colliding_linear(&self, other: &Linear3) -> bool {
+        let (a, b) = self.closest_points(other);
+        a.distance_squared(b) <= POINT_TOLERANCE * POINT_TOLERANCE
+    }
+
+    fn colliding_sphere(&self, sphere: &Sphere) -> bool {
+        self.closest_point(sphere.center).distance(sphere.center) < sphere.radius * 0.5
+    }
+
+    fn colliding_aabb(&self, aabb: &Aabb3) -> bool {
+        let (min, max) = (aabb.min(), aabb.max());
+        let (mut entry, mut exit) = (self.min, self.max);
+        for axis in 0..3 {
+            if self.direction[axis] == 0. {
+                if self.origin[axis] <= min[axis] || self.origin[axis] >= max[axis] {
+                    return false;
+                }
+            } else {
+                let t1 = (min[axis] - self.origin[axis]) / self.direction[axis];
+                let t2 = (max[axis] - self.origin[axis]) / self.direction[axis];
+                entry = entry.max(t1.min(t2));
+                exit = exit.min(t1.max(t2));
+            }
+        }
+        entry < exit
+    }
+
+    fn colliding_plane(&self, plane: &Plane) -> bool {
+        let start = plane.signed_distance(self.start());
+        if start == 0. {
+            return true;
+        }
+        let slope = plane.normal.dot(self.direction);
+        let end = if self.max.is_finite() {
+            start + slope * (self.max - self.min)
+        } else if slope == 0. {
+            start
+        } else {
+            slope * f32::INFINITY
+        };
+        (start < 0.) != (end < 0.) || end == 0.
+    }
+
+    fn colliding_triangle(&self, triangle: &Triangle3) -> bool {
+        let (ab, ac) = (triangle.b() - triangle.a(), triangle.c() - triangle.a());
+        let p = self.direction.cross(ac);
+        let determinant = ab.dot(p);
+        if determinant == 0. {
+            // parallel to the triangle, so only touching when lying in its plane
+            let plane = triangle.plane();
+            let touching_edges = || {
+                triangle
+                    .edges()
+                    .iter()
+                    .any(|edge| self.colliding_linear(&Linear3::from(*edge)))
+            };
+            if plane.normal == Vec3::ZERO {
+                return touching_edges();
+            }
+            if plane.signed_distance(self.start()).abs() > POINT_TOLERANCE {
+                return false;
+            }
+            return triangle.closest_point(self.start()).distance(self.start()) <= POINT_TOLERANCE
+                || touching_edges();
+        }
+        let inverse = 1. / determinant;
+        let offset = self.origin - triangle.a();
+        let u = offset.dot(p) * inverse;
+        if !(0. ..=1.).contains(&u) {
+            return false;
+        }
+        let q = offset.cross(ab);
+        let v = self.direction.dot(q) * inverse;
+        if v < 0. || u + v > 1. {
+            return false;
+        }
+        self.contains(ac.dot(q) * inverse)
+    }
+}
+
+fn point_point(a: &Vec3, b: &Vec3) -> bool {
+    a.distance_squared(*b) <= POINT_TOLERANCE * POINT_TOLERANCE
+}
+
+fn point_sphere(point: &Vec3, sphere: &Sphere) -> bool {
+    sphere.contains_point(*point)
+}
+
+fn point_aabb(point: &Vec3, aabb: &Aabb3) -> bool {
+    aabb.contains_point(*point)
+}
+
+fn point_plane(point: &Vec3, plane: &Plane) -> bool {
+    plane.signed_distance(*point).abs() <= POINT_TOLERANCE
+}
+
+fn point_linear<L: Into<Linear3> + Copy>(point: &Vec3, linear: &L) -> bool {
+    (*linear).into().colliding_point(*point)
+}
+
+fn point_triangle(point: &Vec3, triangle: &Triangle3) -> bool {
+    triangle.closest_point(*point).distance_squared(*point) <= POINT_TOLERANCE * POINT_TOLERANCE
+}
+
+fn sphere_sphere(a: &Sphere, b: &Sphere) -> bool {
+    let combined_radius = (a.radius + b.radius) * 0.5;
+    a.center.distance_squared(b.center) < combined_radius * combined_radius
+}
+
+fn sphere_aabb(sphere: &Sphere, aabb: &Aabb3) -> bool {
+    aabb.closest_point(sphere.center).distance(sphere.center) < sphere.radius * 0.5
+}
+
+fn sphere_plane(sphere: &Sphere, plane: &Plane) -> bool {
+    plane.signed_distance(sphere.center).abs() < sphere.radius * 0.5
+}
+
+fn sphere_linear<L: Into<Linear3> + Copy>(sphere: &Sphere, linear: &L) -> bool {
+    (*linear).into().colliding_sphere(sphere)
+}
+
+fn sphere_triangle(sphere: &Sphere, triangle: &Triangle3) -> bool {
+    triangle
+        .closest_point(sphere.center)
+        .distance(sphere.center)
+        < sphere.radius * 0.5
+}
+
+fn aabb_aabb(a: &Aabb3, b: &Aabb3) -> bool {
+    a.min().cmplt(b.max()).all() && b.min().cmplt(a.max()).all()
+}
+
+fn aabb_plane(aabb: &Aabb3, plane: &Plane) -> bool {
+    let extent = (aabb.size * 0.5).dot(plane.normal.abs());
+    plane.signed_distance(aabb.position).abs() <= extent
+}
+
+fn aabb_linear<L: Into<Linear3> + Copy>(aabb: &Aabb3, linear: &L) -> bool {
+    (*linear).into().colliding_aabb(aabb)
+}
+
+fn aabb_triangle(aabb: &Aabb3, triangle: &Triangle3) -> bool {
+    // separating axis test over the box faces, the triangle normal and the edge cross products
+    let half_size = aabb.size * 0.5;
+    let vertices = triangle.vertices.map(|vertex| vertex - aabb.position);
+    let edges = [
+        vertices[1] - vertices[0],
+        vertices[2] - vertices[1],
+        vertices[0] - vertices[2],
+    ];
+    let mut axes = vec![Vec3::X, Vec3::Y, Vec3::Z, edges[0].cross(edges[1])];
+    for edge in edges.iter() {
+        axes.extend([Vec3::X, Vec3::Y, Vec3::Z].map(|axis| axis.cross(*edge)));
+    }
+    axes.iter()
+        .filter(|axis| axis.length_squared() > f32::EPSILON)
+        .all(|axis| {
+            let projections = vertices.map(|vertex| vertex.dot(*axis));
+            let extent = half_size.dot(axis.abs());
+            let (min, max) = (
+                projections[0].min(projections[1]).min(projections[2]),
+                projections[0].max(projections[1]).max(projections[2]),
+            );
+            min < extent && max > -extent
+        })
+}
+
+fn plane_plane(a: &Plane, b: &Plane) -> bool {
+    if a.normal.cross(b.normal).length_squared() > f32::EPSILON {
+        return true;
+    }
+    // parallel planes only meet when they are the same plane
+    let facing = a.normal.dot(b.normal).signum();
+    (a.distance - b.distance * facing).abs() <= POINT_TOLERANCE
+}
+
+fn plane_linear<L: Into<Linear3> + Copy>(plane: &Plane, linear: &L) -> bool {
+    (*linear).into().colliding_plane(plane)
+}
+
+fn plane_triangle(plane: &Plane, triangle: &Triangle3) -> bool {
+    let distances = triangle
+        .vertices
+        .map(|vertex| plane.signed_distance(vertex));
+    distances.iter().any(|distance| *distance <= 0.)
+        && distances.iter().any(|distance| *distance >= 0.)
+}
+
+fn linear_linear<A: Into<Linear3> + Copy, B: Into<Linear3> + Copy>(a: &A, b: &B) -> bool {
+    (*a).into().colliding_linear(&(*b).into())
+}
+
+fn linear_triangle<L: Into<Linear3> + Copy>(linear: &L, triangle: &Triangle3) -> bool {
+    (*linear).into().colliding_triangle(triangle)
+}
+
+fn triangle_triangle(a: &Triangle3, b: &Triangle3) -> bool {
+    let normal = a.normal();
+    if normal != Vec3::ZERO && normal.cross(b.normal()).length_squared() <= f32::EPSILON {
+        if a.plane().signed_distance(b.a()).abs() > POINT_TOLERANCE {
+            return false;
+        }
+        // coplanar, so drop the axis the normal leans on most and compare in 2D
+        let normal = normal.abs();
+        let flatten = |vertex: Vec3| {
+            if normal.x >= normal.y && normal.x >= normal.z {
+                Vec2::new(vertex.y, vertex.z)
+            } else if normal.y >= normal.z {
+                Vec2::new(vertex.z, vertex.x)
+            } else {
+                Vec2::new(vertex.x, vertex.y)
+            }
+        };
+        let flat = |triangle: &Triangle3| {
+            Triangle2::new(
+                flatten(triangle.a()),
+                flatten(triangle.b()),
+                flatten(triangle.c()),
+            )
+        };
+        return flat(a).colliding(&flat(b));
+    }
+    a.edges().iter().any(|edge| linear_triangle(edge, b))
+        || b.edges().iter().any(|edge| linear_triangle(edge, a))
+}
+
+impl_colliding!(Vec3, Vec3, point_point);
+impl_colliding_symmetric!(Vec3, Sphere, point_sphere);
+impl_colliding_symmetric!(Vec3, Aabb3, point_aabb);
+impl_colliding_symmetric!(Vec3, Plane, point_plane);
+impl_colliding_symmetric!(Vec3, Ray3, point_linear);
+impl_colliding_symmetric!(Vec3, LineSegment3, point_linear);
+impl_colliding_symmetric!(Vec3, Triangle3, point_triangle);
+
+impl_colliding!(Sphere, Sphere, sphere_sphere);
+impl_colliding_symmetric!(Sphere, Aabb3, sphere_aabb);
+impl_colliding_symmetric!(Sphere, Plane, sphere_plane);
+impl_colliding_symmetric!(Sphere, Ray3, sphere_linear);
+impl_colliding_symmetric!(Sphere, LineSegment3, sphere_linear);
+impl_colliding_symmetric!(Sphere, Triangle3, sphere_triangle);
+
+impl_colliding!(Aabb3, Aabb3, aabb_aabb);
+impl_colliding_symmetric!(Aabb3, Plane, aabb_plane);
+impl_colliding_symmetric!(Aabb3, Ray3, aabb_linear);
+impl_colliding_symmetric!(Aabb3, LineSegment3, aabb_linear);
+impl_colliding_symmetric!(Aabb3, Triangle3, aabb_triangle);
+
+impl_colliding!(Plane, Plane, plane_plane);
+impl_colliding_symmetric!(Plane, Ray3, plane_linear);
+impl_colliding_symmetric!(Plane, LineSegment3, plane_linear);
+impl_colliding_symmetric!(Plane, Triangle3, plane_triangle);
+
+impl_colliding!(Ray3, Ray3, linear_linear);
+impl_colliding_symmetric!(Ray3, LineSegment3, linear_linear);
+impl_colliding_symmetric!(Ray3, Triangle3, linear_triangle);
+impl_colliding!(LineSegment3, LineSegment3, linear_linear);
+impl_colliding_symmetric!(LineSegment3, Triangle3, linear_triangle);
+
+impl_colliding!(Triangle3, Triangle3, triangle_triangle);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> Triangle3 {
+        Triangle3::new(Vec3::ZERO, Vec3::new(4., 0., 0.), Vec3::new(0., 4., 0.))
+    }
+
+    #[test]
+    fn colliding_3d_queries() {
+        let aabb = Aabb3::new(Vec3::ZERO, Vec3::splat(2.));
+        let sphere = Sphere::new(Vec3::new(1.5, 0., 0.), 2.);
+        assert!(sphere.colliding(&aabb));
+        assert!(!sphere.colliding(&Aabb3::new(Vec3::new(-1., 0., 0.), Vec3::ONE)));
+        assert!(aabb.colliding(&Aabb3::new(Vec3::splat(1.5), Vec3::splat(2.))));
+        assert!(!aabb.colliding(&Aabb3::new(Vec3::splat(2.), Vec3::splat(2.))));
+
+        let floor = Plane::new_from_point_normal(Vec3::new(0., -1., 0.), Vec3::Y);
+        assert!(aabb.colliding(&floor));
+        assert!(!sphere.colliding(&floor));
+        assert!(floor.colliding(&Plane::new(Vec3::X, 3.)));
+        assert!(!floor.colliding(&Plane::new(Vec3::NEG_Y, 2.)));
+        assert!(floor.colliding(&Plane::new(Vec3::NEG_Y, 1.)));
+
+        let down = Ray3::new(Vec3::new(0.5, 5., 0.5), Vec3::NEG_Y);
+        assert!(down.colliding(&aabb));
+        assert!(down.colliding(&floor));
+        assert!(!Ray3::new(Vec3::new(0.5, 5., 0.5), Vec3::Y).colliding(&aabb));
+        assert!(!down.segment(3.).colliding(&aabb));
+        assert!(down.segment(5.).colliding(&aabb));
+
+        let crossing = LineSegment3::new(Vec3::new(-1., 0., 1.), Vec3::new(1., 0., -1.));
+        assert!(crossing.colliding(&LineSegment3::new(Vec3::new(0., -1., 0.), Vec3::Y)));
+        assert!(!crossing.colliding(&LineSegment3::new(Vec3::new(0., 0.1, -1.), Vec3::Z)));
+    }
+
+    #[test]
+    fn colliding_3d_triangles() {
+        let triangle = triangle();
+        assert!(Vec3::new(1., 1., 0.).colliding(&triangle));
+        assert!(!Vec3::new(1., 1., 0.1).colliding(&triangle));
+        assert!(Ray3::new(Vec3::new(1., 1., 3.), Vec3::NEG_Z).colliding(&triangle));
+        assert!(!Ray3::new(Vec3::new(3., 3., 3.), Vec3::NEG_Z).colliding(&triangle));
+        // lying in the triangle's plane
+        assert!(
+            LineSegment3::new(Vec3::new(-1., 1., 0.), Vec3::new(5., 1., 0.)).colliding(&triangle)
+        );
+        assert!(Aabb3::new(Vec3::new(1., 1., 0.), Vec3::splat(0.5)).colliding(&triangle));
+        assert!(!Aabb3::new(Vec3::new(3., 3., 0.), Vec3::splat(0.5)).colliding(&triangle));
+        assert!(!Aabb3::new(Vec3::new(1., 1., 1.), Vec3::splat(0.5)).colliding(&triangle));
+        assert!(Sphere::new(Vec3::new(1., 1., 0.4), 1.).colliding(&triangle));
+        assert!(triangle.colliding(&Plane::new(Vec3::X, 1.)));
+        assert!(!triangle.colliding(&Plane::new(Vec3::X, 5.)));
+
+        let piercing = Triangle3::new(
+            Vec3::new(1., 1., -1.),
+            Vec3::new(1., 1., 1.),
+            Vec3::new(1., 5., 0.),
+        );
+        assert!(triangle.colliding(&piercing));
+        let above = Triangle3::new(
+            Vec3::new(1., 1., 0.5),
+            Vec3::new(1., 1., 1.),
+            Vec3::new(1., 5., 0.7),
+        );
+        assert!(!triangle.colliding(&above));
+        let coplanar = Triangle3::new(
+            Vec3::new(0.5, 0.5, 0.),
+            Vec3::new(1., 0.5, 0.),
+            Vec3::new(0.5, 1., 0.),
+        );
+        assert!(triangle.colliding(&coplanar));
+        assert!(coplanar.colliding(&triangle));
+    }
+
+    #[test]
+    fn ray_entry_distances_3d() {
+        let ray = Ray3::new(Vec3::new(0., 0., -10.), Vec3::Z);
+        assert_eq!(
+            Aabb3::new(Vec3::ZERO, Vec3::splat(2.)).ray_entry_distance(ray),
+            Some(9.)
+        );
+        assert_eq!(
+            Sphere::new(Vec3::ZERO, 4.).ray_entry_distance(ray),
+            Some(8.)
+        );
+        assert_eq!(Plane::new(Vec3::Z, 1.).ray_entry_distance(ray), Some(11.));
+        assert_eq!(Plane::new(Vec3::Z, -11.).ray_entry_distance(ray), None);
+        let triangle = Triangle3::new(
+            Vec3::new(-1., -1., 2.),
+            Vec3::new(1., -1., 2.),
+            Vec3::new(0., 1., 2.),
+        );
+        assert_eq!(triangle.ray_entry_distance(ray), Some(12.));
+        assert_eq!(
+            Sphere::new(Vec3::new(0., 5., 0.), 4.).ray_entry_distance(ray),
+            None
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use glam::DVec2;
+
+use crate::{
+    collision::{impl_colliding, impl_colliding_symmetric, narrowphase, Colliding},
+    geometry::{
+        DAabb, DCircle, DLine2, DLineRay2, DLineSegment2, DPolyline, DTriangle2, DVertexList2,
+        LineIntersection, Parametric,
+    },
+};
+
+const POINT_TOLERANCE: f64 = crate::collision::POINT_TOLERANCE as f64;
+
+narrowphase! {
+    vector: DVec2,
+    scalar: f64,
+    circle: DCircle,
+    line: DLine2,
+    line_ray: DLineRay2,
+    line_segment: DLineSegment2,
+    polyline: DPolyline,
+    aabb: DAabb,
+    triangle: DTriangle2,
+    vertex_list: DVertexList2,
+}
+
+impl_colliding!(DVec2, DVec2, point_point);
+impl_colliding_symmetric!(DVec2, DCircle, point_circle);
+impl_colliding_symmetric!(DVec2, DLineSegment2, point_curve);
+impl_colliding_symmetric!(DVec2, DLineRay2, point_curve);
+impl_colliding_symmetric!(DVec2, DLine2, point_curve);
+impl_colliding_symmetric!(DVec2, DPolyline, point_curve);
+impl_colliding_symmetric!(DVec2, DAabb, point_polygon);
+impl_colliding_symmetric!(DVec2, DTriangle2, point_polygon);
+impl_colliding_symmetric!(DVec2, DVertexList2, point_polygon);
+
+impl_colliding_symmetric!(DCircle, DLineRay2, circle_curve);
+impl_colliding_symmetric!(DCircle, DLine2, circle_curve);
+impl_colliding_symmetric!(DCircle, DPolyline, circle_curve);
+impl_colliding_symmetric!(DCircle, DAabb, circle_polygon);
+impl_colliding_symmetric!(DCircle, DTriangle2, circle_polygon);
+impl_colliding_symmetric!(DCircle, DVertexList2, circle_polygon);
+
+impl_colliding!(DLineSegment2, DLineSegment2, segment_segment);
+impl_colliding_symmetric!(DLineSegment2, DLineRay2, curve_curve);
+impl_colliding_symmetric!(DLineSegment2, DLine2, curve_curve);
+impl_colliding_symmetric!(DLineSegment2, DPolyline, curve_curve);
+impl_colliding!(DLineRay2, DLineRay2, curve_curve);
+impl_colliding_symmetric!(DLineRay2, DLine2, curve_curve);
+impl_colliding_symmetric!(DLineRay2, DPolyline, curve_curve);
+impl_colliding!(DLine2, DLine2, curve_curve);
+impl_colliding_symmetric!(DLine2, DPolyline, curve_curve);
+impl_colliding!(DPolyline, DPolyline, curve_curve);
+
+impl_colliding_symmetric!(DLineSegment2, DAabb, curve_polygon);
+impl_colliding_symmetric!(DLineSegment2, DTriangle2, curve_polygon);
+impl_colliding_symmetric!(DLineSegment2, DVertexList2, curve_polygon);
+impl_colliding_symmetric!(DLineRay2, DAabb, curve_polygon);
+impl_colliding_symmetric!(DLineRay2, DTriangle2, curve_polygon);
+impl_colliding_symmetric!(DLineRay2, DVertexList2, curve_polygon);
+impl_colliding_symmetric!(DLine2, DAabb, curve_polygon);
+impl_colliding_symmetric!(DLine2, DTriangle2, curve_polygon);
+impl_colliding_symmetric!(DLine2, DVertexList2, curve_polygon);
+impl_colliding_symmetric!(DPolyline, DAabb, curve_polygon);
+impl_colliding_symmetric!(DPolyline, DTriangle2, curve_polygon);
+impl_colliding_symmetric!(DPolyline, DVertexList2, curve_polygon);
+
+impl_colliding_symmetric!(DAabb, DTriangle2, polygon_polygon);
+impl_colliding_symmetric!(DAabb, DVertexList2, polygon_polygon);
+impl_colliding!(DTriangle2, DTriangle2, polygon_polygon);
+impl_colliding_symmetric!(DTriangle2, DVertexList2, polygon_polygon);
+impl_colliding!(DVertexList2, DVertexList2, polygon_polygon);
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec2;
+
+    use crate::geometry::{Aabb, Circle, LineSegment2, Triangle2};
+
+    use super::*;
+
+    #[test]
+    fn colliding_matches_single_precision() {
+        let circle = Circle::new(Vec2::new(1., 2.), 3.);
+        let segment = LineSegment2::new(Vec2::new(-2., 0.), Vec2::new(3., 3.));
+        let aabb = Aabb::new(Vec2::new(2., 1.), Vec2::new(2., 1.));
+        let triangle = Triangle2::new(Vec2::ZERO, Vec2::new(4., 0.), Vec2::new(0., 4.));
+        for point in [Vec2::new(0.5, 0.5), Vec2::new(3., 3.), Vec2::new(1., 3.)] {
+            assert_eq!(
+                point.as_dvec2().colliding(&DCircle::from(circle)),
+                point.colliding(&circle)
+            );
+            assert_eq!(
+                point.as_dvec2().colliding(&DTriangle2::from(triangle)),
+                point.colliding(&triangle)
+            );
+        }
+        assert_eq!(
+            DLineSegment2::from(segment).colliding(&DAabb::from(aabb)),
+            segment.colliding(&aabb)
+        );
+        assert_eq!(
+            DTriangle2::from(triangle).colliding(&DCircle::from(circle)),
+            triangle.colliding(&circle)
+        );
+    }
+
+    #[test]
+    fn colliding_far_from_origin() {
+        let offset = DVec2::splat(1e9);
+        let a = DAabb::new(offset, DVec2::splat(200.));
+        let b = DAabb::new(offset + DVec2::new(200.5, 0.), DVec2::splat(200.));
+        assert!(!a.colliding(&b));
+        // single precision rounds the gap between the boxes away
+        assert!(a.as_aabb().colliding(&b.as_aabb()));
+
+        let segment = DLineSegment2::new(offset, offset + DVec2::new(1., 1.));
+        let other = DLineSegment2::new(offset + DVec2::new(0., 1.), offset + DVec2::new(1., 0.));
+        assert!(segment.colliding(&other));
+        let triangle = DTriangle2::new(offset, offset + DVec2::X, offset + DVec2::Y);
+        assert!((offset + DVec2::splat(0.25)).colliding(&triangle));
+        assert!(!(offset + DVec2::splat(0.75)).colliding(&triangle));
+    }
+}
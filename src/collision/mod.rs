@@ -1,3 +1,15 @@
+mod aabb_tree;
 mod colliding;
+mod colliding3;
+mod double;
+mod quadtree;
+mod spatial_hash;
+mod sweep_and_prune;
+mod time_of_impact;
 
+pub use aabb_tree::*;
 pub use colliding::*;
+pub use quadtree::*;
+pub use spatial_hash::*;
+pub use sweep_and_prune::*;
+pub use time_of_impact::*;
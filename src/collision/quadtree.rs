@@ -0,0 +1,320 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use glam::Vec2;
+
+use crate::{
+    geometry::{Aabb, LineRay2},
+    utility::SortableWith,
+};
+
+use super::Colliding;
+
+/// Identifies an item in a [`Quadtree`] by its position in the list it was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuadtreeKey(pub usize);
+
+#[derive(Debug, Clone)]
+struct QuadtreeNode {
+    cell: Aabb,
+    // the cell grown by half its size, which contains everything stored beneath it
+    loose: Aabb,
+    depth: usize,
+    items: Vec<usize>,
+    children: [Option<usize>; 4],
+}
+
+#[derive(Debug, Clone, Copy)]
+enum QuadtreeEntry {
+    Node(usize),
+    Item(usize),
+}
+
+/// A loose quadtree built once over a list of [`Aabb`]s, suited to static level geometry.
+///
+/// Every item lives in the deepest cell that holds its center and is at least as large as it,
+/// so items never need to be split across cells.
+#[derive(Debug, Clone)]
+pub struct Quadtree<T> {
+    items: Vec<(Aabb, T)>,
+    nodes: Vec<QuadtreeNode>,
+    node_capacity: usize,
+    max_depth: usize,
+}
+
+impl<T> Quadtree<T> {
+    pub const DEFAULT_NODE_CAPACITY: usize = 8;
+    pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+    pub fn new(items: Vec<(Aabb, T)>) -> Quadtree<T> {
+        Quadtree::new_with_limits(items, Self::DEFAULT_NODE_CAPACITY, Self::DEFAULT_MAX_DEPTH)
+    }
+
+    /// Builds a tree whose nodes split once they hold more than `node_capacity` items, down to
+    /// `max_depth` levels below the root.
+    pub fn new_with_limits(
+        items: Vec<(Aabb, T)>,
+        node_capacity: usize,
+        max_depth: usize,
+    ) -> Quadtree<T> {
+        let mut quadtree = Quadtree {
+            items,
+            nodes: vec![],
+            node_capacity,
+            max_depth,
+        };
+        if let Some(bounds) = quadtree
+            .items
+            .iter()
+            .map(|(aabb, _)| *aabb)
+            .reduce(|bounds, aabb| bounds.union(aabb))
+        {
+            let root = Aabb::new(bounds.position, Vec2::splat(bounds.size.max_element()));
+            quadtree.build_node(root, 0, (0..quadtree.items.len()).collect());
+        }
+        quadtree
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, key: QuadtreeKey) -> Option<&T> {
+        Some(&self.items.get(key.0)?.1)
+    }
+
+    pub fn aabb(&self, key: QuadtreeKey) -> Option<Aabb> {
+        Some(self.items.get(key.0)?.0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (QuadtreeKey, &T)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(index, (_, item))| (QuadtreeKey(index), item))
+    }
+
+    /// Cells of every node along with its depth, root first.
+    pub fn nodes(&self) -> Vec<(Aabb, usize)> {
+        self.nodes
+            .iter()
+            .map(|node| (node.cell, node.depth))
+            .collect()
+    }
+
+    pub fn query_aabb(&self, aabb: Aabb) -> Vec<QuadtreeKey> {
+        self.query(|bounds| bounds.colliding(&aabb))
+    }
+
+    pub fn query_point(&self, point: Vec2) -> Vec<QuadtreeKey> {
+        bodge_assert!(point.is_finite());
+        self.query(|bounds| bounds.contains_point(point))
+    }
+
+    /// Items whose [`Aabb`] the ray passes through within `max_distance`, nearest first.
+    pub fn query_ray(&self, ray: LineRay2, max_distance: f32) -> Vec<QuadtreeKey> {
+        bodge_assert!(max_distance >= 0.);
+        let hit = |aabb: &Aabb| {
+            aabb.ray_entry_distance(ray)
+                .filter(|distance| *distance <= max_distance)
+        };
+        let mut hits: Vec<SortableWith<f32, QuadtreeKey>> = self
+            .query(|bounds| hit(bounds).is_some())
+            .into_iter()
+            .map(|key| SortableWith(hit(&self.items[key.0].0).unwrap(), key))
+            .collect();
+        hits.sort();
+        hits.into_iter().map(|hit| hit.1).collect()
+    }
+
+    /// The `k` items whose [`Aabb`]s are closest to a point, nearest first.
+    pub fn nearest(&self, point: Vec2, k: usize) -> Vec<QuadtreeKey> {
+        bodge_assert!(point.is_finite());
+        let distance = |aabb: &Aabb| aabb.closest_point(point).distance(point);
+        let mut nearest = vec![];
+        let mut heap = BinaryHeap::new();
+        if !self.nodes.is_empty() {
+            heap.push(Reverse(SortableWith(0., QuadtreeEntry::Node(0))));
+        }
+        while let Some(Reverse(SortableWith(_, entry))) = heap.pop() {
+            if nearest.len() >= k {
+                break;
+            }
+            match entry {
+                QuadtreeEntry::Item(index) => nearest.push(QuadtreeKey(index)),
+                QuadtreeEntry::Node(index) => {
+                    let node = &self.nodes[index];
+                    for item in node.items.iter() {
+                        heap.push(Reverse(SortableWith(
+                            distance(&self.items[*item].0),
+                            QuadtreeEntry::Item(*item),
+                        )));
+                    }
+                    for child in node.children.iter().flatten() {
+                        heap.push(Reverse(SortableWith(
+                            distance(&self.nodes[*child].loose),
+                            QuadtreeEntry::Node(*child),
+                        )));
+                    }
+                }
+            }
+        }
+        nearest
+    }
+
+    fn query(&self, mut overlaps: impl FnMut(&Aabb) -> bool) -> Vec<QuadtreeKey> {
+        let mut found = vec![];
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !overlaps(&node.loose) {
+                continue;
+            }
+            for item in node.items.iter() {
+                if overlaps(&self.items[*item].0) {
+                    found.push(QuadtreeKey(*item));
+                }
+            }
+            stack.extend(node.children.iter().flatten());
+        }
+        found.sort();
+        found
+    }
+
+    fn build_node(&mut self, cell: Aabb, depth: usize, items: Vec<usize>) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(QuadtreeNode {
+            cell,
+            loose: cell.expanded(cell.size.x * 0.5),
+            depth,
+            items: vec![],
+            children: [None; 4],
+        });
+        if items.len() <= self.node_capacity || depth >= self.max_depth {
+            self.nodes[index].items = items;
+            return index;
+        }
+
+        let child_size = cell.size * 0.5;
+        let mut node_items = vec![];
+        let mut child_items: [Vec<usize>; 4] = Default::default();
+        for item in items {
+            let aabb = self.items[item].0;
+            if aabb.size.x <= child_size.x && aabb.size.y <= child_size.y {
+                let quadrant = (aabb.position.x >= cell.position.x) as usize
+                    + (aabb.position.y >= cell.position.y) as usize * 2;
+                child_items[quadrant].push(item);
+            } else {
+                node_items.push(item);
+            }
+        }
+        self.nodes[index].items = node_items;
+
+        for (quadrant, items) in child_items.into_iter().enumerate() {
+            if items.is_empty() {
+                continue;
+            }
+            let offset = Vec2::new(
+                if quadrant % 2 == 0 { -0.25 } else { 0.25 },
+                if quadrant / 2 == 0 { -0.25 } else { 0.25 },
+            );
+            let child_cell = Aabb::new(cell.position + cell.size * offset, child_size);
+            let child = self.build_node(child_cell, depth + 1, items);
+            self.nodes[index].children[quadrant] = Some(child);
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scattered(count: usize) -> Vec<(Aabb, usize)> {
+        (0..count)
+            .map(|index| {
+                let position = Vec2::new(
+                    (index as f32 * 7.31).sin() * 80.,
+                    (index as f32 * 3.17).cos() * 80.,
+                );
+                let size = Vec2::new(0.5 + (index % 9) as f32, 0.5 + (index % 4) as f32 * 6.);
+                (Aabb::new(position, size), index)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn quadtree_queries_match_brute_force() {
+        let items = scattered(400);
+        let quadtree = Quadtree::new(items.clone());
+        assert!(quadtree.nodes().len() > 1);
+        for step in 0..50 {
+            let point = Vec2::new(step as f32 * 3. - 75., (step as f32).sin() * 60.);
+
+            let region = Aabb::new(point, Vec2::new(14., 6.));
+            let expected: Vec<QuadtreeKey> = items
+                .iter()
+                .filter(|(aabb, _)| aabb.colliding(&region))
+                .map(|(_, index)| QuadtreeKey(*index))
+                .collect();
+            assert_eq!(quadtree.query_aabb(region), expected);
+
+            let expected: Vec<QuadtreeKey> = items
+                .iter()
+                .filter(|(aabb, _)| aabb.contains_point(point))
+                .map(|(_, index)| QuadtreeKey(*index))
+                .collect();
+            assert_eq!(quadtree.query_point(point), expected);
+
+            let ray = LineRay2::new(point, Vec2::from_angle(step as f32));
+            let expected = items
+                .iter()
+                .filter(|(aabb, _)| {
+                    aabb.ray_entry_distance(ray)
+                        .is_some_and(|distance| distance <= 40.)
+                })
+                .count();
+            assert_eq!(quadtree.query_ray(ray, 40.).len(), expected);
+
+            let distance = |aabb: Aabb| aabb.closest_point(point).distance(point);
+            let mut distances: Vec<f32> = items.iter().map(|(aabb, _)| distance(*aabb)).collect();
+            distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let nearest: Vec<f32> = quadtree
+                .nearest(point, 5)
+                .into_iter()
+                .map(|key| distance(quadtree.aabb(key).unwrap()))
+                .collect();
+            assert_eq!(nearest, distances[0..5]);
+        }
+    }
+
+    #[test]
+    fn quadtree_respects_limits() {
+        let mut items: Vec<(Aabb, ())> = (0..100)
+            .map(|index| {
+                (
+                    Aabb::new(Vec2::new(index as f32 * 1e-3, 0.), Vec2::splat(1e-4)),
+                    (),
+                )
+            })
+            .collect();
+        items.push((Aabb::new(Vec2::splat(100.), Vec2::ONE), ()));
+        let quadtree = Quadtree::new_with_limits(items, 4, 3);
+        assert!(quadtree.nodes().iter().all(|(_, depth)| *depth <= 3));
+        assert!(quadtree.nodes().iter().any(|(_, depth)| *depth == 3));
+        assert_eq!(
+            quadtree.query_aabb(Aabb::new(Vec2::ZERO, Vec2::ONE)).len(),
+            100
+        );
+        assert!(Quadtree::<()>::new(vec![])
+            .nearest(Vec2::ZERO, 3)
+            .is_empty());
+    }
+}
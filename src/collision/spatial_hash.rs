@@ -0,0 +1,368 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::{IVec2, Vec2};
+
+use crate::{
+    geometry::{Aabb, LineRay2, Raster},
+    utility::SortableWith,
+};
+
+use super::Colliding;
+
+/// Identifies an item stored in a [`SpatialHash`]. Keys of removed items may be handed out again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpatialHashKey(usize);
+
+#[derive(Debug, Clone)]
+struct SpatialHashEntry<T> {
+    aabb: Aabb,
+    min_cell: IVec2,
+    max_cell: IVec2,
+    item: T,
+}
+
+/// A uniform grid broadphase which buckets items into every cell their [`Aabb`] touches.
+#[derive(Debug, Clone)]
+pub struct SpatialHash<T> {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<SpatialHashKey>>,
+    entries: Vec<Option<SpatialHashEntry<T>>>,
+    free: Vec<usize>,
+    // every cell that has held an item since the last clear lies within these
+    occupied: Option<(IVec2, IVec2)>,
+}
+
+impl<T> SpatialHash<T> {
+    pub fn new(cell_size: f32) -> SpatialHash<T> {
+        bodge_assert!(cell_size.is_finite());
+        bodge_assert!(cell_size > 0.);
+        SpatialHash {
+            cell_size,
+            cells: HashMap::new(),
+            entries: Vec::new(),
+            free: Vec::new(),
+            occupied: None,
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn cell(&self, point: Vec2) -> IVec2 {
+        (point / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+        self.free.clear();
+        self.occupied = None;
+    }
+
+    pub fn insert(&mut self, aabb: Aabb, item: T) -> SpatialHashKey {
+        let key = if let Some(index) = self.free.pop() {
+            SpatialHashKey(index)
+        } else {
+            self.entries.push(None);
+            SpatialHashKey(self.entries.len() - 1)
+        };
+        let (min_cell, max_cell) = (self.cell(aabb.min()), self.cell(aabb.max()));
+        self.add_to_cells(key, min_cell, max_cell);
+        self.entries[key.0] = Some(SpatialHashEntry {
+            aabb,
+            min_cell,
+            max_cell,
+            item,
+        });
+        key
+    }
+
+    pub fn remove(&mut self, key: SpatialHashKey) -> Option<T> {
+        let entry = self.entries.get_mut(key.0)?.take()?;
+        self.remove_from_cells(key, entry.min_cell, entry.max_cell);
+        self.free.push(key.0);
+        Some(entry.item)
+    }
+
+    /// Moves an item to a new [`Aabb`], only touching the cells it entered or left.
+    pub fn update(&mut self, key: SpatialHashKey, aabb: Aabb) {
+        let (min_cell, max_cell) = (self.cell(aabb.min()), self.cell(aabb.max()));
+        let Some(entry) = self.entries.get(key.0).and_then(Option::as_ref) else {
+            bodge_assert!(false, "updated a key that is not in the spatial hash");
+            return;
+        };
+        let (old_min_cell, old_max_cell) = (entry.min_cell, entry.max_cell);
+        if old_min_cell != min_cell || old_max_cell != max_cell {
+            self.remove_from_cells(key, old_min_cell, old_max_cell);
+            self.add_to_cells(key, min_cell, max_cell);
+        }
+        let entry = self.entries[key.0].as_mut().unwrap();
+        entry.aabb = aabb;
+        entry.min_cell = min_cell;
+        entry.max_cell = max_cell;
+    }
+
+    pub fn get(&self, key: SpatialHashKey) -> Option<&T> {
+        Some(&self.entries.get(key.0)?.as_ref()?.item)
+    }
+
+    pub fn get_mut(&mut self, key: SpatialHashKey) -> Option<&mut T> {
+        Some(&mut self.entries.get_mut(key.0)?.as_mut()?.item)
+    }
+
+    pub fn aabb(&self, key: SpatialHashKey) -> Option<Aabb> {
+        Some(self.entries.get(key.0)?.as_ref()?.aabb)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SpatialHashKey, &T)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| Some((SpatialHashKey(index), &entry.as_ref()?.item)))
+    }
+
+    pub fn query_point(&self, point: Vec2) -> Vec<SpatialHashKey> {
+        bodge_assert!(point.is_finite());
+        let Some(keys) = self.cells.get(&self.cell(point)) else {
+            return vec![];
+        };
+        keys.iter()
+            .copied()
+            .filter(|key| self.entry(*key).aabb.contains_point(point))
+            .collect()
+    }
+
+    pub fn query_aabb(&self, aabb: Aabb) -> Vec<SpatialHashKey> {
+        let (min_cell, max_cell) = (self.cell(aabb.min()), self.cell(aabb.max()));
+        let mut found = HashSet::new();
+        for x in min_cell.x..=max_cell.x {
+            for y in min_cell.y..=max_cell.y {
+                if let Some(keys) = self.cells.get(&IVec2::new(x, y)) {
+                    found.extend(
+                        keys.iter()
+                            .copied()
+                            .filter(|key| self.entry(*key).aabb.colliding(&aabb)),
+                    );
+                }
+            }
+        }
+        let mut found: Vec<SpatialHashKey> = found.into_iter().collect();
+        found.sort();
+        found
+    }
+
+    /// Walks the cells along a ray and returns the items it passes through, nearest first.
+    pub fn query_ray(&self, ray: LineRay2, max_distance: f32) -> Vec<SpatialHashKey> {
+        bodge_assert!(max_distance >= 0.);
+        let mut found = HashSet::new();
+        let mut hits = vec![];
+        for cell in self.ray_cells(ray, max_distance) {
+            if let Some(keys) = self.cells.get(&cell) {
+                for key in keys.iter() {
+                    if found.insert(*key) {
+                        if let Some(distance) = self.entry(*key).aabb.ray_entry_distance(ray) {
+                            if distance <= max_distance {
+                                hits.push(SortableWith(distance, *key));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        hits.sort();
+        hits.into_iter().map(|hit| hit.1).collect()
+    }
+
+    /// Cells visited by a ray in order, walked with [`Raster::traverse`]. The walk stops once the ray
+    /// leaves the cells that have held items since the last clear, so `max_distance` may be
+    /// infinite.
+    pub fn ray_cells(&self, ray: LineRay2, max_distance: f32) -> Vec<IVec2> {
+        bodge_assert!(ray.start.is_finite() && ray.axis.is_finite());
+        bodge_assert!(max_distance >= 0.);
+        let Some((entry, exit)) = self.occupied_span(ray, max_distance) else {
+            return vec![];
+        };
+        Raster::new(Vec2::splat(self.cell_size))
+            .traverse(ray.start + ray.axis * entry, ray.start + ray.axis * exit)
+    }
+
+    /// Pairs of items whose [`Aabb`]s overlap, each reported once with the lower key first.
+    pub fn pairs(&self) -> Vec<(SpatialHashKey, SpatialHashKey)> {
+        let mut pairs = HashSet::new();
+        for keys in self.cells.values() {
+            for (index, key) in keys.iter().enumerate() {
+                for other_key in keys.iter().skip(index + 1) {
+                    let pair = if key < other_key {
+                        (*key, *other_key)
+                    } else {
+                        (*other_key, *key)
+                    };
+                    if !pairs.contains(&pair)
+                        && self
+                            .entry(*key)
+                            .aabb
+                            .colliding(&self.entry(*other_key).aabb)
+                    {
+                        pairs.insert(pair);
+                    }
+                }
+            }
+        }
+        let mut pairs: Vec<(SpatialHashKey, SpatialHashKey)> = pairs.into_iter().collect();
+        pairs.sort();
+        pairs
+    }
+
+    fn entry(&self, key: SpatialHashKey) -> &SpatialHashEntry<T> {
+        self.entries[key.0].as_ref().unwrap()
+    }
+
+    // the part of a ray within both `max_distance` and the occupied cells, `None` if it misses
+    fn occupied_span(&self, ray: LineRay2, max_distance: f32) -> Option<(f32, f32)> {
+        let (min_cell, max_cell) = self.occupied?;
+        let min = min_cell.as_vec2() * self.cell_size;
+        let max = (max_cell + 1).as_vec2() * self.cell_size;
+        let (mut entry, mut exit) = (0f32, max_distance);
+        for axis in 0..2 {
+            if ray.axis[axis] == 0. {
+                if ray.start[axis] < min[axis] || ray.start[axis] > max[axis] {
+                    return None;
+                }
+            } else {
+                let t1 = (min[axis] - ray.start[axis]) / ray.axis[axis];
+                let t2 = (max[axis] - ray.start[axis]) / ray.axis[axis];
+                entry = entry.max(t1.min(t2));
+                exit = exit.min(t1.max(t2));
+            }
+        }
+        // also turns away a NaN distance
+        (entry <= exit && exit.is_finite()).then_some((entry, exit))
+    }
+
+    fn add_to_cells(&mut self, key: SpatialHashKey, min_cell: IVec2, max_cell: IVec2) {
+        self.occupied = Some(match self.occupied {
+            Some((min, max)) => (min.min(min_cell), max.max(max_cell)),
+            None => (min_cell, max_cell),
+        });
+        for x in min_cell.x..=max_cell.x {
+            for y in min_cell.y..=max_cell.y {
+                self.cells.entry(IVec2::new(x, y)).or_default().push(key);
+            }
+        }
+    }
+
+    fn remove_from_cells(&mut self, key: SpatialHashKey, min_cell: IVec2, max_cell: IVec2) {
+        for x in min_cell.x..=max_cell.x {
+            for y in min_cell.y..=max_cell.y {
+                let cell = IVec2::new(x, y);
+                if let Some(keys) = self.cells.get_mut(&cell) {
+                    keys.retain(|other_key| *other_key != key);
+                    if keys.is_empty() {
+                        self.cells.remove(&cell);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(position: Vec2, size: f32) -> Aabb {
+        Aabb::new(position, Vec2::splat(size))
+    }
+
+    #[test]
+    fn spatial_hash_queries() {
+        let mut spatial_hash = SpatialHash::new(10.);
+        let a = spatial_hash.insert(square(Vec2::new(5., 5.), 4.), "a");
+        let b = spatial_hash.insert(square(Vec2::new(25., 5.), 4.), "b");
+        let c = spatial_hash.insert(square(Vec2::new(-15., 5.), 30.), "c");
+        assert_eq!(spatial_hash.len(), 3);
+        assert_eq!(spatial_hash.query_point(Vec2::new(5., 5.)), vec![a]);
+        assert_eq!(spatial_hash.query_point(Vec2::new(-5., 5.)), vec![c]);
+        assert!(spatial_hash.query_point(Vec2::new(15., 5.)).is_empty());
+        assert_eq!(
+            spatial_hash.query_aabb(square(Vec2::new(15., 5.), 22.)),
+            vec![a, b]
+        );
+        assert_eq!(spatial_hash.get(b), Some(&"b"));
+
+        spatial_hash.update(b, square(Vec2::new(6., 6.), 4.));
+        assert_eq!(spatial_hash.query_point(Vec2::new(25., 5.)), vec![]);
+        assert_eq!(spatial_hash.pairs(), vec![(a, b)]);
+
+        assert_eq!(spatial_hash.remove(a), Some("a"));
+        assert_eq!(spatial_hash.remove(a), None);
+        assert!(spatial_hash.pairs().is_empty());
+        assert_eq!(spatial_hash.len(), 2);
+    }
+
+    #[test]
+    fn spatial_hash_ray() {
+        let mut spatial_hash = SpatialHash::new(10.);
+        let near = spatial_hash.insert(square(Vec2::new(15., 1.), 2.), ());
+        let far = spatial_hash.insert(square(Vec2::new(45., 0.5), 2.), ());
+        spatial_hash.insert(square(Vec2::new(25., 20.), 2.), ());
+        spatial_hash.insert(square(Vec2::new(-15., 0.), 2.), ());
+        let ray = LineRay2::new(Vec2::new(0.5, 0.5), Vec2::new(1., -0.01));
+        assert_eq!(spatial_hash.query_ray(ray, 100.), vec![near, far]);
+        assert_eq!(spatial_hash.query_ray(ray, 20.), vec![near]);
+        assert_eq!(
+            spatial_hash.ray_cells(ray, 25.),
+            vec![IVec2::new(0, 0), IVec2::new(1, 0), IVec2::new(2, 0)]
+        );
+    }
+
+    #[test]
+    fn spatial_hash_infinite_ray() {
+        let mut spatial_hash = SpatialHash::new(0.01);
+        assert!(spatial_hash
+            .ray_cells(LineRay2::new(Vec2::ZERO, Vec2::X), f32::INFINITY)
+            .is_empty());
+        let hit = spatial_hash.insert(square(Vec2::new(1., 0.), 0.1), ());
+        spatial_hash.insert(square(Vec2::new(0., 1.), 0.1), ());
+        // the walk starts where the ray reaches the occupied cells and ends where it leaves them
+        let ray = LineRay2::new(Vec2::new(-1000., 0.), Vec2::X);
+        let cells = spatial_hash.ray_cells(ray, f32::INFINITY);
+        assert!(cells.len() < 120);
+        assert_eq!(spatial_hash.query_ray(ray, f32::INFINITY), vec![hit]);
+        let away = LineRay2::new(Vec2::new(2., 0.), Vec2::X);
+        assert!(spatial_hash.query_ray(away, f32::INFINITY).is_empty());
+    }
+
+    #[test]
+    fn spatial_hash_pairs_match_brute_force() {
+        let mut spatial_hash = SpatialHash::new(8.);
+        let mut aabbs = vec![];
+        for index in 0..200 {
+            let position = Vec2::new(
+                (index as f32 * 7.31).sin() * 60.,
+                (index as f32 * 3.17).cos() * 60.,
+            );
+            let aabb = square(position, 1. + (index % 7) as f32);
+            aabbs.push((spatial_hash.insert(aabb, index), aabb));
+        }
+        let mut brute_force = vec![];
+        for (index, (key, aabb)) in aabbs.iter().enumerate() {
+            for (other_key, other_aabb) in aabbs.iter().skip(index + 1) {
+                if aabb.colliding(other_aabb) {
+                    brute_force.push((*key, *other_key));
+                }
+            }
+        }
+        assert!(!brute_force.is_empty());
+        assert_eq!(spatial_hash.pairs(), brute_force);
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::HashSet;
+
+use glam::Vec2;
+
+use crate::{geometry::Aabb, utility::Sortable2DWith};
+
+use super::Colliding;
+
+/// Identifies an item stored in a [`SweepAndPrune`]. Keys of removed items may be handed out again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SweepAndPruneKey(usize);
+
+/// A change in overlap between two items since the previous [`SweepAndPrune::update_pairs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SweepAndPruneEvent {
+    Begin(SweepAndPruneKey, SweepAndPruneKey),
+    End(SweepAndPruneKey, SweepAndPruneKey),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SweepAndPruneEndpoint {
+    key: SweepAndPruneKey,
+    is_min: bool,
+}
+
+// at equal positions max endpoints sort before min endpoints, so touching boxes never overlap
+type Endpoint = Sortable2DWith<f32, u8, SweepAndPruneEndpoint>;
+
+#[derive(Debug, Clone)]
+struct SweepAndPruneEntry<T> {
+    aabb: Aabb,
+    item: T,
+}
+
+/// A broadphase keeping the bounds of every item sorted along both axes.
+///
+/// The endpoint lists are re-sorted with an insertion sort, which is close to linear when items
+/// only move a little between updates.
+#[derive(Debug, Clone)]
+pub struct SweepAndPrune<T> {
+    entries: Vec<Option<SweepAndPruneEntry<T>>>,
+    free: Vec<usize>,
+    endpoints: [Vec<Endpoint>; 2],
+    dirty: bool,
+    pairs: HashSet<(SweepAndPruneKey, SweepAndPruneKey)>,
+    reported: HashSet<(SweepAndPruneKey, SweepAndPruneKey)>,
+    // reported pairs of removed items, ending on the next update_pairs whatever reuses their keys
+    ended: Vec<(SweepAndPruneKey, SweepAndPruneKey)>,
+}
+
+impl<T> Default for SweepAndPrune<T> {
+    fn default() -> Self {
+        SweepAndPrune::new()
+    }
+}
+
+impl<T> SweepAndPrune<T> {
+    pub fn new() -> SweepAndPrune<T> {
+        SweepAndPrune {
+            entries: Vec::new(),
+            free: Vec::new(),
+            endpoints: [Vec::new(), Vec::new()],
+            dirty: false,
+            pairs: HashSet::new(),
+            reported: HashSet::new(),
+            ended: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.free.clear();
+        self.endpoints = [Vec::new(), Vec::new()];
+        self.dirty = false;
+        self.pairs.clear();
+        self.reported.clear();
+        self.ended.clear();
+    }
+
+    pub fn insert(&mut self, aabb: Aabb, item: T) -> SweepAndPruneKey {
+        let key = if let Some(index) = self.free.pop() {
+            SweepAndPruneKey(index)
+        } else {
+            self.entries.push(None);
+            SweepAndPruneKey(self.entries.len() - 1)
+        };
+        self.entries[key.0] = Some(SweepAndPruneEntry { aabb, item });
+        for (axis, endpoints) in self.endpoints.iter_mut().enumerate() {
+            endpoints.push(endpoint(aabb, axis, key, true));
+            endpoints.push(endpoint(aabb, axis, key, false));
+        }
+        self.dirty = true;
+        key
+    }
+
+    /// Removes an item. The next [`SweepAndPrune::update_pairs`] reports the end of its overlaps
+    /// even if its key has been handed out again by then.
+    pub fn remove(&mut self, key: SweepAndPruneKey) -> Option<T> {
+        let entry = self.entries.get_mut(key.0)?.take()?;
+        self.free.push(key.0);
+        for endpoints in self.endpoints.iter_mut() {
+            endpoints.retain(|endpoint| endpoint.2.key != key);
+        }
+        self.pairs.retain(|(a, b)| *a != key && *b != key);
+        let ended = self
+            .reported
+            .iter()
+            .filter(|(a, b)| *a == key || *b == key)
+            .copied()
+            .collect::<Vec<_>>();
+        for pair in ended {
+            self.reported.remove(&pair);
+            self.ended.push(pair);
+        }
+        Some(entry.item)
+    }
+
+    /// Moves an item. The endpoint lists are re-sorted on the next query.
+    pub fn update(&mut self, key: SweepAndPruneKey, aabb: Aabb) {
+        let Some(Some(entry)) = self.entries.get_mut(key.0) else {
+            return;
+        };
+        entry.aabb = aabb;
+        self.dirty = true;
+    }
+
+    pub fn get(&self, key: SweepAndPruneKey) -> Option<&T> {
+        Some(&self.entries.get(key.0)?.as_ref()?.item)
+    }
+
+    pub fn get_mut(&mut self, key: SweepAndPruneKey) -> Option<&mut T> {
+        Some(&mut self.entries.get_mut(key.0)?.as_mut()?.item)
+    }
+
+    pub fn aabb(&self, key: SweepAndPruneKey) -> Option<Aabb> {
+        Some(self.entries.get(key.0)?.as_ref()?.aabb)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SweepAndPruneKey, &T)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| Some((SweepAndPruneKey(index), &entry.as_ref()?.item)))
+    }
+
+    /// Every overlapping pair, each ordered and sorted.
+    pub fn pairs(&mut self) -> Vec<(SweepAndPruneKey, SweepAndPruneKey)> {
+        self.sort();
+        let mut pairs: Vec<_> = self.pairs.iter().copied().collect();
+        pairs.sort();
+        pairs
+    }
+
+    /// Brings the overlapping pairs up to date and reports which began and ended since the
+    /// previous call, sorted. The overlaps of removed items come first, so a key handed out again
+    /// ends its old overlaps before beginning new ones.
+    pub fn update_pairs(&mut self) -> Vec<SweepAndPruneEvent> {
+        self.sort();
+        let mut ended: Vec<SweepAndPruneEvent> = self
+            .ended
+            .drain(..)
+            .map(|(a, b)| SweepAndPruneEvent::End(a, b))
+            .collect();
+        ended.sort();
+        let mut events: Vec<SweepAndPruneEvent> = self
+            .pairs
+            .difference(&self.reported)
+            .map(|(a, b)| SweepAndPruneEvent::Begin(*a, *b))
+            .chain(
+                self.reported
+                    .difference(&self.pairs)
+                    .map(|(a, b)| SweepAndPruneEvent::End(*a, *b)),
+            )
+            .collect();
+        events.sort();
+        self.reported = self.pairs.clone();
+        ended.extend(events);
+        ended
+    }
+
+    fn sort(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        for axis in 0..2 {
+            let mut endpoints = std::mem::take(&mut self.endpoints[axis]);
+            for endpoint in endpoints.iter_mut() {
+                let key = endpoint.2.key;
+                let aabb = self.entries[key.0].as_ref().unwrap().aabb;
+                *endpoint = self::endpoint(aabb, axis, key, endpoint.2.is_min);
+            }
+            for index in 1..endpoints.len() {
+                let mut current = index;
+                while current > 0 && endpoints[current - 1] > endpoints[current] {
+                    let (moving, passed) = (endpoints[current].2, endpoints[current - 1].2);
+                    if moving.is_min && !passed.is_min {
+                        // a min moving below a max may start an overlap
+                        if self.overlapping(moving.key, passed.key) {
+                            self.pairs.insert(ordered(moving.key, passed.key));
+                        }
+                    } else if !moving.is_min && passed.is_min {
+                        // a max moving below a min ends any overlap on this axis
+                        self.pairs.remove(&ordered(moving.key, passed.key));
+                    }
+                    endpoints.swap(current - 1, current);
+                    current -= 1;
+                }
+            }
+            self.endpoints[axis] = endpoints;
+        }
+    }
+
+    fn overlapping(&self, a: SweepAndPruneKey, b: SweepAndPruneKey) -> bool {
+        a != b && self.aabb(a).unwrap().colliding(&self.aabb(b).unwrap())
+    }
+}
+
+fn endpoint(aabb: Aabb, axis: usize, key: SweepAndPruneKey, is_min: bool) -> Endpoint {
+    let corner: Vec2 = if is_min { aabb.min() } else { aabb.max() };
+    Sortable2DWith(
+        corner[axis],
+        is_min as u8,
+        SweepAndPruneEndpoint { key, is_min },
+    )
+}
+
+fn ordered(a: SweepAndPruneKey, b: SweepAndPruneKey) -> (SweepAndPruneKey, SweepAndPruneKey) {
+    (a.min(b), a.max(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(aabbs: &[Aabb]) -> Vec<(SweepAndPruneKey, SweepAndPruneKey)> {
+        let mut pairs = vec![];
+        for a in 0..aabbs.len() {
+            for b in (a + 1)..aabbs.len() {
+                if aabbs[a].colliding(&aabbs[b]) {
+                    pairs.push((SweepAndPruneKey(a), SweepAndPruneKey(b)));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn sweep_and_prune_tracks_moving_items() {
+        let mut sweep_and_prune = SweepAndPrune::new();
+        let mut aabbs: Vec<Aabb> = (0..60)
+            .map(|index| {
+                let position = Vec2::new(
+                    (index as f32 * 1.7).sin() * 30.,
+                    (index as f32 * 2.3).cos() * 30.,
+                );
+                Aabb::new(position, Vec2::new(4. + (index % 3) as f32, 3.))
+            })
+            .collect();
+        for (index, aabb) in aabbs.iter().enumerate() {
+            sweep_and_prune.insert(*aabb, index);
+        }
+        for frame in 0..100 {
+            for (index, aabb) in aabbs.iter_mut().enumerate() {
+                let angle = frame as f32 * 0.1 + index as f32;
+                aabb.position += Vec2::from_angle(angle) * 0.8;
+                sweep_and_prune.update(SweepAndPruneKey(index), *aabb);
+            }
+            assert_eq!(sweep_and_prune.pairs(), brute_force(&aabbs));
+        }
+    }
+
+    #[test]
+    fn sweep_and_prune_reports_events() {
+        let mut sweep_and_prune = SweepAndPrune::new();
+        let a = sweep_and_prune.insert(Aabb::new(Vec2::ZERO, Vec2::ONE), "a");
+        let b = sweep_and_prune.insert(Aabb::new(Vec2::new(3., 0.), Vec2::ONE), "b");
+        assert!(sweep_and_prune.update_pairs().is_empty());
+
+        sweep_and_prune.update(b, Aabb::new(Vec2::new(0.5, 0.5), Vec2::ONE));
+        assert_eq!(
+            sweep_and_prune.update_pairs(),
+            vec![SweepAndPruneEvent::Begin(a, b)]
+        );
+        assert!(sweep_and_prune.update_pairs().is_empty());
+
+        // touching edges do not count as overlapping
+        sweep_and_prune.update(b, Aabb::new(Vec2::new(0.5, 1.), Vec2::ONE));
+        assert_eq!(
+            sweep_and_prune.update_pairs(),
+            vec![SweepAndPruneEvent::End(a, b)]
+        );
+
+        let c = sweep_and_prune.insert(Aabb::new(Vec2::new(0.2, 0.2), Vec2::ONE), "c");
+        assert_eq!(
+            sweep_and_prune.update_pairs(),
+            vec![
+                SweepAndPruneEvent::Begin(a, c),
+                SweepAndPruneEvent::Begin(b, c)
+            ]
+        );
+        assert_eq!(sweep_and_prune.remove(a), Some("a"));
+        assert_eq!(
+            sweep_and_prune.update_pairs(),
+            vec![SweepAndPruneEvent::End(a, c)]
+        );
+        assert_eq!(sweep_and_prune.len(), 2);
+    }
+
+    #[test]
+    fn sweep_and_prune_reports_reused_keys() {
+        let mut sweep_and_prune = SweepAndPrune::new();
+        let a = sweep_and_prune.insert(Aabb::new(Vec2::ZERO, Vec2::ONE), "a");
+        let b = sweep_and_prune.insert(Aabb::new(Vec2::splat(0.5), Vec2::ONE), "b");
+        assert_eq!(
+            sweep_and_prune.update_pairs(),
+            vec![SweepAndPruneEvent::Begin(a, b)]
+        );
+
+        // a replacement overlapping the same partner before the events are collected
+        sweep_and_prune.remove(b);
+        let c = sweep_and_prune.insert(Aabb::new(Vec2::splat(0.5), Vec2::ONE), "c");
+        assert_eq!(b, c);
+        assert_eq!(
+            sweep_and_prune.update_pairs(),
+            vec![
+                SweepAndPruneEvent::End(a, b),
+                SweepAndPruneEvent::Begin(a, c)
+            ]
+        );
+        assert_eq!(sweep_and_prune.pairs(), vec![(a, c)]);
+        assert!(sweep_and_prune.update_pairs().is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+use glam::Vec2;
+
+use crate::geometry::{Aabb, Circle, LineSegment2, Triangle2, VertexList2};
+
+/// The first moment two moving shapes touch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Impact {
+    pub time: f32,
+    pub point: Vec2,
+    /// Contact normal, pointing from the second shape towards the first.
+    pub normal: Vec2,
+}
+
+/// A convex shape described by its vertices, optionally rounded by a radius.
+///
+/// Non-convex vertex lists are swept as their convex hull.
+pub trait Sweepable {
+    fn sweep_vertices(&self) -> Vec<Vec2>;
+
+    fn sweep_radius(&self) -> f32 {
+        0.
+    }
+}
+
+impl Sweepable for Vec2 {
+    fn sweep_vertices(&self) -> Vec<Vec2> {
+        vec![*self]
+    }
+}
+
+impl Sweepable for Circle {
+    fn sweep_vertices(&self) -> Vec<Vec2> {
+        vec![self.center]
+    }
+
+    fn sweep_radius(&self) -> f32 {
+        self.radius * 0.5
+    }
+}
+
+impl Sweepable for LineSegment2 {
+    fn sweep_vertices(&self) -> Vec<Vec2> {
+        vec![self.start, self.end]
+    }
+}
+
+impl Sweepable for Aabb {
+    fn sweep_vertices(&self) -> Vec<Vec2> {
+        self.corners().to_vec()
+    }
+}
+
+impl Sweepable for Triangle2 {
+    fn sweep_vertices(&self) -> Vec<Vec2> {
+        self.vertices.to_vec()
+    }
+}
+
+impl Sweepable for VertexList2 {
+    fn sweep_vertices(&self) -> Vec<Vec2> {
+        self.0.clone()
+    }
+}
+
+/// Finds the first time in `0..=max_t` at which two shapes moving at constant velocities touch.
+///
+/// Shapes that already overlap report an impact at time zero.
+pub fn time_of_impact<A: Sweepable, B: Sweepable>(
+    shape_a: &A,
+    velocity_a: Vec2,
+    shape_b: &B,
+    velocity_b: Vec2,
+    max_t: f32,
+) -> Option<Impact> {
+    bodge_assert!(velocity_a.is_finite());
+    bodge_assert!(velocity_b.is_finite());
+    bodge_assert!(max_t >= 0.);
+    let vertices_a = shape_a.sweep_vertices();
+    let vertices_b = shape_b.sweep_vertices();
+    bodge_assert!(!vertices_a.is_empty());
+    bodge_assert!(!vertices_b.is_empty());
+    let radius_a = shape_a.sweep_radius();
+    let radius_b = shape_b.sweep_radius();
+
+    // the shapes touch when the relative motion carries the origin into the
+    // minkowski difference of b and a, inflated by both radii
+    let mut differences = Vec::with_capacity(vertices_a.len() * vertices_b.len());
+    for vertex_b in vertices_b.iter() {
+        for vertex_a in vertices_a.iter() {
+            differences.push(*vertex_b - *vertex_a);
+        }
+    }
+    let hull = VertexList2::new_convex_hull(&differences).0;
+    let radius = radius_a + radius_b;
+    let direction = velocity_a - velocity_b;
+
+    let (time, normal) = if let Some(normal) = penetration_normal(&hull, radius, direction) {
+        (0., normal)
+    } else {
+        ray_cast_hull(&hull, radius, direction, max_t)?
+    };
+
+    // the contact point sits in the middle of where the touching features overlap
+    let tangent = normal.perp();
+    let interval = |(start, end): (Vec2, Vec2)| {
+        let (start, end) = (start.dot(tangent), end.dot(tangent));
+        (start.min(end), start.max(end))
+    };
+    let feature_b = support_feature(&vertices_b, velocity_b * time, normal);
+    let (b_low, b_high) = interval(feature_b);
+    let (a_low, a_high) = interval(support_feature(&vertices_a, velocity_a * time, -normal));
+    let along = (b_low.max(a_low) + b_high.min(a_high)) * 0.5;
+    let point = normal * (feature_b.0.dot(normal) + radius_b) + tangent * along;
+
+    Some(Impact {
+        time,
+        point,
+        normal,
+    })
+}
+
+fn hull_edges(hull: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2, Vec2)> + '_ {
+    let edge_count = if hull.len() < 2 { 0 } else { hull.len() };
+    (0..edge_count).map(move |index| {
+        let start = hull[index];
+        let end = hull[(index + 1) % hull.len()];
+        // hull winds counterclockwise, so the outward normal is on the right
+        let normal = -(end - start).perp().normalize_or_zero();
+        (start, end, normal)
+    })
+}
+
+fn penetration_normal(hull: &[Vec2], radius: f32, direction: Vec2) -> Option<Vec2> {
+    let fallback = || {
+        let normal = -direction.normalize_or_zero();
+        if normal == Vec2::ZERO {
+            Vec2::Y
+        } else {
+            normal
+        }
+    };
+    if hull.len() >= 3 {
+        let mut deepest: Option<(f32, Vec2)> = None;
+        for (start, _, normal) in hull_edges(hull) {
+            let distance = start.dot(normal);
+            if distance <= 0. {
+                deepest = None;
+                break;
+            }
+            if deepest.is_none_or(|(deepest_distance, _)| distance < deepest_distance) {
+                deepest = Some((distance, normal));
+            }
+        }
+        if let Some((_, normal)) = deepest {
+            return Some(normal);
+        }
+    }
+    let closest = if hull.len() == 1 {
+        hull[0]
+    } else {
+        let mut closest = hull[0];
+        for (start, end, _) in hull_edges(hull) {
+            let closest_point = LineSegment2::new(start, end).closest_point(Vec2::ZERO);
+            if closest_point.length_squared() < closest.length_squared() {
+                closest = closest_point;
+            }
+        }
+        closest
+    };
+    let distance = closest.length();
+    if distance < radius {
+        if distance > 0. {
+            Some(-closest / distance)
+        } else {
+            Some(fallback())
+        }
+    } else {
+        None
+    }
+}
+
+fn ray_cast_hull(hull: &[Vec2], radius: f32, direction: Vec2, max_t: f32) -> Option<(f32, Vec2)> {
+    if direction == Vec2::ZERO {
+        return None;
+    }
+    let mut best: Option<(f32, Vec2)> = None;
+    let mut consider = |time: f32, normal: Vec2| {
+        if (0. ..=max_t).contains(&time) && best.is_none_or(|(best_time, _)| time < best_time) {
+            best = Some((time, normal));
+        }
+    };
+    for (start, end, normal) in hull_edges(hull) {
+        if direction.dot(normal) >= 0. {
+            continue;
+        }
+        let edge = end - start;
+        let start = start + normal * radius;
+        let denominator = direction.perp_dot(edge);
+        let time = start.perp_dot(edge) / denominator;
+        let along = start.perp_dot(direction) / denominator;
+        if (0. ..=1.).contains(&along) {
+            consider(time, normal);
+        }
+    }
+    let direction_length_squared = direction.length_squared();
+    for vertex in hull.iter() {
+        if radius > 0. {
+            let half_b = direction.dot(*vertex);
+            let discriminant = half_b * half_b
+                - direction_length_squared * (vertex.length_squared() - radius * radius);
+            if discriminant >= 0. {
+                let time = (half_b - discriminant.sqrt()) / direction_length_squared;
+                consider(time, (direction * time - *vertex) / radius);
+            }
+        } else if direction.perp_dot(*vertex).abs() <= 1e-5 * direction.length() * vertex.length()
+            && direction.dot(*vertex) >= 0.
+        {
+            // head on with a vertex, e.g. collinear segments
+            consider(
+                direction.dot(*vertex) / direction_length_squared,
+                -direction.normalize(),
+            );
+        }
+    }
+    best
+}
+
+fn support_feature(vertices: &[Vec2], offset: Vec2, direction: Vec2) -> (Vec2, Vec2) {
+    let tangent = direction.perp();
+    let mut max_distance = f32::NEG_INFINITY;
+    let mut scale = 1f32;
+    for vertex in vertices.iter() {
+        max_distance = max_distance.max(vertex.dot(direction));
+        scale = scale.max(vertex.dot(direction).abs());
+    }
+    let tolerance = scale * 1e-4;
+    let mut feature: Option<(Vec2, Vec2)> = None;
+    for vertex in vertices.iter() {
+        if vertex.dot(direction) >= max_distance - tolerance {
+            feature = Some(match feature {
+                Some((min, max)) => (
+                    if vertex.dot(tangent) < min.dot(tangent) {
+                        *vertex
+                    } else {
+                        min
+                    },
+                    if vertex.dot(tangent) > max.dot(tangent) {
+                        *vertex
+                    } else {
+                        max
+                    },
+                ),
+                None => (*vertex, *vertex),
+            });
+        }
+    }
+    let (min, max) = feature.unwrap();
+    (min + offset, max + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn bullet_does_not_tunnel_through_thin_wall() {
+        let bullet = Circle::new(Vec2::new(-100., 0.), 2.);
+        let wall = LineSegment2::new(Vec2::new(0., -50.), Vec2::new(0., 50.));
+        let impact = time_of_impact(&bullet, Vec2::new(10000., 0.), &wall, Vec2::ZERO, 1.).unwrap();
+        assert_near(impact.time, 0.0099);
+        assert_near(impact.point.x, 0.);
+        assert_near(impact.point.y, 0.);
+        assert_near(impact.normal.x, -1.);
+        assert_near(impact.normal.y, 0.);
+    }
+
+    #[test]
+    fn circles() {
+        let circle_a = Circle::new(Vec2::new(-10., 0.), 2.);
+        let circle_b = Circle::new(Vec2::new(10., 0.), 2.);
+        let impact = time_of_impact(
+            &circle_a,
+            Vec2::new(1., 0.),
+            &circle_b,
+            Vec2::new(-1., 0.),
+            100.,
+        )
+        .unwrap();
+        assert_near(impact.time, 9.);
+        assert_near(impact.point.x, 0.);
+        assert_near(impact.normal.x, -1.);
+        assert!(time_of_impact(
+            &circle_a,
+            Vec2::new(1., 0.),
+            &circle_b,
+            Vec2::new(-1., 0.),
+            8.
+        )
+        .is_none());
+        assert!(
+            time_of_impact(&circle_a, Vec2::new(-1., 0.), &circle_b, Vec2::ZERO, 100.).is_none()
+        );
+    }
+
+    #[test]
+    fn aabbs_face_to_face() {
+        let aabb_a = Aabb::new(Vec2::new(0., 10.), Vec2::new(4., 4.));
+        let aabb_b = Aabb::new(Vec2::new(1., 0.), Vec2::new(4., 4.));
+        let impact = time_of_impact(&aabb_a, Vec2::new(0., -3.), &aabb_b, Vec2::ZERO, 10.).unwrap();
+        assert_near(impact.time, 2.);
+        assert_near(impact.normal.y, 1.);
+        assert_near(impact.point.x, 0.5);
+        assert_near(impact.point.y, 2.);
+    }
+
+    #[test]
+    fn convex_polygon_vertex_hits_edge() {
+        let polygon = VertexList2(vec![
+            Vec2::new(0., 5.),
+            Vec2::new(1., 7.),
+            Vec2::new(-1., 7.),
+        ]);
+        let wall = LineSegment2::new(Vec2::new(-10., 0.), Vec2::new(10., 0.));
+        let impact = time_of_impact(&polygon, Vec2::new(0., -1.), &wall, Vec2::ZERO, 10.).unwrap();
+        assert_near(impact.time, 5.);
+        assert_near(impact.point.x, 0.);
+        assert_near(impact.point.y, 0.);
+    }
+
+    #[test]
+    fn collinear_segments() {
+        let segment_a = LineSegment2::new(Vec2::new(0., 0.), Vec2::new(1., 0.));
+        let segment_b = LineSegment2::new(Vec2::new(5., 0.), Vec2::new(6., 0.));
+        let impact =
+            time_of_impact(&segment_a, Vec2::new(2., 0.), &segment_b, Vec2::ZERO, 10.).unwrap();
+        assert_near(impact.time, 2.);
+        assert_near(impact.point.x, 5.);
+    }
+
+    #[test]
+    fn overlapping_shapes_impact_immediately() {
+        let aabb = Aabb::new(Vec2::ZERO, Vec2::new(10., 10.));
+        let point = Vec2::new(4., 1.);
+        let impact = time_of_impact(&point, Vec2::ZERO, &aabb, Vec2::ZERO, 1.).unwrap();
+        assert_eq!(impact.time, 0.);
+        assert_near(impact.normal.x, 1.);
+    }
+}
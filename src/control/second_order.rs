@@ -4,6 +4,7 @@ use std::{
 };
 
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SecondOrder<T> {
     pub input_previous: T,
     pub output: T,
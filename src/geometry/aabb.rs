@@ -1,6 +1,10 @@
 use glam::Vec2;
 
+use super::LineRay2;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "AabbUnchecked"))]
 pub struct Aabb {
     pub position: Vec2,
     pub size: Vec2,
@@ -8,13 +12,38 @@ pub struct Aabb {
 
 macro_rules! aabb_validity_check {
     ($aabb:expr) => {
-        bodge_assert!($aabb.position.is_finite());
-        bodge_assert!($aabb.size.is_finite());
-        bodge_assert!($aabb.size.x >= 0.);
-        bodge_assert!($aabb.size.y >= 0.);
+        aabb_validity_check!($aabb, bodge_assert);
+    };
+    ($aabb:expr, $check:ident) => {
+        $check!($aabb.position.is_finite());
+        $check!($aabb.size.is_finite());
+        $check!($aabb.size.x >= 0.);
+        $check!($aabb.size.y >= 0.);
     };
 }
 
+// deserialized values go through the validity check like constructed ones
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct AabbUnchecked {
+    position: Vec2,
+    size: Vec2,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AabbUnchecked> for Aabb {
+    type Error = &'static str;
+
+    fn try_from(unchecked: AabbUnchecked) -> Result<Aabb, &'static str> {
+        let aabb = Aabb {
+            position: unchecked.position,
+            size: unchecked.size,
+        };
+        aabb_validity_check!(aabb, bodge_validate);
+        Ok(aabb)
+    }
+}
+
 impl Aabb {
     pub fn new(position: Vec2, size: Vec2) -> Aabb {
         let aabb = Aabb { position, size };
@@ -22,6 +51,47 @@ impl Aabb {
         aabb
     }
 
+    pub fn new_from_min_max(min: Vec2, max: Vec2) -> Aabb {
+        Aabb::new((min + max) * 0.5, max - min)
+    }
+
+    pub fn min(&self) -> Vec2 {
+        aabb_validity_check!(self);
+        self.position - self.size * 0.5
+    }
+
+    pub fn max(&self) -> Vec2 {
+        aabb_validity_check!(self);
+        self.position + self.size * 0.5
+    }
+
+    pub fn perimeter(&self) -> f32 {
+        aabb_validity_check!(self);
+        (self.size.x + self.size.y) * 2.
+    }
+
+    pub fn union(&self, other: Aabb) -> Aabb {
+        Aabb::new_from_min_max(self.min().min(other.min()), self.max().max(other.max()))
+    }
+
+    pub fn expanded(&self, amount: f32) -> Aabb {
+        Aabb::new(self.position, self.size + Vec2::splat(amount * 2.))
+    }
+
+    pub fn contains_aabb(&self, other: Aabb) -> bool {
+        self.min().cmple(other.min()).all() && self.max().cmpge(other.max()).all()
+    }
+
+    pub fn corners(&self) -> [Vec2; 4] {
+        aabb_validity_check!(self);
+        [
+            self.position + self.size * Vec2::new(0.5, 0.5),
+            self.position + self.size * Vec2::new(-0.5, 0.5),
+            self.position + self.size * Vec2::new(-0.5, -0.5),
+            self.position + self.size * Vec2::new(0.5, -0.5),
+        ]
+    }
+
     pub fn closest_point(&self, point: Vec2) -> Vec2 {
         aabb_validity_check!(self);
         point.clamp(
@@ -37,4 +107,45 @@ impl Aabb {
             && point.y > self.position.y - self.size.y * 0.5
             && point.y < self.position.y + self.size.y * 0.5
     }
+
+    /// Distance along the ray at which it enters this box, or zero if it starts inside.
+    pub fn ray_entry_distance(&self, ray: LineRay2) -> Option<f32> {
+        aabb_validity_check!(self);
+        let min = self.min();
+        let max = self.max();
+        let mut entry = 0f32;
+        let mut exit = f32::INFINITY;
+        for axis in 0..2 {
+            if ray.axis[axis] == 0. {
+                if ray.start[axis] < min[axis] || ray.start[axis] > max[axis] {
+                    return None;
+                }
+            } else {
+                let t1 = (min[axis] - ray.start[axis]) / ray.axis[axis];
+                let t2 = (max[axis] - ray.start[axis]) / ray.axis[axis];
+                entry = entry.max(t1.min(t2));
+                exit = exit.min(t1.max(t2));
+            }
+        }
+        if entry <= exit {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aabb_deserialize() {
+        let aabb = Aabb::new(Vec2::new(1., 2.), Vec2::new(3., 4.));
+        assert_eq!(
+            ron::from_str::<Aabb>(&ron::to_string(&aabb).unwrap()).unwrap(),
+            aabb
+        );
+        assert!(ron::from_str::<Aabb>("(position: (0., 0.), size: (-1., 1.))").is_err());
+    }
 }
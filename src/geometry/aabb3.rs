@@ -0,0 +1,92 @@
+use glam::Vec3;
+
+use super::Ray3;
+
+/// A 3D axis aligned box, the counterpart of [`Aabb`](super::Aabb).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb3 {
+    pub position: Vec3,
+    pub size: Vec3,
+}
+
+macro_rules! aabb_3_validity_check {
+    ($aabb_3:expr) => {
+        bodge_assert!($aabb_3.position.is_finite());
+        bodge_assert!($aabb_3.size.is_finite());
+        bodge_assert!($aabb_3.size.cmpge(Vec3::ZERO).all());
+    };
+}
+
+impl Aabb3 {
+    pub fn new(position: Vec3, size: Vec3) -> Aabb3 {
+        let aabb = Aabb3 { position, size };
+        aabb_3_validity_check!(aabb);
+        aabb
+    }
+
+    pub fn new_from_min_max(min: Vec3, max: Vec3) -> Aabb3 {
+        Aabb3::new((min + max) * 0.5, max - min)
+    }
+
+    pub fn min(&self) -> Vec3 {
+        aabb_3_validity_check!(self);
+        self.position - self.size * 0.5
+    }
+
+    pub fn max(&self) -> Vec3 {
+        aabb_3_validity_check!(self);
+        self.position + self.size * 0.5
+    }
+
+    pub fn union(&self, other: Aabb3) -> Aabb3 {
+        Aabb3::new_from_min_max(self.min().min(other.min()), self.max().max(other.max()))
+    }
+
+    /// The eight corners, with bit 0, 1 and 2 of the index selecting the max x, y and z side.
+    pub fn corners(&self) -> [Vec3; 8] {
+        let (min, max) = (self.min(), self.max());
+        [0, 1, 2, 3, 4, 5, 6, 7].map(|index| {
+            Vec3::new(
+                if index & 1 == 0 { min.x } else { max.x },
+                if index & 2 == 0 { min.y } else { max.y },
+                if index & 4 == 0 { min.z } else { max.z },
+            )
+        })
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        bodge_assert!(point.is_finite());
+        point.clamp(self.min(), self.max())
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        bodge_assert!(point.is_finite());
+        point.cmpgt(self.min()).all() && point.cmplt(self.max()).all()
+    }
+
+    /// Distance along the ray at which it enters this box, or zero if it starts inside.
+    pub fn ray_entry_distance(&self, ray: Ray3) -> Option<f32> {
+        aabb_3_validity_check!(self);
+        let min = self.min();
+        let max = self.max();
+        let mut entry = 0f32;
+        let mut exit = f32::INFINITY;
+        for axis in 0..3 {
+            if ray.axis[axis] == 0. {
+                if ray.start[axis] < min[axis] || ray.start[axis] > max[axis] {
+                    return None;
+                }
+            } else {
+                let t1 = (min[axis] - ray.start[axis]) / ray.axis[axis];
+                let t2 = (max[axis] - ray.start[axis]) / ray.axis[axis];
+                entry = entry.max(t1.min(t2));
+                exit = exit.min(t1.max(t2));
+            }
+        }
+        if entry <= exit {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+}
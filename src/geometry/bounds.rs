@@ -0,0 +1,126 @@
+use glam::Vec2;
+
+use super::{Aabb, Circle, LineSegment2, Obb, Polyline, Triangle2, VertexList2};
+
+/// Tight bounding volumes of a finite shape. Lines and rays are endless and have none.
+pub trait Bounded {
+    fn aabb(&self) -> Aabb;
+    fn bounding_circle(&self) -> Circle;
+}
+
+fn points_aabb(points: &[Vec2]) -> Aabb {
+    bodge_assert!(!points.is_empty());
+    let (min, max) = points
+        .iter()
+        .fold((points[0], points[0]), |(min, max), point| {
+            (min.min(*point), max.max(*point))
+        });
+    Aabb::new_from_min_max(min, max)
+}
+
+impl Bounded for Vec2 {
+    fn aabb(&self) -> Aabb {
+        Aabb::new(*self, Vec2::ZERO)
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        Circle::new_enclosing(&[*self])
+    }
+}
+
+impl Bounded for Aabb {
+    fn aabb(&self) -> Aabb {
+        *self
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        Circle::new_enclosing(&self.corners())
+    }
+}
+
+impl Bounded for Obb {
+    fn aabb(&self) -> Aabb {
+        points_aabb(&self.corners())
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        Circle::new_enclosing(&self.corners())
+    }
+}
+
+impl Bounded for Circle {
+    fn aabb(&self) -> Aabb {
+        Aabb::new(self.center, Vec2::splat(self.radius))
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        *self
+    }
+}
+
+impl Bounded for LineSegment2 {
+    fn aabb(&self) -> Aabb {
+        points_aabb(&[self.start, self.end])
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        Circle::new_enclosing(&[self.start, self.end])
+    }
+}
+
+impl Bounded for Triangle2 {
+    fn aabb(&self) -> Aabb {
+        points_aabb(&self.vertices)
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        Circle::new_enclosing(&self.vertices)
+    }
+}
+
+impl Bounded for Polyline {
+    fn aabb(&self) -> Aabb {
+        points_aabb(&self.0)
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        Circle::new_enclosing(&self.0)
+    }
+}
+
+impl Bounded for VertexList2 {
+    fn aabb(&self) -> Aabb {
+        points_aabb(&self.0)
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        Circle::new_enclosing(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_contain_shapes() {
+        let triangle = Triangle2::new(Vec2::ZERO, Vec2::new(4., 0.), Vec2::new(1., 3.));
+        assert_eq!(
+            triangle.aabb(),
+            Aabb::new_from_min_max(Vec2::ZERO, Vec2::new(4., 3.))
+        );
+        let circle = triangle.bounding_circle();
+        assert!(triangle
+            .vertices
+            .iter()
+            .all(|vertex| circle.contains_point(*vertex)));
+
+        let aabb = Aabb::new(Vec2::ONE, Vec2::new(6., 8.));
+        assert_eq!(aabb.bounding_circle().center, Vec2::ONE);
+        assert!((aabb.bounding_circle().radius - 10.).abs() < 1e-5);
+        assert_eq!(
+            Circle::new(Vec2::ONE, 2.).aabb(),
+            Aabb::new(Vec2::ONE, Vec2::splat(2.))
+        );
+    }
+}
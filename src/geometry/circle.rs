@@ -1,6 +1,8 @@
-use glam::Vec2;
+use glam::{DVec2, Vec2};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CircleUnchecked"))]
 pub struct Circle {
     pub center: Vec2,
     pub radius: f32,
@@ -8,10 +10,34 @@ pub struct Circle {
 
 macro_rules! circle_validity_check {
     ($circle:expr) => {
-        bodge_assert!($circle.center.is_finite());
-        bodge_assert!($circle.radius.is_finite());
-        bodge_assert!($circle.radius > 0.);
+        circle_validity_check!($circle, bodge_assert);
     };
+    ($circle:expr, $check:ident) => {
+        $check!($circle.center.is_finite());
+        $check!($circle.radius.is_finite());
+        $check!($circle.radius > 0.);
+    };
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CircleUnchecked {
+    center: Vec2,
+    radius: f32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CircleUnchecked> for Circle {
+    type Error = &'static str;
+
+    fn try_from(unchecked: CircleUnchecked) -> Result<Circle, &'static str> {
+        let circle = Circle {
+            center: unchecked.center,
+            radius: unchecked.radius,
+        };
+        circle_validity_check!(circle, bodge_validate);
+        Ok(circle)
+    }
 }
 
 impl Circle {
@@ -21,6 +47,52 @@ impl Circle {
         circle
     }
 
+    /// The smallest circle containing every point, using Welzl's algorithm. A set of identical
+    /// points gets the smallest positive radius.
+    pub fn new_enclosing(points: &[Vec2]) -> Circle {
+        bodge_assert!(!points.is_empty());
+        bodge_assert!(points.iter().all(|point| point.is_finite()));
+        // visiting the points in a scrambled order keeps the expected running time linear, a fixed
+        // seed keeps the result repeatable
+        let mut points: Vec<DVec2> = points.iter().map(|point| point.as_dvec2()).collect();
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        for index in (1..points.len()).rev() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            points.swap(index, (state % (index as u64 + 1)) as usize);
+        }
+
+        let mut circle = (points[0], 0.);
+        for i in 1..points.len() {
+            if encloses(circle, points[i]) {
+                continue;
+            }
+            circle = (points[i], 0.);
+            for j in 0..i {
+                if encloses(circle, points[j]) {
+                    continue;
+                }
+                circle = (
+                    (points[i] + points[j]) * 0.5,
+                    points[i].distance(points[j]) * 0.5,
+                );
+                for k in 0..j {
+                    if !encloses(circle, points[k]) {
+                        circle = circle_through(points[i], points[j], points[k]);
+                    }
+                }
+            }
+        }
+        // grow to cover rounding in the last step, so every point is really inside
+        let center = circle.0.as_vec2();
+        let radius = points
+            .iter()
+            .map(|point| center.distance(point.as_vec2()))
+            .fold(0f32, f32::max);
+        Circle::new(center, (radius * 2.).max(f32::MIN_POSITIVE))
+    }
+
     pub fn closest_point(&self, point: Vec2) -> Vec2 {
         if self.contains_point(point) {
             point
@@ -35,6 +107,28 @@ impl Circle {
     }
 }
 
+fn encloses((center, radius): (DVec2, f64), point: DVec2) -> bool {
+    center.distance(point) <= radius * (1. + 1e-12)
+}
+
+// the circle with `a`, `b` and `c` on its boundary, or the widest of their diameters when collinear
+fn circle_through(a: DVec2, b: DVec2, c: DVec2) -> (DVec2, f64) {
+    let (ab, ac) = (b - a, c - a);
+    let determinant = 2. * ab.perp_dot(ac);
+    if determinant == 0. {
+        return [(a, b), (a, c), (b, c)]
+            .map(|(start, end)| ((start + end) * 0.5, start.distance(end) * 0.5))
+            .into_iter()
+            .max_by(|x, y| x.1.total_cmp(&y.1))
+            .unwrap();
+    }
+    let offset = DVec2::new(
+        ac.y * ab.length_squared() - ab.y * ac.length_squared(),
+        ab.x * ac.length_squared() - ac.x * ab.length_squared(),
+    ) / determinant;
+    (a + offset, offset.length())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,4 +139,42 @@ mod tests {
         assert!(circle.contains_point(Vec2::new(15., 10.)));
         assert!(!circle.contains_point(Vec2::new(16., 10.)));
     }
+
+    #[test]
+    fn circle_enclosing() {
+        let points: Vec<Vec2> = (0..200)
+            .map(|index| {
+                let angle = index as f32 * 2.4;
+                Vec2::new(angle.cos(), angle.sin()) * (index % 17) as f32 + Vec2::new(3., -2.)
+            })
+            .collect();
+        let circle = Circle::new_enclosing(&points);
+        assert!(points.iter().all(|point| circle.contains_point(*point)));
+        // the farthest points all lie on the boundary of the radius 16 ring
+        assert!((circle.center - Vec2::new(3., -2.)).length() < 0.5);
+        assert!(circle.radius < 32.5);
+
+        let circle = Circle::new_enclosing(&[Vec2::ZERO, Vec2::new(4., 0.), Vec2::new(2., 1.)]);
+        assert_eq!(circle, Circle::new(Vec2::new(2., 0.), 4.));
+        let circle = Circle::new_enclosing(&[Vec2::ONE, Vec2::ONE]);
+        assert_eq!(circle.center, Vec2::ONE);
+        assert!(circle.radius > 0.);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn circle_deserialize() {
+        let circle: Circle = ron::from_str("(center: (1., 2.), radius: 3.)").unwrap();
+        assert_eq!(circle, Circle::new(Vec2::new(1., 2.), 3.));
+        assert_eq!(
+            ron::from_str::<Circle>(&ron::to_string(&circle).unwrap()).unwrap(),
+            circle
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn circle_deserialize_checks_validity() {
+        assert!(ron::from_str::<Circle>("(center: (1., 2.), radius: -3.)").is_err());
+    }
 }
@@ -0,0 +1,80 @@
+use glam::Vec2;
+
+use super::{incircle, orient2d};
+
+/// The Delaunay triangulation of `points` with the Bowyer-Watson algorithm, as counterclockwise
+/// triangles of indices into `points`. A point repeated exactly is only used once.
+pub fn delaunay(points: &[Vec2]) -> Vec<[usize; 3]> {
+    bodge_assert!(points.iter().all(|point| point.is_finite()));
+    let count = points.len();
+    let (min, max) = points.iter().fold(
+        (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+        |(min, max), point| (min.min(*point), max.max(*point)),
+    );
+    // a triangle far enough out to hold every point, removed again at the end
+    let center = (min + max) * 0.5;
+    let extent = (max - min).max_element().max(1.) * 10.;
+    let mut points = points.to_vec();
+    points.extend([
+        center + Vec2::new(-3., -1.) * extent,
+        center + Vec2::new(3., -1.) * extent,
+        center + Vec2::new(0., 3.) * extent,
+    ]);
+
+    let mut triangles = vec![[count, count + 1, count + 2]];
+    for index in 0..count {
+        let point = points[index];
+        let (bad, good): (Vec<[usize; 3]>, Vec<[usize; 3]>) = triangles
+            .into_iter()
+            .partition(|[a, b, c]| incircle(points[*a], points[*b], points[*c], point) > 0.);
+        triangles = good;
+        let sides: Vec<(usize, usize)> = bad
+            .iter()
+            .flat_map(|triangle| (0..3).map(|side| (triangle[side], triangle[(side + 1) % 3])))
+            .collect();
+        for (start, end) in sides.iter() {
+            // only the outline of the hole gets connected to the new point
+            if !sides.contains(&(*end, *start))
+                && orient2d(points[*start], points[*end], point) > 0.
+            {
+                triangles.push([*start, *end, index]);
+            }
+        }
+    }
+    triangles.retain(|triangle| triangle.iter().all(|index| *index < count));
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::VertexList2;
+
+    #[test]
+    fn delaunay_triangulation() {
+        assert!(delaunay(&[]).is_empty());
+        assert_eq!(delaunay(&[Vec2::ZERO, Vec2::X, Vec2::Y]), vec![[0, 1, 2]]);
+
+        let points: Vec<Vec2> = (0..40)
+            .map(|index| {
+                let index = index as f32;
+                Vec2::new((index * 0.618).fract(), (index * 0.377).fract()) * 10.
+            })
+            .collect();
+        let triangles = delaunay(&points);
+        assert!(!triangles.is_empty());
+        for [a, b, c] in triangles.iter() {
+            let (a, b, c) = (points[*a], points[*b], points[*c]);
+            assert!(orient2d(a, b, c) > 0.);
+            assert!(points.iter().all(|point| incircle(a, b, c, *point) <= 0.));
+        }
+        // every point is used, and the triangles cover the hull exactly once
+        let hull = VertexList2::new_convex_hull(&points);
+        let area: f64 = triangles
+            .iter()
+            .map(|[a, b, c]| orient2d(points[*a], points[*b], points[*c]) * 0.5)
+            .sum();
+        assert!((area - hull.signed_area() as f64).abs() < 1e-3);
+        assert!((0..points.len()).all(|index| triangles.iter().flatten().any(|i| *i == index)));
+    }
+}
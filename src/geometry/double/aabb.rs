@@ -0,0 +1,129 @@
+use glam::DVec2;
+
+use crate::geometry::Aabb;
+
+use super::DLineRay2;
+
+/// Double precision counterpart of [`Aabb`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DAabb {
+    pub position: DVec2,
+    pub size: DVec2,
+}
+
+macro_rules! daabb_validity_check {
+    ($aabb:expr) => {
+        bodge_assert!($aabb.position.is_finite());
+        bodge_assert!($aabb.size.is_finite());
+        bodge_assert!($aabb.size.x >= 0.);
+        bodge_assert!($aabb.size.y >= 0.);
+    };
+}
+
+impl DAabb {
+    pub fn new(position: DVec2, size: DVec2) -> DAabb {
+        let aabb = DAabb { position, size };
+        daabb_validity_check!(aabb);
+        aabb
+    }
+
+    pub fn new_from_min_max(min: DVec2, max: DVec2) -> DAabb {
+        DAabb::new((min + max) * 0.5, max - min)
+    }
+
+    pub fn min(&self) -> DVec2 {
+        daabb_validity_check!(self);
+        self.position - self.size * 0.5
+    }
+
+    pub fn max(&self) -> DVec2 {
+        daabb_validity_check!(self);
+        self.position + self.size * 0.5
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        daabb_validity_check!(self);
+        (self.size.x + self.size.y) * 2.
+    }
+
+    pub fn union(&self, other: DAabb) -> DAabb {
+        DAabb::new_from_min_max(self.min().min(other.min()), self.max().max(other.max()))
+    }
+
+    pub fn expanded(&self, amount: f64) -> DAabb {
+        DAabb::new(self.position, self.size + DVec2::splat(amount * 2.))
+    }
+
+    pub fn contains_aabb(&self, other: DAabb) -> bool {
+        self.min().cmple(other.min()).all() && self.max().cmpge(other.max()).all()
+    }
+
+    pub fn corners(&self) -> [DVec2; 4] {
+        daabb_validity_check!(self);
+        [
+            self.position + self.size * DVec2::new(0.5, 0.5),
+            self.position + self.size * DVec2::new(-0.5, 0.5),
+            self.position + self.size * DVec2::new(-0.5, -0.5),
+            self.position + self.size * DVec2::new(0.5, -0.5),
+        ]
+    }
+
+    pub fn closest_point(&self, point: DVec2) -> DVec2 {
+        daabb_validity_check!(self);
+        point.clamp(
+            self.position - self.size * 0.5,
+            self.position + self.size * 0.5,
+        )
+    }
+
+    pub fn contains_point(&self, point: DVec2) -> bool {
+        daabb_validity_check!(self);
+        point.x > self.position.x - self.size.x * 0.5
+            && point.x < self.position.x + self.size.x * 0.5
+            && point.y > self.position.y - self.size.y * 0.5
+            && point.y < self.position.y + self.size.y * 0.5
+    }
+
+    /// Distance along the ray at which it enters this box, or zero if it starts inside.
+    pub fn ray_entry_distance(&self, ray: DLineRay2) -> Option<f64> {
+        daabb_validity_check!(self);
+        let min = self.min();
+        let max = self.max();
+        let mut entry = 0f64;
+        let mut exit = f64::INFINITY;
+        for axis in 0..2 {
+            if ray.axis[axis] == 0. {
+                if ray.start[axis] < min[axis] || ray.start[axis] > max[axis] {
+                    return None;
+                }
+            } else {
+                let t1 = (min[axis] - ray.start[axis]) / ray.axis[axis];
+                let t2 = (max[axis] - ray.start[axis]) / ray.axis[axis];
+                entry = entry.max(t1.min(t2));
+                exit = exit.min(t1.max(t2));
+            }
+        }
+        if entry <= exit {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    /// Converts to single precision, losing precision.
+    pub fn as_aabb(&self) -> Aabb {
+        Aabb {
+            position: self.position.as_vec2(),
+            size: self.size.as_vec2(),
+        }
+    }
+}
+
+impl From<Aabb> for DAabb {
+    fn from(aabb: Aabb) -> DAabb {
+        DAabb {
+            position: aabb.position.as_dvec2(),
+            size: aabb.size.as_dvec2(),
+        }
+    }
+}
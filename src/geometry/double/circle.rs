@@ -0,0 +1,56 @@
+use glam::DVec2;
+
+use crate::geometry::Circle;
+
+/// Double precision counterpart of [`Circle`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DCircle {
+    pub center: DVec2,
+    pub radius: f64,
+}
+
+macro_rules! dcircle_validity_check {
+    ($circle:expr) => {
+        bodge_assert!($circle.center.is_finite());
+        bodge_assert!($circle.radius.is_finite());
+        bodge_assert!($circle.radius > 0.);
+    };
+}
+
+impl DCircle {
+    pub fn new(center: DVec2, radius: f64) -> DCircle {
+        let circle = DCircle { center, radius };
+        dcircle_validity_check!(circle);
+        circle
+    }
+
+    pub fn closest_point(&self, point: DVec2) -> DVec2 {
+        if self.contains_point(point) {
+            point
+        } else {
+            self.center + (point - self.center).normalize() * self.radius * 0.5
+        }
+    }
+
+    pub fn contains_point(&self, point: DVec2) -> bool {
+        dcircle_validity_check!(self);
+        self.center.distance(point) <= self.radius * 0.5
+    }
+
+    /// Converts to single precision, losing precision.
+    pub fn as_circle(&self) -> Circle {
+        Circle {
+            center: self.center.as_vec2(),
+            radius: self.radius as f32,
+        }
+    }
+}
+
+impl From<Circle> for DCircle {
+    fn from(circle: Circle) -> DCircle {
+        DCircle {
+            center: circle.center.as_dvec2(),
+            radius: circle.radius as f64,
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use glam::DVec2;
+
+use crate::collision::POINT_TOLERANCE;
+use crate::geometry::{Line2, LineIntersection, LineSide, Parametric, PARALLEL_TOLERANCE};
+
+use super::{DLineRay2, DLineSegment2};
+
+/// Double precision counterpart of [`Line2`].
+// ax + by = c
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DLine2 {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+}
+
+macro_rules! dline_2_validity_check {
+    ($line_2:expr) => {
+        bodge_assert!($line_2.a != 0. || $line_2.b != 0.);
+    };
+}
+
+impl DLine2 {
+    pub fn new(a: f64, b: f64, c: f64) -> DLine2 {
+        let line = DLine2 { a, b, c };
+        dline_2_validity_check!(line);
+        line
+    }
+
+    pub fn new_from_points(point1: DVec2, point2: DVec2) -> DLine2 {
+        let a = point2.y - point1.y;
+        let b = point1.x - point2.x;
+        let c = a * point1.x + b * point1.y;
+        let line = DLine2 { a, b, c };
+        dline_2_validity_check!(line);
+        line
+    }
+
+    pub fn new_from_point_axis(point: DVec2, axis: DVec2) -> DLine2 {
+        bodge_assert!(axis.length_squared() > 0.);
+        DLine2::new_from_points(point, point + axis)
+    }
+
+    pub fn x(&self, y: f64) -> Option<f64> {
+        dline_2_validity_check!(self);
+        if self.a != 0.0 {
+            Some((self.c - self.b * y) / self.a)
+        } else {
+            None
+        }
+    }
+
+    pub fn y(&self, x: f64) -> Option<f64> {
+        dline_2_validity_check!(self);
+        if self.b != 0.0 {
+            Some((self.c - self.a * x) / self.b)
+        } else {
+            None
+        }
+    }
+
+    pub fn axis(&self) -> DVec2 {
+        dline_2_validity_check!(self);
+        DVec2::new(-self.b, self.a).normalize()
+    }
+
+    /// Unit normal, pointing to the [`LineSide::Right`] of [`DLine2::axis`].
+    pub fn normal(&self) -> DVec2 {
+        dline_2_validity_check!(self);
+        DVec2::new(self.a, self.b).normalize()
+    }
+
+    /// The same line with `(a, b)` scaled to unit length.
+    pub fn normalized(&self) -> DLine2 {
+        dline_2_validity_check!(self);
+        let length = DVec2::new(self.a, self.b).length();
+        DLine2::new(self.a / length, self.b / length, self.c / length)
+    }
+
+    /// Distance from the line, positive on the side [`DLine2::normal`] points to.
+    pub fn signed_distance(&self, point: DVec2) -> f64 {
+        let line = self.normalized();
+        line.a * point.x + line.b * point.y - line.c
+    }
+
+    pub fn distance(&self, point: DVec2) -> f64 {
+        self.signed_distance(point).abs()
+    }
+
+    pub fn side_of(&self, point: DVec2) -> LineSide {
+        dline_2_validity_check!(self);
+        let side = self.a * point.x + self.b * point.y - self.c;
+        if side > 0. {
+            LineSide::Right
+        } else if side < 0. {
+            LineSide::Left
+        } else {
+            LineSide::On
+        }
+    }
+
+    pub fn closest_point(&self, point: DVec2) -> DVec2 {
+        point - self.normal() * self.signed_distance(point)
+    }
+
+    pub fn project_point(&self, point: DVec2) -> DVec2 {
+        self.closest_point(point)
+    }
+
+    pub fn project_vector(&self, vector: DVec2) -> DVec2 {
+        let axis = self.axis();
+        axis * vector.dot(axis)
+    }
+
+    pub fn reflect_point(&self, point: DVec2) -> DVec2 {
+        point - self.normal() * self.signed_distance(point) * 2.
+    }
+
+    pub fn reflect_vector(&self, vector: DVec2) -> DVec2 {
+        let normal = self.normal();
+        vector - normal * vector.dot(normal) * 2.
+    }
+
+    pub fn is_parallel(&self, other: DLine2) -> bool {
+        self.normal().perp_dot(other.normal()).abs() <= PARALLEL_TOLERANCE as f64
+    }
+
+    pub fn is_coincident(&self, other: DLine2) -> bool {
+        self.is_parallel(other)
+            && self.distance(other.closest_point(DVec2::ZERO)) <= POINT_TOLERANCE as f64
+    }
+
+    pub fn intersection(&self, other: DLine2) -> LineIntersection<DVec2> {
+        Parametric::from(*self).intersection(&Parametric::from(other))
+    }
+
+    pub fn intersection_ray(&self, ray: DLineRay2) -> LineIntersection<DVec2> {
+        Parametric::from(*self).intersection(&Parametric::from(ray))
+    }
+
+    pub fn intersection_segment(&self, segment: DLineSegment2) -> LineIntersection<DVec2> {
+        Parametric::from(*self).intersection(&Parametric::from(segment))
+    }
+
+    pub fn intersection_point(&self, other: DLine2) -> Option<DVec2> {
+        match self.intersection(other) {
+            LineIntersection::Point(point) => Some(point),
+            _ => None,
+        }
+    }
+
+    /// Converts to single precision, losing precision.
+    pub fn as_line(&self) -> Line2 {
+        Line2 {
+            a: self.a as f32,
+            b: self.b as f32,
+            c: self.c as f32,
+        }
+    }
+}
+
+impl From<Line2> for DLine2 {
+    fn from(line: Line2) -> DLine2 {
+        DLine2 {
+            a: line.a as f64,
+            b: line.b as f64,
+            c: line.c as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dline_queries() {
+        let line = DLine2::new_from_points(DVec2::new(0., 1.), DVec2::new(2., 1.));
+        assert_eq!(line.signed_distance(DVec2::new(5., 4.)), -3.);
+        assert_eq!(line.side_of(DVec2::new(5., -4.)), LineSide::Right);
+        assert_eq!(line.closest_point(DVec2::new(5., 4.)), DVec2::new(5., 1.));
+        assert_eq!(line.reflect_point(DVec2::new(5., 4.)), DVec2::new(5., -2.));
+        assert!(line.is_coincident(DLine2::new(0., -2., -2.)));
+        assert_eq!(
+            line.intersection(DLine2::new(0., -2., -2.)),
+            LineIntersection::Coincident
+        );
+        assert_eq!(
+            line.intersection_point(DLine2::new(1., 0., 4.)),
+            Some(DVec2::new(4., 1.))
+        );
+
+        let segment = DLineSegment2::new(DVec2::ZERO, DVec2::new(4., 0.));
+        assert_eq!(
+            segment.intersection(DLineSegment2::new(DVec2::new(3., 0.), DVec2::new(6., 0.))),
+            LineIntersection::Coincident
+        );
+        assert_eq!(
+            DLineRay2::new(DVec2::new(2., -2.), DVec2::Y).intersection_segment(segment),
+            LineIntersection::Point(DVec2::new(2., 0.))
+        );
+    }
+}
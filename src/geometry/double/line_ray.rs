@@ -0,0 +1,75 @@
+use glam::DVec2;
+
+use crate::geometry::{LineIntersection, LineRay2, Parametric};
+
+use super::{DLine2, DLineSegment2};
+
+/// Double precision counterpart of [`LineRay2`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DLineRay2 {
+    pub start: DVec2,
+    pub axis: DVec2,
+}
+
+macro_rules! dline_ray_2_validity_check {
+    ($line_ray_2:expr) => {
+        bodge_assert!($line_ray_2.start.is_finite());
+        bodge_assert!($line_ray_2.axis.is_finite());
+        bodge_assert!($line_ray_2.axis.is_normalized());
+    };
+}
+
+impl DLineRay2 {
+    pub fn new(start: DVec2, axis: DVec2) -> DLineRay2 {
+        let line_ray = DLineRay2 {
+            start,
+            axis: axis.normalize_or_zero(),
+        };
+        dline_ray_2_validity_check!(line_ray);
+        line_ray
+    }
+
+    pub fn closest_point(&self, point: DVec2) -> DVec2 {
+        dline_ray_2_validity_check!(self);
+        self.start + (point - self.start).dot(self.axis).max(0.) * self.axis
+    }
+
+    pub fn segment(&self, length: f64) -> DLineSegment2 {
+        dline_ray_2_validity_check!(self);
+        DLineSegment2::new(self.start, self.start + self.axis * length)
+    }
+
+    pub fn line(&self) -> DLine2 {
+        dline_ray_2_validity_check!(self);
+        DLine2::new_from_point_axis(self.start, self.axis)
+    }
+
+    pub fn intersection(&self, other: DLineRay2) -> LineIntersection<DVec2> {
+        Parametric::from(*self).intersection(&Parametric::from(other))
+    }
+
+    pub fn intersection_segment(&self, segment: DLineSegment2) -> LineIntersection<DVec2> {
+        Parametric::from(*self).intersection(&Parametric::from(segment))
+    }
+
+    pub fn intersection_line(&self, line: DLine2) -> LineIntersection<DVec2> {
+        Parametric::from(*self).intersection(&Parametric::from(line))
+    }
+
+    /// Converts to single precision, losing precision.
+    pub fn as_line_ray(&self) -> LineRay2 {
+        LineRay2 {
+            start: self.start.as_vec2(),
+            axis: self.axis.as_vec2(),
+        }
+    }
+}
+
+impl From<LineRay2> for DLineRay2 {
+    fn from(line_ray: LineRay2) -> DLineRay2 {
+        DLineRay2 {
+            start: line_ray.start.as_dvec2(),
+            axis: line_ray.axis.as_dvec2(),
+        }
+    }
+}
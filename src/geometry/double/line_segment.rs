@@ -0,0 +1,156 @@
+use glam::DVec2;
+
+use crate::geometry::{dorient2d, LineIntersection, LineSegment2, Parametric};
+
+use super::{DLine2, DLineRay2};
+
+/// Double precision counterpart of [`LineSegment2`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DLineSegment2 {
+    pub start: DVec2,
+    pub end: DVec2,
+}
+
+macro_rules! dline_segment_2_validity_check {
+    ($line_segment_2:expr) => {
+        bodge_assert!($line_segment_2.start.is_finite());
+        bodge_assert!($line_segment_2.end.is_finite());
+    };
+}
+
+impl DLineSegment2 {
+    pub fn new(start: DVec2, end: DVec2) -> DLineSegment2 {
+        let line_segment = DLineSegment2 { start, end };
+        dline_segment_2_validity_check!(line_segment);
+        line_segment
+    }
+
+    pub fn center(&self) -> DVec2 {
+        dline_segment_2_validity_check!(self);
+        (self.start + self.end) * 0.5
+    }
+
+    pub fn length(&self) -> f64 {
+        dline_segment_2_validity_check!(self);
+        self.start.distance(self.end)
+    }
+
+    pub fn axis(&self) -> DVec2 {
+        dline_segment_2_validity_check!(self);
+        (self.end - self.start).normalize_or_zero()
+    }
+
+    pub fn closest_point(&self, point: DVec2) -> DVec2 {
+        bodge_assert!(point.is_finite());
+        let ab = self.end - self.start;
+        let t = ((point - self.start).dot(ab) / ab.dot(ab)).clamp(0., 1.);
+        self.start + t * ab
+    }
+
+    pub fn perpendicular_bisector(&self) -> DLine2 {
+        dline_segment_2_validity_check!(self);
+        DLine2::new_from_point_axis(self.center(), -self.axis().perp())
+    }
+
+    pub fn is_same(&self, other: DLineSegment2) -> bool {
+        (self.start == other.start && self.end == other.end)
+            || (self.start == other.end && self.end == other.start)
+    }
+
+    pub fn line(&self) -> DLine2 {
+        DLine2::new_from_points(self.start, self.end)
+    }
+
+    /// Where two segments meet. Crossing, touching and overlapping are told apart exactly with
+    /// [`dorient2d`], so only the position of a crossing is rounded.
+    pub fn intersection(&self, other: DLineSegment2) -> LineIntersection<DVec2> {
+        let (a_start, a_end) = (
+            dorient2d(other.start, other.end, self.start),
+            dorient2d(other.start, other.end, self.end),
+        );
+        let (b_start, b_end) = (
+            dorient2d(self.start, self.end, other.start),
+            dorient2d(self.start, self.end, other.end),
+        );
+        if [a_start, a_end, b_start, b_end] == [0.; 4] {
+            return self.collinear_intersection(other);
+        }
+        let same_side = |p: f64, q: f64| (p > 0. && q > 0.) || (p < 0. && q < 0.);
+        if same_side(a_start, a_end) || same_side(b_start, b_end) {
+            return LineIntersection::None;
+        }
+        LineIntersection::Point(if b_start == 0. {
+            other.start
+        } else if b_end == 0. {
+            other.end
+        } else if a_start == 0. {
+            self.start
+        } else if a_end == 0. {
+            self.end
+        } else {
+            self.start + (self.end - self.start) * (a_start / (a_start - a_end))
+        })
+    }
+
+    pub fn intersection_ray(&self, ray: DLineRay2) -> LineIntersection<DVec2> {
+        Parametric::from(*self).intersection(&Parametric::from(ray))
+    }
+
+    pub fn intersection_line(&self, line: DLine2) -> LineIntersection<DVec2> {
+        Parametric::from(*self).intersection(&Parametric::from(line))
+    }
+
+    // both segments lie on one line, so compare them along an axis that changes along it
+    fn collinear_intersection(&self, other: DLineSegment2) -> LineIntersection<DVec2> {
+        let axis = if [self.end, other.start, other.end]
+            .iter()
+            .any(|point| point.x != self.start.x)
+        {
+            0
+        } else {
+            1
+        };
+        let ordered = |segment: DLineSegment2| {
+            if segment.start[axis] <= segment.end[axis] {
+                (segment.start, segment.end)
+            } else {
+                (segment.end, segment.start)
+            }
+        };
+        let ((a_min, a_max), (b_min, b_max)) = (ordered(*self), ordered(other));
+        let low = if a_min[axis] >= b_min[axis] {
+            a_min
+        } else {
+            b_min
+        };
+        let high = if a_max[axis] <= b_max[axis] {
+            a_max
+        } else {
+            b_max
+        };
+        if low[axis] > high[axis] {
+            LineIntersection::None
+        } else if low[axis] == high[axis] {
+            LineIntersection::Point(low)
+        } else {
+            LineIntersection::Coincident
+        }
+    }
+
+    /// Converts to single precision, losing precision.
+    pub fn as_line_segment(&self) -> LineSegment2 {
+        LineSegment2 {
+            start: self.start.as_vec2(),
+            end: self.end.as_vec2(),
+        }
+    }
+}
+
+impl From<LineSegment2> for DLineSegment2 {
+    fn from(line_segment: LineSegment2) -> DLineSegment2 {
+        DLineSegment2 {
+            start: line_segment.start.as_dvec2(),
+            end: line_segment.end.as_dvec2(),
+        }
+    }
+}
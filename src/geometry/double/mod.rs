@@ -0,0 +1,24 @@
+//! Double precision counterparts of the geometry types, for coordinates too large for `f32`.
+//!
+//! They implement [`Colliding`](crate::collision::Colliding) between each other, with the same
+//! narrowphase as the single precision types. The broadphases (`SpatialHash`, `AabbTree`,
+//! `Quadtree` and `SweepAndPrune`) and `time_of_impact` stay single precision only: subtract a
+//! nearby origin from the coordinates and convert with `as_aabb` and friends to use them.
+
+mod aabb;
+mod circle;
+mod line;
+mod line_ray;
+mod line_segment;
+mod polyline;
+mod triangle;
+mod vertex_list;
+
+pub use aabb::*;
+pub use circle::*;
+pub use line::*;
+pub use line_ray::*;
+pub use line_segment::*;
+pub use polyline::*;
+pub use triangle::*;
+pub use vertex_list::*;
@@ -0,0 +1,63 @@
+use glam::DVec2;
+
+use crate::geometry::Polyline;
+
+use super::DLineSegment2;
+
+/// Double precision counterpart of [`Polyline`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DPolyline(pub Vec<DVec2>);
+
+impl DPolyline {
+    pub fn new() -> DPolyline {
+        DPolyline(Vec::new())
+    }
+
+    pub fn line_segments(&self) -> Vec<DLineSegment2> {
+        // TODO: make this an iterator instead?
+        if self.0.is_empty() {
+            vec![]
+        } else {
+            let mut edges = vec![];
+            for point_index in 0..(self.0.len() - 1) {
+                let next_point_index = (point_index + 1) % self.0.len();
+                edges.push(DLineSegment2::new(
+                    self.0[point_index],
+                    self.0[next_point_index],
+                ));
+            }
+            edges
+        }
+    }
+
+    pub fn closest_point(&self, point: DVec2) -> DVec2 {
+        bodge_assert!(!self.0.is_empty());
+        bodge_assert!(point.is_finite());
+        if self.0.len() == 1 {
+            self.0[0]
+        } else {
+            let line_segments = self.line_segments();
+            let first_closest_point = self.line_segments()[0].closest_point(point);
+            let mut closest = (first_closest_point.distance(point), first_closest_point);
+            for line_segment in line_segments.iter().skip(1) {
+                let closest_point = line_segment.closest_point(point);
+                let distance = closest_point.distance(point);
+                if distance < closest.0 {
+                    closest = (distance, closest_point)
+                }
+            }
+            closest.1
+        }
+    }
+
+    /// Converts to single precision, losing precision.
+    pub fn as_polyline(&self) -> Polyline {
+        Polyline(self.0.iter().map(|point| point.as_vec2()).collect())
+    }
+}
+
+impl From<&Polyline> for DPolyline {
+    fn from(polyline: &Polyline) -> DPolyline {
+        DPolyline(polyline.0.iter().map(|point| point.as_dvec2()).collect())
+    }
+}
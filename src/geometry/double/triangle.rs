@@ -0,0 +1,205 @@
+use glam::DVec2;
+
+use crate::geometry::{dincircle, dorient2d, Triangle2};
+
+use super::{DCircle, DLine2, DLineSegment2};
+
+/// Double precision counterpart of [`Triangle2`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DTriangle2 {
+    pub vertices: [DVec2; 3],
+}
+
+macro_rules! dtriangle_2_validity_check {
+    ($triangle_2:expr) => {
+        bodge_assert!($triangle_2.vertices[0].is_finite());
+        bodge_assert!($triangle_2.vertices[1].is_finite());
+        bodge_assert!($triangle_2.vertices[2].is_finite());
+    };
+}
+
+impl DTriangle2 {
+    pub fn new(a: DVec2, b: DVec2, c: DVec2) -> DTriangle2 {
+        let triangle = DTriangle2 {
+            vertices: [a, b, c],
+        };
+        dtriangle_2_validity_check!(triangle);
+        triangle
+    }
+
+    pub fn a(&self) -> DVec2 {
+        dtriangle_2_validity_check!(self);
+        self.vertices[0]
+    }
+
+    pub fn set_a(&mut self, a: DVec2) {
+        self.vertices[0] = a;
+        dtriangle_2_validity_check!(self);
+    }
+
+    pub fn b(&self) -> DVec2 {
+        dtriangle_2_validity_check!(self);
+        self.vertices[1]
+    }
+
+    pub fn set_b(&mut self, b: DVec2) {
+        self.vertices[1] = b;
+        dtriangle_2_validity_check!(self);
+    }
+
+    pub fn c(&self) -> DVec2 {
+        dtriangle_2_validity_check!(self);
+        self.vertices[2]
+    }
+
+    pub fn set_c(&mut self, c: DVec2) {
+        self.vertices[2] = c;
+        dtriangle_2_validity_check!(self);
+    }
+
+    pub fn a_angle(&self) -> f64 {
+        (self.c() - self.a())
+            .angle_between(self.b() - self.a())
+            .abs()
+    }
+
+    pub fn b_angle(&self) -> f64 {
+        (self.c() - self.b())
+            .angle_between(self.a() - self.b())
+            .abs()
+    }
+
+    pub fn c_angle(&self) -> f64 {
+        (self.a() - self.c())
+            .angle_between(self.b() - self.c())
+            .abs()
+    }
+
+    pub fn a_angle_bisector(&self) -> DLine2 {
+        DLine2::new_from_point_axis(
+            self.a(),
+            DVec2::from_angle(
+                (DVec2::X.angle_between(self.b() - self.a())
+                    + DVec2::X.angle_between(self.c() - self.a()))
+                    * 0.5,
+            ),
+        )
+    }
+
+    pub fn b_angle_bisector(&self) -> DLine2 {
+        DLine2::new_from_point_axis(
+            self.b(),
+            DVec2::from_angle(
+                (DVec2::X.angle_between(self.c() - self.b())
+                    + DVec2::X.angle_between(self.a() - self.b()))
+                    * 0.5,
+            ),
+        )
+    }
+
+    pub fn c_angle_bisector(&self) -> DLine2 {
+        DLine2::new_from_point_axis(
+            self.c(),
+            DVec2::from_angle(
+                (DVec2::X.angle_between(self.b() - self.c())
+                    + DVec2::X.angle_between(self.a() - self.c()))
+                    * 0.5,
+            ),
+        )
+    }
+
+    pub fn edges(&self) -> [DLineSegment2; 3] {
+        dtriangle_2_validity_check!(self);
+        [self.ab(), self.bc(), self.ca()]
+    }
+
+    pub fn ab(&self) -> DLineSegment2 {
+        dtriangle_2_validity_check!(self);
+        DLineSegment2::new(self.a(), self.b())
+    }
+
+    pub fn bc(&self) -> DLineSegment2 {
+        dtriangle_2_validity_check!(self);
+        DLineSegment2::new(self.b(), self.c())
+    }
+
+    pub fn ca(&self) -> DLineSegment2 {
+        dtriangle_2_validity_check!(self);
+        DLineSegment2::new(self.c(), self.a())
+    }
+
+    pub fn circumcenter(&self) -> Option<DVec2> {
+        dtriangle_2_validity_check!(self);
+        let bisector1 = self.ab().perpendicular_bisector();
+        let bisector2 = self.bc().perpendicular_bisector();
+        bisector1.intersection_point(bisector2)
+    }
+
+    pub fn circumcircle(&self) -> Option<DCircle> {
+        dtriangle_2_validity_check!(self);
+        let circumcenter = self.circumcenter()?;
+        Some(DCircle::new(
+            circumcenter,
+            circumcenter.distance(self.a()) * 2.,
+        ))
+    }
+
+    pub fn centroid(&self) -> Option<DVec2> {
+        dtriangle_2_validity_check!(self);
+        let line1 = DLine2::new_from_points(self.a(), self.bc().center());
+        let line2 = DLine2::new_from_points(self.b(), self.ca().center());
+        line1.intersection_point(line2)
+    }
+
+    pub fn scale(&mut self, scale: f64) {
+        if let Some(centroid) = self.centroid() {
+            self.vertices[0] = centroid + (self.vertices[0] - centroid) * scale;
+            self.vertices[1] = centroid + (self.vertices[1] - centroid) * scale;
+            self.vertices[2] = centroid + (self.vertices[2] - centroid) * scale;
+        }
+    }
+
+    pub fn scaled(&self, scale: f64) -> DTriangle2 {
+        let mut triangle = *self;
+        triangle.scale(scale);
+        triangle
+    }
+
+    pub fn contains_point(&self, point: DVec2) -> bool {
+        let d1 = dorient2d(point, self.a(), self.b());
+        let d2 = dorient2d(point, self.b(), self.c());
+        let d3 = dorient2d(point, self.c(), self.a());
+        let has_neg = (d1 < 0.) || (d2 < 0.) || (d3 < 0.);
+        let has_pos = (d1 > 0.) || (d2 > 0.) || (d3 > 0.);
+        !(has_neg && has_pos)
+    }
+
+    /// Whether a point lies strictly inside the circumcircle, decided exactly.
+    pub fn circumcircle_contains_point(&self, point: DVec2) -> bool {
+        let determinant = dincircle(self.a(), self.b(), self.c(), point);
+        if self.is_clockwise() {
+            determinant < 0.
+        } else {
+            determinant > 0.
+        }
+    }
+
+    pub fn is_clockwise(&self) -> bool {
+        dorient2d(self.a(), self.b(), self.c()) < 0.
+    }
+
+    /// Converts to single precision, losing precision.
+    pub fn as_triangle(&self) -> Triangle2 {
+        Triangle2 {
+            vertices: self.vertices.map(|vertex| vertex.as_vec2()),
+        }
+    }
+}
+
+impl From<Triangle2> for DTriangle2 {
+    fn from(triangle: Triangle2) -> DTriangle2 {
+        DTriangle2 {
+            vertices: triangle.vertices.map(|vertex| vertex.as_dvec2()),
+        }
+    }
+}
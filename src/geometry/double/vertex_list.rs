@@ -0,0 +1,101 @@
+use glam::DVec2;
+
+use crate::geometry::VertexList2;
+
+use crate::collision::Colliding;
+
+use super::DLineSegment2;
+
+/// Double precision counterpart of [`VertexList2`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DVertexList2(pub Vec<DVec2>);
+
+impl DVertexList2 {
+    pub fn new() -> DVertexList2 {
+        DVertexList2(Vec::new())
+    }
+
+    pub fn edges(&self) -> Vec<DLineSegment2> {
+        // TODO: make this an iterator instead?
+        let mut edges = vec![];
+        for point_index in 0..self.0.len() {
+            let next_point_index = (point_index + 1) % self.0.len();
+            edges.push(DLineSegment2::new(
+                self.0[point_index],
+                self.0[next_point_index],
+            ));
+        }
+        edges
+    }
+
+    pub fn is_simple_polygon(&self) -> bool {
+        // TODO: rewrite with a sweep line algorithm
+        let edges = self.edges();
+        for edge_index in 0..edges.len() {
+            let edge = edges[edge_index];
+            let last_edge_index = edges.len() - if edge_index == 0 { 1 } else { 0 };
+            for other_edge in edges[..last_edge_index].iter().skip(edge_index + 2) {
+                if edge.colliding(other_edge) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn contains_point(&self, point: DVec2) -> bool {
+        bodge_assert!(point.is_finite());
+        let mut inside = false;
+        for point_index in 0..self.0.len() {
+            let start = self.0[point_index];
+            let end = self.0[(point_index + 1) % self.0.len()];
+            if (start.y > point.y) != (end.y > point.y)
+                && point.x < start.x + (point.y - start.y) / (end.y - start.y) * (end.x - start.x)
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    pub fn is_clockwise(&self) -> bool {
+        let mut area = 0.;
+        for i in 0..self.0.len() {
+            let j = (i + 1) % self.0.len();
+            area += self.0[i].x * self.0[j].y;
+            area -= self.0[j].x * self.0[i].y;
+        }
+        area / 2. > 0.
+    }
+
+    pub fn make_clockwise(&mut self) {
+        if !self.is_clockwise() {
+            let mut new_points = vec![];
+            for point in self.0.iter().rev() {
+                new_points.push(*point);
+            }
+            self.0 = new_points;
+        }
+    }
+
+    pub fn make_counterclockwise(&mut self) {
+        if self.is_clockwise() {
+            let mut new_points = vec![];
+            for point in self.0.iter().rev() {
+                new_points.push(*point);
+            }
+            self.0 = new_points;
+        }
+    }
+
+    /// Converts to single precision, losing precision.
+    pub fn as_vertex_list(&self) -> VertexList2 {
+        VertexList2(self.0.iter().map(|point| point.as_vec2()).collect())
+    }
+}
+
+impl From<&VertexList2> for DVertexList2 {
+    fn from(vertex_list: &VertexList2) -> DVertexList2 {
+        DVertexList2(vertex_list.0.iter().map(|point| point.as_dvec2()).collect())
+    }
+}
@@ -0,0 +1,612 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use super::{orient2d, Triangle2, VertexList2};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VertexKey(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HalfEdgeKey(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FaceKey(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HalfEdge {
+    origin: usize,
+    twin: usize,
+    next: usize,
+    // none on the boundary, where half edges run clockwise round the outside
+    face: Option<usize>,
+}
+
+/// A triangulation with full connectivity. Every edge is a pair of twin half edges, one for each
+/// side, and the half edges of a face run counterclockwise in the sense of
+/// [`orient2d`](super::orient2d).
+///
+/// Each vertex must have its triangles in a single fan, so two triangles may not meet at just a
+/// corner.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HalfEdgeMesh {
+    positions: Vec<Vec2>,
+    // one half edge leaving each vertex, on the boundary for boundary vertices
+    vertex_edges: Vec<usize>,
+    half_edges: Vec<HalfEdge>,
+    face_edges: Vec<usize>,
+}
+
+impl HalfEdgeMesh {
+    /// Joins triangles along the edges whose end points are identical.
+    pub fn new(triangles: &[Triangle2]) -> HalfEdgeMesh {
+        let mut positions = vec![];
+        let mut indices = HashMap::new();
+        let mut index_of = |point: Vec2| {
+            // adding zero turns -0.0 into 0.0, which would otherwise hash differently
+            let point = point + Vec2::ZERO;
+            *indices
+                .entry((point.x.to_bits(), point.y.to_bits()))
+                .or_insert_with(|| {
+                    positions.push(point);
+                    positions.len() - 1
+                })
+        };
+        let faces: Vec<[usize; 3]> = triangles
+            .iter()
+            .map(|triangle| triangle.vertices.map(&mut index_of))
+            .collect();
+        HalfEdgeMesh::new_indexed(&positions, &faces)
+    }
+
+    /// Builds the mesh from triangles given as indices into `positions`, in either winding.
+    /// Triangles repeating a corner, or an edge in the same direction as an earlier triangle,
+    /// can't be joined up. They fail a `bodge_assert`, or are skipped without it, in which case
+    /// the face keys after them no longer match their index in `faces`.
+    pub fn new_indexed(positions: &[Vec2], faces: &[[usize; 3]]) -> HalfEdgeMesh {
+        let mut mesh = HalfEdgeMesh {
+            positions: positions.to_vec(),
+            vertex_edges: vec![usize::MAX; positions.len()],
+            half_edges: vec![],
+            face_edges: vec![],
+        };
+        let mut directed = HashMap::new();
+        for face in faces.iter() {
+            bodge_assert!(face.iter().all(|index| *index < positions.len()));
+            let [a, mut b, mut c] = *face;
+            if orient2d(positions[a], positions[b], positions[c]) < 0. {
+                (b, c) = (c, b);
+            }
+            let sides = [(a, b), (b, c), (c, a)];
+            let joinable =
+                a != b && b != c && c != a && !sides.iter().any(|side| directed.contains_key(side));
+            bodge_assert!(joinable);
+            if !joinable {
+                continue;
+            }
+            let first = mesh.half_edges.len();
+            for (corner, origin) in [a, b, c].into_iter().enumerate() {
+                mesh.half_edges.push(HalfEdge {
+                    origin,
+                    twin: usize::MAX,
+                    next: first + (corner + 1) % 3,
+                    face: Some(mesh.face_edges.len()),
+                });
+                mesh.vertex_edges[origin] = first + corner;
+            }
+            for (corner, side) in sides.into_iter().enumerate() {
+                directed.insert(side, first + corner);
+            }
+            mesh.face_edges.push(first);
+        }
+
+        // pair up twins, giving unshared edges a twin on the boundary
+        let mut boundary = vec![];
+        for index in 0..mesh.half_edges.len() {
+            if mesh.half_edges[index].twin != usize::MAX {
+                continue;
+            }
+            let (start, end) = (mesh.half_edges[index].origin, mesh.destination_index(index));
+            let twin = directed.get(&(end, start)).copied().unwrap_or_else(|| {
+                mesh.half_edges.push(HalfEdge {
+                    origin: end,
+                    twin: index,
+                    next: usize::MAX,
+                    face: None,
+                });
+                boundary.push(mesh.half_edges.len() - 1);
+                mesh.half_edges.len() - 1
+            });
+            mesh.half_edges[index].twin = twin;
+            mesh.half_edges[twin].twin = index;
+        }
+        // a boundary half edge continues with the first boundary half edge found by turning
+        // round its end through the triangles there
+        for index in boundary {
+            let mut around = mesh.half_edges[index].twin;
+            loop {
+                let twin = mesh.half_edges[mesh.previous_index(around)].twin;
+                if mesh.half_edges[twin].face.is_none() {
+                    mesh.half_edges[index].next = twin;
+                    mesh.vertex_edges[mesh.half_edges[twin].origin] = twin;
+                    break;
+                }
+                around = twin;
+            }
+        }
+        mesh
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn half_edge_count(&self) -> usize {
+        self.half_edges.len()
+    }
+
+    pub fn face_count(&self) -> usize {
+        self.face_edges.len()
+    }
+
+    pub fn vertices(&self) -> impl Iterator<Item = VertexKey> {
+        (0..self.positions.len()).map(VertexKey)
+    }
+
+    pub fn half_edges(&self) -> impl Iterator<Item = HalfEdgeKey> {
+        (0..self.half_edges.len()).map(HalfEdgeKey)
+    }
+
+    pub fn faces(&self) -> impl Iterator<Item = FaceKey> {
+        (0..self.face_edges.len()).map(FaceKey)
+    }
+
+    pub fn position(&self, vertex: VertexKey) -> Vec2 {
+        self.positions[vertex.0]
+    }
+
+    pub fn set_position(&mut self, vertex: VertexKey, position: Vec2) {
+        bodge_assert!(position.is_finite());
+        self.positions[vertex.0] = position;
+    }
+
+    pub fn origin(&self, half_edge: HalfEdgeKey) -> VertexKey {
+        VertexKey(self.half_edges[half_edge.0].origin)
+    }
+
+    pub fn destination(&self, half_edge: HalfEdgeKey) -> VertexKey {
+        VertexKey(self.destination_index(half_edge.0))
+    }
+
+    pub fn twin(&self, half_edge: HalfEdgeKey) -> HalfEdgeKey {
+        HalfEdgeKey(self.half_edges[half_edge.0].twin)
+    }
+
+    pub fn next(&self, half_edge: HalfEdgeKey) -> HalfEdgeKey {
+        HalfEdgeKey(self.half_edges[half_edge.0].next)
+    }
+
+    /// The half edge before this one round its face, or round the boundary.
+    pub fn previous(&self, half_edge: HalfEdgeKey) -> HalfEdgeKey {
+        HalfEdgeKey(self.previous_index(half_edge.0))
+    }
+
+    /// The face on the left of the half edge, none for the outside of the boundary.
+    pub fn face(&self, half_edge: HalfEdgeKey) -> Option<FaceKey> {
+        self.half_edges[half_edge.0].face.map(FaceKey)
+    }
+
+    /// Whether the half edge or its twin lies on the outside.
+    pub fn is_boundary_edge(&self, half_edge: HalfEdgeKey) -> bool {
+        self.face(half_edge).is_none() || self.face(self.twin(half_edge)).is_none()
+    }
+
+    pub fn is_boundary_vertex(&self, vertex: VertexKey) -> bool {
+        self.half_edges
+            .get(self.vertex_edges[vertex.0])
+            .is_some_and(|half_edge| half_edge.face.is_none())
+    }
+
+    /// The half edges leaving a vertex, turning clockwise and starting on the boundary if it
+    /// is there. Empty for vertices no triangle uses.
+    pub fn outgoing(&self, vertex: VertexKey) -> Vec<HalfEdgeKey> {
+        let first = self.vertex_edges[vertex.0];
+        if first == usize::MAX {
+            return vec![];
+        }
+        let mut half_edges = vec![HalfEdgeKey(first)];
+        let mut current = self.half_edges[self.half_edges[first].twin].next;
+        while current != first {
+            half_edges.push(HalfEdgeKey(current));
+            current = self.half_edges[self.half_edges[current].twin].next;
+        }
+        half_edges
+    }
+
+    pub fn vertex_neighbours(&self, vertex: VertexKey) -> Vec<VertexKey> {
+        self.outgoing(vertex)
+            .into_iter()
+            .map(|half_edge| self.destination(half_edge))
+            .collect()
+    }
+
+    pub fn vertex_faces(&self, vertex: VertexKey) -> Vec<FaceKey> {
+        self.outgoing(vertex)
+            .into_iter()
+            .filter_map(|half_edge| self.face(half_edge))
+            .collect()
+    }
+
+    /// The half edge from `start` to `end`, if they share an edge.
+    pub fn find_half_edge(&self, start: VertexKey, end: VertexKey) -> Option<HalfEdgeKey> {
+        self.outgoing(start)
+            .into_iter()
+            .find(|half_edge| self.destination(*half_edge) == end)
+    }
+
+    pub fn face_half_edges(&self, face: FaceKey) -> [HalfEdgeKey; 3] {
+        let first = self.face_edges[face.0];
+        let second = self.half_edges[first].next;
+        [first, second, self.half_edges[second].next].map(HalfEdgeKey)
+    }
+
+    pub fn face_vertices(&self, face: FaceKey) -> [VertexKey; 3] {
+        self.face_half_edges(face)
+            .map(|half_edge| self.origin(half_edge))
+    }
+
+    /// The faces across each edge of a face, leaving out the boundary.
+    pub fn face_neighbours(&self, face: FaceKey) -> Vec<FaceKey> {
+        self.face_half_edges(face)
+            .into_iter()
+            .filter_map(|half_edge| self.face(self.twin(half_edge)))
+            .collect()
+    }
+
+    pub fn triangle(&self, face: FaceKey) -> Triangle2 {
+        let [a, b, c] = self.face_vertices(face).map(|vertex| self.position(vertex));
+        Triangle2::new(a, b, c)
+    }
+
+    pub fn to_triangles(&self) -> Vec<Triangle2> {
+        self.faces().map(|face| self.triangle(face)).collect()
+    }
+
+    /// Every boundary half edge, each loop of them running clockwise round the mesh or
+    /// counterclockwise round a hole.
+    pub fn boundary_half_edges(&self) -> Vec<HalfEdgeKey> {
+        self.half_edges()
+            .filter(|half_edge| self.face(*half_edge).is_none())
+            .collect()
+    }
+
+    /// The outline of every boundary loop, in the same direction as its half edges.
+    pub fn boundary_loops(&self) -> Vec<VertexList2> {
+        let mut visited = vec![false; self.half_edges.len()];
+        let mut loops = vec![];
+        for start in self.boundary_half_edges() {
+            if visited[start.0] {
+                continue;
+            }
+            let mut points = vec![];
+            let mut current = start;
+            while !visited[current.0] {
+                visited[current.0] = true;
+                points.push(self.position(self.origin(current)));
+                current = self.next(current);
+            }
+            loops.push(VertexList2(points));
+        }
+        loops
+    }
+
+    /// Swaps the edge shared by two triangles for the other diagonal of the quadrilateral they
+    /// make. Refused on the boundary, and when the quadrilateral isn't convex since the new
+    /// triangles would overlap.
+    pub fn flip_edge(&mut self, half_edge: HalfEdgeKey) -> bool {
+        let e = half_edge.0;
+        let t = self.half_edges[e].twin;
+        let (Some(f), Some(g)) = (self.half_edges[e].face, self.half_edges[t].face) else {
+            return false;
+        };
+        let (e1, t1) = (self.half_edges[e].next, self.half_edges[t].next);
+        let (e2, t2) = (self.half_edges[e1].next, self.half_edges[t1].next);
+        let (u, v) = (self.half_edges[e].origin, self.half_edges[t].origin);
+        let (a, b) = (self.half_edges[e2].origin, self.half_edges[t2].origin);
+        let [pu, pv, pa, pb] = [u, v, a, b].map(|index| self.positions[index]);
+        if orient2d(pu, pb, pa) <= 0. || orient2d(pb, pv, pa) <= 0. {
+            return false;
+        }
+        // the edge now runs between a and b, with u, b, a on one side and b, v, a on the other
+        self.link(e, b, e2, f);
+        self.link(e2, a, t1, f);
+        self.link(t1, u, e, f);
+        self.link(t, a, t2, g);
+        self.link(t2, b, e1, g);
+        self.link(e1, v, t, g);
+        self.face_edges[f] = e;
+        self.face_edges[g] = t;
+        if self.vertex_edges[u] == e {
+            self.vertex_edges[u] = t1;
+        }
+        if self.vertex_edges[v] == t {
+            self.vertex_edges[v] = e1;
+        }
+        true
+    }
+
+    /// Puts a new vertex on an edge, splitting the triangles on either side of it in two.
+    pub fn split_edge(&mut self, half_edge: HalfEdgeKey, position: Vec2) -> VertexKey {
+        bodge_assert!(position.is_finite());
+        let e = half_edge.0;
+        let t = self.half_edges[e].twin;
+        let (u, v) = (self.half_edges[e].origin, self.half_edges[t].origin);
+        let m = self.positions.len();
+        self.positions.push(position);
+        self.vertex_edges.push(t);
+
+        // e keeps running from u and now ends at m, while t now starts at m and still ends at u
+        let (to_v, from_v) = self.add_twins(m, v);
+        self.half_edges[t].origin = m;
+        if self.vertex_edges[v] == t {
+            self.vertex_edges[v] = from_v;
+        }
+        match self.half_edges[e].face {
+            Some(f) => {
+                let e1 = self.half_edges[e].next;
+                let e2 = self.half_edges[e1].next;
+                let a = self.half_edges[e2].origin;
+                let (m_to_a, a_to_m) = self.add_twins(m, a);
+                let f2 = self.face_edges.len();
+                self.face_edges.push(to_v);
+                self.link(e, u, m_to_a, f);
+                self.link(m_to_a, m, e2, f);
+                self.link(to_v, m, e1, f2);
+                self.link(e1, v, a_to_m, f2);
+                self.link(a_to_m, a, to_v, f2);
+                self.face_edges[f] = e;
+            }
+            None => {
+                self.half_edges[to_v].next = self.half_edges[e].next;
+                self.half_edges[e].next = to_v;
+                self.vertex_edges[m] = to_v;
+            }
+        }
+        match self.half_edges[t].face {
+            Some(g) => {
+                let t1 = self.half_edges[t].next;
+                let t2 = self.half_edges[t1].next;
+                let b = self.half_edges[t2].origin;
+                let (m_to_b, b_to_m) = self.add_twins(m, b);
+                let g2 = self.face_edges.len();
+                self.face_edges.push(from_v);
+                self.link(t, m, t1, g);
+                self.link(t1, u, b_to_m, g);
+                self.link(b_to_m, b, t, g);
+                self.link(from_v, v, m_to_b, g2);
+                self.link(m_to_b, m, t2, g2);
+                self.link(t2, b, from_v, g2);
+                self.face_edges[g] = t;
+            }
+            None => {
+                let before = self.previous_index(t);
+                self.half_edges[before].next = from_v;
+                self.half_edges[from_v].next = t;
+                self.vertex_edges[v] = from_v;
+                self.vertex_edges[m] = t;
+            }
+        }
+        VertexKey(m)
+    }
+
+    /// Puts a new vertex inside a face, splitting it into three triangles around it.
+    pub fn split_face(&mut self, face: FaceKey, position: Vec2) -> VertexKey {
+        bodge_assert!(position.is_finite());
+        let [e0, e1, e2] = self.face_half_edges(face).map(|half_edge| half_edge.0);
+        let [a, b, c] = [e0, e1, e2].map(|index| self.half_edges[index].origin);
+        let m = self.positions.len();
+        self.positions.push(position);
+        let (b_to_m, m_to_b) = self.add_twins(b, m);
+        let (c_to_m, m_to_c) = self.add_twins(c, m);
+        let (a_to_m, m_to_a) = self.add_twins(a, m);
+        self.vertex_edges.push(m_to_b);
+        let (f1, f2) = (self.face_edges.len(), self.face_edges.len() + 1);
+        self.face_edges.extend([e1, e2]);
+        self.link(e0, a, b_to_m, face.0);
+        self.link(b_to_m, b, m_to_a, face.0);
+        self.link(m_to_a, m, e0, face.0);
+        self.link(e1, b, c_to_m, f1);
+        self.link(c_to_m, c, m_to_b, f1);
+        self.link(m_to_b, m, e1, f1);
+        self.link(e2, c, a_to_m, f2);
+        self.link(a_to_m, a, m_to_c, f2);
+        self.link(m_to_c, m, e2, f2);
+        VertexKey(m)
+    }
+
+    fn destination_index(&self, half_edge: usize) -> usize {
+        self.half_edges[self.half_edges[half_edge].next].origin
+    }
+
+    fn previous_index(&self, half_edge: usize) -> usize {
+        let mut current = half_edge;
+        loop {
+            let next = self.half_edges[current].next;
+            if next == half_edge {
+                return current;
+            }
+            current = next;
+        }
+    }
+
+    fn link(&mut self, half_edge: usize, origin: usize, next: usize, face: usize) {
+        let record = &mut self.half_edges[half_edge];
+        record.origin = origin;
+        record.next = next;
+        record.face = Some(face);
+    }
+
+    // a new pair of twins from `start` to `end` and back, to be linked up by the caller
+    fn add_twins(&mut self, start: usize, end: usize) -> (usize, usize) {
+        let forward = self.half_edges.len();
+        self.half_edges.extend([
+            HalfEdge {
+                origin: start,
+                twin: forward + 1,
+                next: usize::MAX,
+                face: None,
+            },
+            HalfEdge {
+                origin: end,
+                twin: forward,
+                next: usize::MAX,
+                face: None,
+            },
+        ]);
+        (forward, forward + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // checks that twins, nexts and faces agree, and that every face still winds counterclockwise
+    fn assert_consistent(mesh: &HalfEdgeMesh) {
+        for half_edge in mesh.half_edges() {
+            assert_eq!(mesh.twin(mesh.twin(half_edge)), half_edge);
+            assert_eq!(
+                mesh.origin(mesh.twin(half_edge)),
+                mesh.destination(half_edge)
+            );
+            assert_eq!(mesh.previous(mesh.next(half_edge)), half_edge);
+            assert!(mesh.face(half_edge).is_some() || mesh.face(mesh.twin(half_edge)).is_some());
+        }
+        for face in mesh.faces() {
+            for half_edge in mesh.face_half_edges(face) {
+                assert_eq!(mesh.face(half_edge), Some(face));
+            }
+            let [a, b, c] = mesh.triangle(face).vertices;
+            assert!(orient2d(a, b, c) > 0.);
+        }
+        for vertex in mesh.vertices() {
+            for half_edge in mesh.outgoing(vertex) {
+                assert_eq!(mesh.origin(half_edge), vertex);
+            }
+        }
+    }
+
+    fn square() -> HalfEdgeMesh {
+        // the second triangle is clockwise and gets turned round
+        HalfEdgeMesh::new(&[
+            Triangle2::new(Vec2::ZERO, Vec2::new(2., 0.), Vec2::new(2., 2.)),
+            Triangle2::new(Vec2::ZERO, Vec2::new(2., 2.), Vec2::new(0., 2.)),
+        ])
+    }
+
+    #[test]
+    fn half_edge_mesh_connectivity() {
+        let mesh = square();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(mesh.face_count(), 2);
+        assert_eq!(mesh.half_edge_count(), 10);
+        assert_eq!(mesh.boundary_half_edges().len(), 4);
+        assert!(mesh
+            .vertices()
+            .all(|vertex| mesh.is_boundary_vertex(vertex)));
+
+        let [corner, diagonal] = [Vec2::ZERO, Vec2::splat(2.)].map(|point| {
+            mesh.vertices()
+                .find(|vertex| mesh.position(*vertex) == point)
+                .unwrap()
+        });
+        let shared = mesh.find_half_edge(corner, diagonal).unwrap();
+        assert!(!mesh.is_boundary_edge(shared));
+        assert_eq!(mesh.vertex_neighbours(corner).len(), 3);
+        assert_eq!(mesh.vertex_faces(corner).len(), 2);
+        assert!(mesh.is_boundary_edge(mesh.outgoing(corner)[0]));
+        assert_eq!(mesh.face_neighbours(FaceKey(0)), vec![FaceKey(1)]);
+
+        let loops = mesh.boundary_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].0.len(), 4);
+        let [a, b, c] = [0, 1, 2].map(|index| loops[0].0[index]);
+        assert!(orient2d(a, b, c) < 0.);
+
+        let mut triangles = mesh.to_triangles();
+        triangles.extend(HalfEdgeMesh::new(&triangles).to_triangles());
+        assert_eq!(triangles.len(), 4);
+        assert_eq!(triangles[0], triangles[2]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "bodge_assert", should_panic)]
+    fn half_edge_mesh_skips_unjoinable_faces() {
+        let positions = [
+            Vec2::ZERO,
+            Vec2::new(2., 0.),
+            Vec2::new(2., 2.),
+            Vec2::new(0., 2.),
+        ];
+        let mesh =
+            HalfEdgeMesh::new_indexed(&positions, &[[0, 1, 2], [0, 2, 3], [2, 0, 1], [0, 0, 3]]);
+        assert_consistent(&mesh);
+        assert_eq!(mesh.face_count(), 2);
+        assert_eq!(mesh.half_edge_count(), 10);
+    }
+
+    #[test]
+    fn half_edge_mesh_joins_signed_zeros() {
+        let mesh = HalfEdgeMesh::new(&[
+            Triangle2::new(Vec2::new(-0., 0.), Vec2::new(0., 2.), Vec2::new(-2., 1.)),
+            Triangle2::new(Vec2::new(0., -0.), Vec2::new(2., 1.), Vec2::new(-0., 2.)),
+        ]);
+        assert_consistent(&mesh);
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(mesh.face_neighbours(FaceKey(0)), vec![FaceKey(1)]);
+    }
+
+    #[test]
+    fn half_edge_mesh_flip_and_split() {
+        let mut mesh = square();
+        let corner = VertexKey(0);
+        let diagonal = mesh.find_half_edge(corner, VertexKey(2)).unwrap();
+        assert!(mesh.flip_edge(diagonal));
+        assert_consistent(&mesh);
+        assert!(mesh.find_half_edge(corner, VertexKey(2)).is_none());
+        assert!(mesh.find_half_edge(VertexKey(1), VertexKey(3)).is_some());
+        assert!(!mesh.flip_edge(mesh.outgoing(corner)[0]));
+
+        // splitting the inside edge makes four triangles, splitting the boundary adds one more
+        let inside = mesh.find_half_edge(VertexKey(1), VertexKey(3)).unwrap();
+        let middle = mesh.split_edge(inside, Vec2::ONE);
+        assert_consistent(&mesh);
+        assert_eq!(mesh.face_count(), 4);
+        assert_eq!(mesh.vertex_neighbours(middle).len(), 4);
+        assert!(!mesh.is_boundary_vertex(middle));
+
+        let outside = mesh.twin(mesh.find_half_edge(corner, VertexKey(1)).unwrap());
+        let bottom = mesh.split_edge(outside, Vec2::new(1., 0.));
+        assert_consistent(&mesh);
+        assert_eq!(mesh.face_count(), 5);
+        assert!(mesh.is_boundary_vertex(bottom));
+        assert_eq!(mesh.vertex_neighbours(bottom).len(), 3);
+        assert_eq!(mesh.boundary_loops()[0].0.len(), 5);
+
+        let face = mesh.vertex_faces(middle)[0];
+        let inner = mesh.split_face(face, mesh.triangle(face).centroid().unwrap());
+        assert_consistent(&mesh);
+        assert_eq!(mesh.face_count(), 7);
+        assert_eq!(mesh.vertex_faces(inner).len(), 3);
+        let area: f32 = mesh
+            .to_triangles()
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = triangle.vertices;
+                (b - a).perp_dot(c - a) * 0.5
+            })
+            .sum();
+        assert!((area - 4.).abs() < 1e-5);
+    }
+}
@@ -1,7 +1,16 @@
 use glam::Vec2;
 
+use crate::collision::POINT_TOLERANCE;
+
+use super::{LineRay2, LineSegment2, Parametric};
+
+/// How far the sine of the angle between two lines may be from zero for them to be parallel.
+pub const PARALLEL_TOLERANCE: f32 = 1e-6;
+
 // ax + by = c
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Line2Unchecked"))]
 pub struct Line2 {
     pub a: f32,
     pub b: f32,
@@ -10,10 +19,36 @@ pub struct Line2 {
 
 macro_rules! line_2_validity_check {
     ($line_2:expr) => {
-        bodge_assert!($line_2.a != 0. || $line_2.b != 0.);
+        line_2_validity_check!($line_2, bodge_assert);
+    };
+    ($line_2:expr, $check:ident) => {
+        $check!($line_2.a != 0. || $line_2.b != 0.);
     };
 }
 
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Line2Unchecked {
+    a: f32,
+    b: f32,
+    c: f32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Line2Unchecked> for Line2 {
+    type Error = &'static str;
+
+    fn try_from(unchecked: Line2Unchecked) -> Result<Line2, &'static str> {
+        let line = Line2 {
+            a: unchecked.a,
+            b: unchecked.b,
+            c: unchecked.c,
+        };
+        line_2_validity_check!(line, bodge_validate);
+        Ok(line)
+    }
+}
+
 impl Line2 {
     pub fn new(a: f32, b: f32, c: f32) -> Line2 {
         let line = Line2 { a, b, c };
@@ -58,25 +93,182 @@ impl Line2 {
         Vec2::new(-self.b, self.a).normalize()
     }
 
-    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+    /// Unit normal, pointing to the [`LineSide::Right`] of [`Line2::axis`].
+    pub fn normal(&self) -> Vec2 {
         line_2_validity_check!(self);
-        // TODO: what happens if they are the same line?
-        let a = self.b;
-        let b = -self.a;
-        let c = a * point.x + b * point.y;
-        self.intersection_point(Line2 { a, b, c }).unwrap()
+        Vec2::new(self.a, self.b).normalize()
     }
 
-    pub fn intersection_point(&self, other: Line2) -> Option<Vec2> {
+    /// The same line with `(a, b)` scaled to unit length.
+    pub fn normalized(&self) -> Line2 {
         line_2_validity_check!(self);
-        let determinant = self.a * other.b - other.a * self.b;
-        if determinant != 0. {
-            Some(Vec2::new(
-                (other.b * self.c - self.b * other.c) / determinant,
-                (self.a * other.c - other.a * self.c) / determinant,
-            ))
+        let length = Vec2::new(self.a, self.b).length();
+        Line2::new(self.a / length, self.b / length, self.c / length)
+    }
+
+    /// Distance from the line, positive on the side [`Line2::normal`] points to.
+    pub fn signed_distance(&self, point: Vec2) -> f32 {
+        let line = self.normalized();
+        line.a * point.x + line.b * point.y - line.c
+    }
+
+    pub fn distance(&self, point: Vec2) -> f32 {
+        self.signed_distance(point).abs()
+    }
+
+    pub fn side_of(&self, point: Vec2) -> LineSide {
+        line_2_validity_check!(self);
+        let side = self.a * point.x + self.b * point.y - self.c;
+        if side > 0. {
+            LineSide::Right
+        } else if side < 0. {
+            LineSide::Left
         } else {
-            None
+            LineSide::On
+        }
+    }
+
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        point - self.normal() * self.signed_distance(point)
+    }
+
+    pub fn project_point(&self, point: Vec2) -> Vec2 {
+        self.closest_point(point)
+    }
+
+    pub fn project_vector(&self, vector: Vec2) -> Vec2 {
+        let axis = self.axis();
+        axis * vector.dot(axis)
+    }
+
+    pub fn reflect_point(&self, point: Vec2) -> Vec2 {
+        point - self.normal() * self.signed_distance(point) * 2.
+    }
+
+    pub fn reflect_vector(&self, vector: Vec2) -> Vec2 {
+        let normal = self.normal();
+        vector - normal * vector.dot(normal) * 2.
+    }
+
+    pub fn is_parallel(&self, other: Line2) -> bool {
+        self.normal().perp_dot(other.normal()).abs() <= PARALLEL_TOLERANCE
+    }
+
+    pub fn is_coincident(&self, other: Line2) -> bool {
+        self.is_parallel(other) && self.distance(other.closest_point(Vec2::ZERO)) <= POINT_TOLERANCE
+    }
+
+    pub fn intersection(&self, other: Line2) -> LineIntersection {
+        Parametric::from(*self).intersection(&Parametric::from(other))
+    }
+
+    pub fn intersection_ray(&self, ray: LineRay2) -> LineIntersection {
+        Parametric::from(*self).intersection(&Parametric::from(ray))
+    }
+
+    pub fn intersection_segment(&self, segment: LineSegment2) -> LineIntersection {
+        Parametric::from(*self).intersection(&Parametric::from(segment))
+    }
+
+    pub fn intersection_point(&self, other: Line2) -> Option<Vec2> {
+        match self.intersection(other) {
+            LineIntersection::Point(point) => Some(point),
+            _ => None,
         }
     }
 }
+
+/// Which side of a [`Line2`] a point lies on, looking along [`Line2::axis`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineSide {
+    Left,
+    Right,
+    On,
+}
+
+/// How two lines, rays or segments meet, in either precision.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineIntersection<V = Vec2> {
+    None,
+    Point(V),
+    /// They overlap along a stretch rather than at a single point.
+    Coincident,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_queries() {
+        let line = Line2::new_from_points(Vec2::new(0., 1.), Vec2::new(2., 1.));
+        assert_eq!(line.axis(), Vec2::X);
+        assert_eq!(line.signed_distance(Vec2::new(5., 4.)), -3.);
+        assert_eq!(line.side_of(Vec2::new(5., 4.)), LineSide::Left);
+        assert_eq!(line.side_of(Vec2::new(5., -4.)), LineSide::Right);
+        assert_eq!(line.side_of(Vec2::new(-5., 1.)), LineSide::On);
+        assert_eq!(line.closest_point(Vec2::new(5., 4.)), Vec2::new(5., 1.));
+        assert_eq!(line.reflect_point(Vec2::new(5., 4.)), Vec2::new(5., -2.));
+        assert_eq!(line.reflect_vector(Vec2::new(1., 1.)), Vec2::new(1., -1.));
+        assert_eq!(line.project_vector(Vec2::new(3., 2.)), Vec2::new(3., 0.));
+        assert_eq!(
+            Line2::new(3., 4., 10.).normalized(),
+            Line2::new(0.6, 0.8, 2.)
+        );
+
+        let same = Line2::new(0., -2., -2.);
+        assert!(line.is_parallel(same));
+        assert!(line.is_coincident(same));
+        assert_eq!(line.intersection(same), LineIntersection::Coincident);
+        let parallel = Line2::new(0., 1., 3.);
+        assert!(line.is_parallel(parallel) && !line.is_coincident(parallel));
+        assert_eq!(line.intersection(parallel), LineIntersection::None);
+        assert_eq!(
+            line.intersection(Line2::new(1., 0., 4.)),
+            LineIntersection::Point(Vec2::new(4., 1.))
+        );
+    }
+
+    #[test]
+    fn segment_and_ray_intersections() {
+        let segment = LineSegment2::new(Vec2::ZERO, Vec2::new(4., 0.));
+        assert_eq!(
+            segment.intersection(LineSegment2::new(Vec2::new(2., -1.), Vec2::new(2., 1.))),
+            LineIntersection::Point(Vec2::new(2., 0.))
+        );
+        assert_eq!(
+            segment.intersection(LineSegment2::new(Vec2::new(2., 1.), Vec2::new(2., 3.))),
+            LineIntersection::None
+        );
+        assert_eq!(
+            segment.intersection(LineSegment2::new(Vec2::new(3., 0.), Vec2::new(6., 0.))),
+            LineIntersection::Coincident
+        );
+        assert_eq!(
+            segment.intersection(LineSegment2::new(Vec2::new(6., 0.), Vec2::new(4., 0.))),
+            LineIntersection::Point(Vec2::new(4., 0.))
+        );
+        assert_eq!(
+            segment.intersection(LineSegment2::new(Vec2::new(5., 0.), Vec2::new(6., 0.))),
+            LineIntersection::None
+        );
+
+        let ray = LineRay2::new(Vec2::new(2., -2.), Vec2::Y);
+        assert_eq!(
+            ray.intersection_segment(segment),
+            LineIntersection::Point(Vec2::new(2., 0.))
+        );
+        assert_eq!(
+            ray.intersection(LineRay2::new(Vec2::new(2., 5.), Vec2::NEG_Y)),
+            LineIntersection::Coincident
+        );
+        assert_eq!(
+            ray.intersection(LineRay2::new(Vec2::new(2., -5.), Vec2::NEG_Y)),
+            LineIntersection::None
+        );
+        assert_eq!(
+            ray.intersection_line(Line2::new(0., 1., -3.)),
+            LineIntersection::None
+        );
+    }
+}
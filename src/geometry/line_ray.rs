@@ -1,8 +1,10 @@
 use glam::Vec2;
 
-use super::{Line2, LineSegment2};
+use super::{Line2, LineIntersection, LineSegment2, Parametric};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "LineRay2Unchecked"))]
 pub struct LineRay2 {
     pub start: Vec2,
     pub axis: Vec2,
@@ -10,10 +12,34 @@ pub struct LineRay2 {
 
 macro_rules! line_ray_2_validity_check {
     ($line_ray_2:expr) => {
-        bodge_assert!($line_ray_2.start.is_finite());
-        bodge_assert!($line_ray_2.axis.is_finite());
-        bodge_assert!($line_ray_2.axis.is_normalized());
+        line_ray_2_validity_check!($line_ray_2, bodge_assert);
     };
+    ($line_ray_2:expr, $check:ident) => {
+        $check!($line_ray_2.start.is_finite());
+        $check!($line_ray_2.axis.is_finite());
+        $check!($line_ray_2.axis.is_normalized());
+    };
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LineRay2Unchecked {
+    start: Vec2,
+    axis: Vec2,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LineRay2Unchecked> for LineRay2 {
+    type Error = &'static str;
+
+    fn try_from(unchecked: LineRay2Unchecked) -> Result<LineRay2, &'static str> {
+        let line_ray = LineRay2 {
+            start: unchecked.start,
+            axis: unchecked.axis,
+        };
+        line_ray_2_validity_check!(line_ray, bodge_validate);
+        Ok(line_ray)
+    }
 }
 
 impl LineRay2 {
@@ -40,4 +66,16 @@ impl LineRay2 {
         line_ray_2_validity_check!(self);
         Line2::new_from_point_axis(self.start, self.axis)
     }
+
+    pub fn intersection(&self, other: LineRay2) -> LineIntersection {
+        Parametric::from(*self).intersection(&Parametric::from(other))
+    }
+
+    pub fn intersection_segment(&self, segment: LineSegment2) -> LineIntersection {
+        Parametric::from(*self).intersection(&Parametric::from(segment))
+    }
+
+    pub fn intersection_line(&self, line: Line2) -> LineIntersection {
+        Parametric::from(*self).intersection(&Parametric::from(line))
+    }
 }
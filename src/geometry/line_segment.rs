@@ -1,9 +1,11 @@
 use glam::Vec2;
 
-use super::Line2;
+use super::{DLineSegment2, Line2, LineIntersection, LineRay2, Parametric};
 
 /// A 2D line segment represented by two distinct vertices.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "LineSegment2Unchecked"))]
 pub struct LineSegment2 {
     pub start: Vec2,
     pub end: Vec2,
@@ -11,9 +13,33 @@ pub struct LineSegment2 {
 
 macro_rules! line_segment_2_validity_check {
     ($line_segment_2:expr) => {
-        bodge_assert!($line_segment_2.start.is_finite());
-        bodge_assert!($line_segment_2.end.is_finite());
+        line_segment_2_validity_check!($line_segment_2, bodge_assert);
     };
+    ($line_segment_2:expr, $check:ident) => {
+        $check!($line_segment_2.start.is_finite());
+        $check!($line_segment_2.end.is_finite());
+    };
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LineSegment2Unchecked {
+    start: Vec2,
+    end: Vec2,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LineSegment2Unchecked> for LineSegment2 {
+    type Error = &'static str;
+
+    fn try_from(unchecked: LineSegment2Unchecked) -> Result<LineSegment2, &'static str> {
+        let line_segment = LineSegment2 {
+            start: unchecked.start,
+            end: unchecked.end,
+        };
+        line_segment_2_validity_check!(line_segment, bodge_validate);
+        Ok(line_segment)
+    }
 }
 
 impl LineSegment2 {
@@ -58,4 +84,36 @@ impl LineSegment2 {
     pub fn line(&self) -> Line2 {
         Line2::new_from_points(self.start, self.end)
     }
+
+    /// Where two segments meet, decided exactly like [`DLineSegment2::intersection`].
+    pub fn intersection(&self, other: LineSegment2) -> LineIntersection {
+        match DLineSegment2::from(*self).intersection(other.into()) {
+            LineIntersection::None => LineIntersection::None,
+            LineIntersection::Point(point) => LineIntersection::Point(point.as_vec2()),
+            LineIntersection::Coincident => LineIntersection::Coincident,
+        }
+    }
+
+    pub fn intersection_ray(&self, ray: LineRay2) -> LineIntersection {
+        Parametric::from(*self).intersection(&Parametric::from(ray))
+    }
+
+    pub fn intersection_line(&self, line: Line2) -> LineIntersection {
+        Parametric::from(*self).intersection(&Parametric::from(line))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_segment_deserialize() {
+        let line_segment = LineSegment2::new(Vec2::new(1., 2.), Vec2::new(3., 4.));
+        assert_eq!(
+            ron::from_str::<LineSegment2>(&ron::to_string(&line_segment).unwrap()).unwrap(),
+            line_segment
+        );
+        assert!(ron::from_str::<LineSegment2>("(start: (0., 0.), end: (inf, 1.))").is_err());
+    }
 }
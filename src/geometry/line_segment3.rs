@@ -0,0 +1,48 @@
+use glam::Vec3;
+
+/// The 3D counterpart of [`LineSegment2`](super::LineSegment2).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineSegment3 {
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+macro_rules! line_segment_3_validity_check {
+    ($line_segment_3:expr) => {
+        bodge_assert!($line_segment_3.start.is_finite());
+        bodge_assert!($line_segment_3.end.is_finite());
+    };
+}
+
+impl LineSegment3 {
+    pub fn new(start: Vec3, end: Vec3) -> LineSegment3 {
+        let line_segment = LineSegment3 { start, end };
+        line_segment_3_validity_check!(line_segment);
+        line_segment
+    }
+
+    pub fn center(&self) -> Vec3 {
+        line_segment_3_validity_check!(self);
+        (self.start + self.end) * 0.5
+    }
+
+    pub fn length(&self) -> f32 {
+        line_segment_3_validity_check!(self);
+        self.start.distance(self.end)
+    }
+
+    pub fn axis(&self) -> Vec3 {
+        line_segment_3_validity_check!(self);
+        (self.end - self.start).normalize_or_zero()
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        bodge_assert!(point.is_finite());
+        let ab = self.end - self.start;
+        if ab == Vec3::ZERO {
+            return self.start;
+        }
+        let t = ((point - self.start).dot(ab) / ab.dot(ab)).clamp(0., 1.);
+        self.start + t * ab
+    }
+}
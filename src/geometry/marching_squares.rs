@@ -0,0 +1,321 @@
+use glam::Vec2;
+
+use super::{Polyline, VertexList2};
+
+/// Settings for extracting the outlines of a sampled scalar field with marching squares.
+///
+/// Samples at or above `iso_level` count as inside. Sample `(x, y)` sits at
+/// `origin + Vec2::new(x, y) * cell_size`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarchingSquares {
+    pub iso_level: f32,
+    pub origin: Vec2,
+    pub cell_size: Vec2,
+    /// Join the segments of neighbouring cells into outlines instead of returning one
+    /// [`Polyline`] per segment.
+    pub merge: bool,
+    /// Douglas-Peucker tolerance applied to merged outlines, zero keeps every point.
+    pub simplify_tolerance: f32,
+}
+
+/// The outlines found by [`MarchingSquares`]. Inside areas lie to the left of every outline, so
+/// closed outlines around them are counterclockwise and holes are clockwise, in the sense of
+/// [`orient2d`](super::orient2d). Open outlines start and end on the border of the grid.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarchingSquaresContours {
+    pub closed: Vec<VertexList2>,
+    pub open: Vec<Polyline>,
+}
+
+impl Default for MarchingSquares {
+    fn default() -> MarchingSquares {
+        MarchingSquares {
+            iso_level: 0.,
+            origin: Vec2::ZERO,
+            cell_size: Vec2::ONE,
+            merge: true,
+            simplify_tolerance: 0.,
+        }
+    }
+}
+
+impl MarchingSquares {
+    pub fn new(iso_level: f32) -> MarchingSquares {
+        MarchingSquares {
+            iso_level,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_origin(&self, origin: Vec2) -> MarchingSquares {
+        MarchingSquares { origin, ..*self }
+    }
+
+    pub fn with_cell_size(&self, cell_size: Vec2) -> MarchingSquares {
+        MarchingSquares { cell_size, ..*self }
+    }
+
+    pub fn with_merge(&self, merge: bool) -> MarchingSquares {
+        MarchingSquares { merge, ..*self }
+    }
+
+    pub fn with_simplify_tolerance(&self, simplify_tolerance: f32) -> MarchingSquares {
+        MarchingSquares {
+            simplify_tolerance,
+            ..*self
+        }
+    }
+
+    /// Outlines of a row major grid of samples, `width` samples per row.
+    pub fn contours(&self, values: &[f32], width: usize) -> MarchingSquaresContours {
+        bodge_assert!(self.iso_level.is_finite());
+        bodge_assert!(self.origin.is_finite());
+        bodge_assert!(self.cell_size.is_finite());
+        bodge_assert!(self.simplify_tolerance >= 0.);
+        bodge_assert!(width > 0 && values.len().is_multiple_of(width));
+        bodge_assert!(values.iter().all(|value| !value.is_nan()));
+        let height = values.len() / width;
+        if width < 2 || height < 2 {
+            return MarchingSquaresContours::default();
+        }
+
+        let grid = Grid {
+            values,
+            width,
+            height,
+            settings: self,
+        };
+        let edge_count = grid.edge_count();
+        let mut positions = vec![Vec2::ZERO; edge_count];
+        let mut next = vec![None; edge_count];
+        let mut has_previous = vec![false; edge_count];
+        let mut segments = vec![];
+        for y in 0..height - 1 {
+            for x in 0..width - 1 {
+                for (start, end) in grid.cell_segments(x, y) {
+                    positions[start] = grid.crossing(start);
+                    positions[end] = grid.crossing(end);
+                    next[start] = Some(end);
+                    has_previous[end] = true;
+                    segments.push((start, end));
+                }
+            }
+        }
+
+        let mut contours = MarchingSquaresContours::default();
+        if !self.merge {
+            contours.open = segments
+                .into_iter()
+                .map(|(start, end)| Polyline(vec![positions[start], positions[end]]))
+                .collect();
+            return contours;
+        }
+
+        let mut visited = vec![false; edge_count];
+        let follow = |start: usize, visited: &mut [bool]| {
+            let mut chain = vec![];
+            let mut current = Some(start);
+            while let Some(edge) = current.filter(|edge| !visited[*edge]) {
+                visited[edge] = true;
+                chain.push(positions[edge]);
+                current = next[edge];
+            }
+            chain
+        };
+        // chains without a start lead in from the border, everything left over loops
+        for (start, _) in segments.iter() {
+            if !has_previous[*start] && !visited[*start] {
+                let polyline = Polyline(follow(*start, &mut visited));
+                contours.open.push(if self.simplify_tolerance > 0. {
+                    polyline.simplified(self.simplify_tolerance)
+                } else {
+                    polyline
+                });
+            }
+        }
+        for (start, _) in segments.iter() {
+            if !visited[*start] {
+                let vertex_list = VertexList2(follow(*start, &mut visited));
+                contours.closed.push(if self.simplify_tolerance > 0. {
+                    vertex_list.simplified(self.simplify_tolerance)
+                } else {
+                    vertex_list
+                });
+            }
+        }
+        contours
+    }
+
+    /// Outlines between `true` and `false` samples, ignoring `iso_level`. Crossings land halfway
+    /// between samples.
+    pub fn contours_bool(&self, values: &[bool], width: usize) -> MarchingSquaresContours {
+        let values: Vec<f32> = values.iter().map(|value| *value as u8 as f32).collect();
+        MarchingSquares {
+            iso_level: 0.5,
+            ..*self
+        }
+        .contours(&values, width)
+    }
+}
+
+// crossings are identified by the grid edge they lie on: horizontal edges first, then vertical
+struct Grid<'a> {
+    values: &'a [f32],
+    width: usize,
+    height: usize,
+    settings: &'a MarchingSquares,
+}
+
+impl Grid<'_> {
+    fn edge_count(&self) -> usize {
+        (self.width - 1) * self.height + self.width * (self.height - 1)
+    }
+
+    fn horizontal(&self, x: usize, y: usize) -> usize {
+        y * (self.width - 1) + x
+    }
+
+    fn vertical(&self, x: usize, y: usize) -> usize {
+        (self.width - 1) * self.height + y * self.width + x
+    }
+
+    fn value(&self, x: usize, y: usize) -> f32 {
+        self.values[y * self.width + x]
+    }
+
+    fn inside(&self, value: f32) -> bool {
+        value >= self.settings.iso_level
+    }
+
+    fn crossing(&self, edge: usize) -> Vec2 {
+        let horizontal_count = (self.width - 1) * self.height;
+        let (start, end) = if edge < horizontal_count {
+            let (x, y) = (edge % (self.width - 1), edge / (self.width - 1));
+            ((x, y), (x + 1, y))
+        } else {
+            let edge = edge - horizontal_count;
+            let (x, y) = (edge % self.width, edge / self.width);
+            ((x, y), (x, y + 1))
+        };
+        let (start_value, end_value) = (self.value(start.0, start.1), self.value(end.0, end.1));
+        let t = ((self.settings.iso_level - start_value) / (end_value - start_value)).clamp(0., 1.);
+        let position = |(x, y): (usize, usize)| Vec2::new(x as f32, y as f32);
+        self.settings.origin + position(start).lerp(position(end), t) * self.settings.cell_size
+    }
+
+    // segments run from where the counterclockwise walk around the cell leaves the inside to
+    // where it enters it again, which keeps the inside on their left
+    fn cell_segments(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+        let edges = [
+            self.horizontal(x, y),
+            self.vertical(x + 1, y),
+            self.horizontal(x, y + 1),
+            self.vertical(x, y),
+        ];
+        let values = corners.map(|(x, y)| self.value(x, y));
+        // (edge, leaves the inside)
+        let crossings: Vec<(usize, bool)> = (0..4)
+            .filter(|index| self.inside(values[*index]) != self.inside(values[(index + 1) % 4]))
+            .map(|index| (edges[index], self.inside(values[index])))
+            .collect();
+        // in a saddle the center decides whether the two inside corners connect
+        let center_inside = self.inside(values.iter().sum::<f32>() * 0.25);
+        let count = crossings.len();
+        (0..count)
+            .filter(|index| crossings[*index].1)
+            .map(|index| {
+                let partner = if center_inside {
+                    (index + 1) % count
+                } else {
+                    (index + count - 1) % count
+                };
+                (crossings[index].0, crossings[partner].0)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disc(width: usize, center: Vec2, radius: f32) -> Vec<f32> {
+        (0..width * width)
+            .map(|index| {
+                let position = Vec2::new((index % width) as f32, (index / width) as f32);
+                radius - position.distance(center)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn marching_squares_closed_and_open() {
+        let marching_squares = MarchingSquares::new(0.);
+        let contours = marching_squares.contours(&disc(20, Vec2::splat(9.5), 6.), 20);
+        assert!(contours.open.is_empty());
+        assert_eq!(contours.closed.len(), 1);
+        assert!(contours.closed[0].signed_area() > 0.);
+        let outline = &contours.closed[0].0;
+        assert!(outline
+            .iter()
+            .all(|point| (point.distance(Vec2::splat(9.5)) - 6.).abs() < 0.1));
+
+        // a disc poking out of the grid is cut open along the border
+        let contours = marching_squares.contours(&disc(20, Vec2::ZERO, 6.), 20);
+        assert!(contours.closed.is_empty());
+        assert_eq!(contours.open.len(), 1);
+        let polyline = &contours.open[0];
+        assert!((polyline.0[0].length() - 6.).abs() < 1e-4);
+        assert!((polyline.length() - std::f32::consts::FRAC_PI_2 * 6.).abs() < 0.1);
+
+        let unmerged = marching_squares
+            .with_merge(false)
+            .contours(&disc(20, Vec2::splat(9.5), 6.), 20);
+        assert_eq!(unmerged.open.len(), outline.len());
+        let simplified = marching_squares
+            .with_simplify_tolerance(0.5)
+            .contours(&disc(20, Vec2::splat(9.5), 6.), 20);
+        assert!(simplified.closed[0].0.len() < outline.len());
+    }
+
+    #[test]
+    fn marching_squares_holes_and_saddles() {
+        #[rustfmt::skip]
+        let ring = [
+            true, true, true, true,
+            true, false, false, true,
+            true, false, false, true,
+            true, true, true, true,
+        ];
+        let contours = MarchingSquares::default()
+            .with_cell_size(Vec2::splat(2.))
+            .contours_bool(&ring, 4);
+        assert_eq!(contours.closed.len(), 1);
+        assert!(contours.closed[0].signed_area() < 0.);
+        assert!(contours.open.is_empty());
+
+        // with a low center the two inside corners stay apart, with a high one they join
+        let saddle = [1., 0., 0., 1.];
+        let matches = |polyline: &Polyline, expected: [Vec2; 2]| {
+            polyline.0.len() == 2
+                && polyline
+                    .0
+                    .iter()
+                    .zip(expected)
+                    .all(|(point, expected)| point.distance(expected) < 1e-6)
+        };
+        let apart = MarchingSquares::new(0.6).contours(&saddle, 2);
+        assert_eq!(apart.open.len(), 2);
+        assert!(apart
+            .open
+            .iter()
+            .any(|polyline| matches(polyline, [Vec2::new(0.4, 0.), Vec2::new(0., 0.4)])));
+        let joined = MarchingSquares::new(0.4).contours(&saddle, 2);
+        assert_eq!(joined.open.len(), 2);
+        assert!(joined
+            .open
+            .iter()
+            .any(|polyline| matches(polyline, [Vec2::new(0.6, 0.), Vec2::new(1., 0.4)])));
+    }
+}
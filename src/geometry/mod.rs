@@ -1,17 +1,56 @@
 mod aabb;
+mod aabb3;
+mod bounds;
 mod circle;
+mod delaunay;
+mod double;
+mod half_edge_mesh;
 mod line;
 mod line_ray;
 mod line_segment;
+mod line_segment3;
+mod marching_squares;
+mod obb;
+mod parametric;
+mod plane;
 mod polyline;
+mod predicates;
+mod raster;
+mod ray3;
+#[cfg(feature = "rand")]
+mod sampling;
+mod skeleton;
+mod smoothing;
+mod sphere;
 mod triangle;
+mod triangle3;
 mod vertex_list;
+mod visibility;
 
 pub use aabb::*;
+pub use aabb3::*;
+pub use bounds::*;
 pub use circle::*;
+pub use delaunay::*;
+pub use double::*;
+pub use half_edge_mesh::*;
 pub use line::*;
 pub use line_ray::*;
 pub use line_segment::*;
+pub use line_segment3::*;
+pub use marching_squares::*;
+pub use obb::*;
+pub(crate) use parametric::*;
+pub use plane::*;
 pub use polyline::*;
+pub use predicates::*;
+pub use raster::*;
+pub use ray3::*;
+#[cfg(feature = "rand")]
+pub use sampling::*;
+pub use skeleton::*;
+pub use sphere::*;
 pub use triangle::*;
+pub use triangle3::*;
 pub use vertex_list::*;
+pub use visibility::*;
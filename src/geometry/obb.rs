@@ -0,0 +1,192 @@
+use glam::Vec2;
+
+use super::{orient2d, Aabb, VertexList2};
+
+/// A box rotated by `rotation` radians counterclockwise around its `position`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ObbUnchecked"))]
+pub struct Obb {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub rotation: f32,
+}
+
+macro_rules! obb_validity_check {
+    ($obb:expr) => {
+        obb_validity_check!($obb, bodge_assert);
+    };
+    ($obb:expr, $check:ident) => {
+        $check!($obb.position.is_finite());
+        $check!($obb.size.is_finite());
+        $check!($obb.size.x >= 0.);
+        $check!($obb.size.y >= 0.);
+        $check!($obb.rotation.is_finite());
+    };
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ObbUnchecked {
+    position: Vec2,
+    size: Vec2,
+    rotation: f32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ObbUnchecked> for Obb {
+    type Error = &'static str;
+
+    fn try_from(unchecked: ObbUnchecked) -> Result<Obb, &'static str> {
+        let obb = Obb {
+            position: unchecked.position,
+            size: unchecked.size,
+            rotation: unchecked.rotation,
+        };
+        obb_validity_check!(obb, bodge_validate);
+        Ok(obb)
+    }
+}
+
+impl From<Aabb> for Obb {
+    fn from(aabb: Aabb) -> Obb {
+        Obb::new(aabb.position, aabb.size, 0.)
+    }
+}
+
+impl Obb {
+    pub fn new(position: Vec2, size: Vec2, rotation: f32) -> Obb {
+        let obb = Obb {
+            position,
+            size,
+            rotation,
+        };
+        obb_validity_check!(obb);
+        obb
+    }
+
+    /// The smallest area box containing every point, found with rotating calipers over their
+    /// convex hull.
+    pub fn new_enclosing(points: &[Vec2]) -> Obb {
+        bodge_assert!(!points.is_empty());
+        let hull = VertexList2::new_convex_hull(points).0;
+        if hull.len() < 3 {
+            let (start, end) = (hull[0], *hull.last().unwrap());
+            return Obb::new(
+                (start + end) * 0.5,
+                Vec2::new(start.distance(end), 0.),
+                (end - start).y.atan2((end - start).x),
+            );
+        }
+
+        let count = hull.len();
+        let next = |index: usize| (index + 1) % count;
+        let along = |index: usize, axis: Vec2| hull[index].dot(axis);
+        let mut best: Option<(f32, Obb)> = None;
+        // the hull is counterclockwise, so the perpendicular of each edge points inwards
+        let (mut right, mut top, mut left) = (0, 0, 0);
+        for edge in 0..count {
+            let axis = (hull[next(edge)] - hull[edge]).normalize();
+            let normal = axis.perp();
+            if edge == 0 {
+                right = (0..count)
+                    .max_by(|a, b| along(*a, axis).total_cmp(&along(*b, axis)))
+                    .unwrap();
+                top = (0..count)
+                    .max_by(|a, b| along(*a, normal).total_cmp(&along(*b, normal)))
+                    .unwrap();
+                left = (0..count)
+                    .min_by(|a, b| along(*a, axis).total_cmp(&along(*b, axis)))
+                    .unwrap();
+            }
+            // each caliper only ever turns forwards as the edges go around
+            while along(next(right), axis) > along(right, axis) {
+                right = next(right);
+            }
+            while along(next(top), normal) > along(top, normal) {
+                top = next(top);
+            }
+            while along(next(left), axis) < along(left, axis) {
+                left = next(left);
+            }
+
+            let (min_x, max_x) = (along(left, axis), along(right, axis));
+            let (min_y, max_y) = (along(edge, normal), along(top, normal));
+            let area = (max_x - min_x) * (max_y - min_y);
+            if best.is_none_or(|(best_area, _)| area < best_area) {
+                let center = axis * (min_x + max_x) * 0.5 + normal * (min_y + max_y) * 0.5;
+                best = Some((
+                    area,
+                    Obb::new(
+                        center,
+                        Vec2::new(max_x - min_x, max_y - min_y),
+                        axis.y.atan2(axis.x),
+                    ),
+                ));
+            }
+        }
+        best.unwrap().1
+    }
+
+    /// The box's local x axis.
+    pub fn axis(&self) -> Vec2 {
+        obb_validity_check!(self);
+        Vec2::from_angle(self.rotation)
+    }
+
+    pub fn area(&self) -> f32 {
+        obb_validity_check!(self);
+        self.size.x * self.size.y
+    }
+
+    /// The corners in the same order as [`Aabb::corners`], rotated with the box.
+    pub fn corners(&self) -> [Vec2; 4] {
+        obb_validity_check!(self);
+        let axis = self.axis();
+        [
+            Vec2::new(0.5, 0.5),
+            Vec2::new(-0.5, 0.5),
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(0.5, -0.5),
+        ]
+        .map(|corner| self.position + axis.rotate(corner * self.size))
+    }
+
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        obb_validity_check!(self);
+        bodge_assert!(point.is_finite());
+        let axis = self.axis();
+        let local = Vec2::new(axis.x, -axis.y).rotate(point - self.position);
+        self.position + axis.rotate(local.clamp(-self.size * 0.5, self.size * 0.5))
+    }
+
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        obb_validity_check!(self);
+        let corners = self.corners();
+        (0..4).all(|index| orient2d(corners[index], corners[(index + 1) % 4], point) > 0.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use super::*;
+
+    #[test]
+    fn obb_enclosing_finds_rotated_rectangle() {
+        let rectangle = Obb::new(Vec2::new(5., -3.), Vec2::new(6., 2.), FRAC_PI_4);
+        let mut points = rectangle.corners().to_vec();
+        points.extend([rectangle.position, rectangle.position + rectangle.axis()]);
+        let enclosing = Obb::new_enclosing(&points);
+        assert!((enclosing.area() - 12.).abs() < 1e-3);
+        assert!(enclosing.position.distance(rectangle.position) < 1e-3);
+        assert!(points
+            .iter()
+            .all(|point| enclosing.closest_point(*point).distance(*point) < 1e-3));
+
+        let segment = Obb::new_enclosing(&[Vec2::ZERO, Vec2::ONE, Vec2::splat(2.)]);
+        assert_eq!(segment.area(), 0.);
+        assert!((segment.size.x - 8f32.sqrt()).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,162 @@
+use glam::{DVec2, Vec2, Vec3};
+
+use crate::collision::POINT_TOLERANCE;
+
+use super::{
+    DLine2, DLineRay2, DLineSegment2, Line2, LineIntersection, LineRay2, LineSegment2,
+    LineSegment3, Ray3, PARALLEL_TOLERANCE,
+};
+
+// a line, ray or segment as `origin + direction * t` with `t` in `min..=max`, in any precision or
+// dimension
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Parametric<V, S> {
+    pub(crate) origin: V,
+    pub(crate) direction: V,
+    pub(crate) min: S,
+    pub(crate) max: S,
+}
+
+macro_rules! impl_parametric {
+    ($vector:ident, $scalar:ident) => {
+        impl Parametric<$vector, $scalar> {
+            pub(crate) fn closest_point(&self, point: $vector) -> $vector {
+                let length_squared = self.direction.length_squared();
+                if length_squared == 0. {
+                    return self.origin;
+                }
+                let t = ((point - self.origin).dot(self.direction) / length_squared)
+                    .clamp(self.min, self.max);
+                self.origin + self.direction * t
+            }
+
+            pub(crate) fn colliding_point(&self, point: $vector) -> bool {
+                let tolerance = POINT_TOLERANCE as $scalar;
+                self.closest_point(point).distance_squared(point) <= tolerance * tolerance
+            }
+
+            pub(crate) fn contains(&self, t: $scalar) -> bool {
+                t >= self.min && t <= self.max
+            }
+        }
+    };
+}
+
+macro_rules! impl_parametric_2 {
+    ($vector:ident, $scalar:ident, $line:ident, $line_ray:ident, $line_segment:ident) => {
+        impl_parametric!($vector, $scalar);
+
+        impl Parametric<$vector, $scalar> {
+            pub(crate) fn intersection(&self, other: &Self) -> LineIntersection<$vector> {
+                if self.direction == $vector::ZERO {
+                    return other.intersection_point(self.origin);
+                }
+                if other.direction == $vector::ZERO {
+                    return self.intersection_point(other.origin);
+                }
+                let offset = other.origin - self.origin;
+                let cross = self.direction.perp_dot(other.direction);
+                let sin = cross / (self.direction.length() * other.direction.length());
+                if sin.abs() > PARALLEL_TOLERANCE as $scalar {
+                    let t = offset.perp_dot(other.direction) / cross;
+                    let s = offset.perp_dot(self.direction) / cross;
+                    return if self.contains(t) && other.contains(s) {
+                        LineIntersection::Point(self.origin + self.direction * t)
+                    } else {
+                        LineIntersection::None
+                    };
+                }
+                if offset.perp_dot(self.direction).abs() / self.direction.length()
+                    > POINT_TOLERANCE as $scalar
+                {
+                    return LineIntersection::None;
+                }
+                // collinear, so overlap the ranges along our direction
+                let length_squared = self.direction.length_squared();
+                let base = offset.dot(self.direction) / length_squared;
+                let scale = other.direction.dot(self.direction) / length_squared;
+                let (other_start, other_end) = (base + other.min * scale, base + other.max * scale);
+                let start = self.min.max(other_start.min(other_end));
+                let end = self.max.min(other_start.max(other_end));
+                if start > end {
+                    LineIntersection::None
+                } else if start == end {
+                    LineIntersection::Point(self.origin + self.direction * start)
+                } else {
+                    LineIntersection::Coincident
+                }
+            }
+
+            pub(crate) fn intersects(&self, other: &Self) -> bool {
+                self.intersection(other) != LineIntersection::None
+            }
+
+            fn intersection_point(&self, point: $vector) -> LineIntersection<$vector> {
+                if self.colliding_point(point) {
+                    LineIntersection::Point(point)
+                } else {
+                    LineIntersection::None
+                }
+            }
+        }
+
+        impl From<$line> for Parametric<$vector, $scalar> {
+            fn from(line: $line) -> Self {
+                Parametric {
+                    origin: line.closest_point($vector::ZERO),
+                    direction: line.axis(),
+                    min: $scalar::NEG_INFINITY,
+                    max: $scalar::INFINITY,
+                }
+            }
+        }
+
+        impl From<$line_ray> for Parametric<$vector, $scalar> {
+            fn from(line_ray: $line_ray) -> Self {
+                Parametric {
+                    origin: line_ray.start,
+                    direction: line_ray.axis,
+                    min: 0.,
+                    max: $scalar::INFINITY,
+                }
+            }
+        }
+
+        impl From<$line_segment> for Parametric<$vector, $scalar> {
+            fn from(line_segment: $line_segment) -> Self {
+                Parametric {
+                    origin: line_segment.start,
+                    direction: line_segment.end - line_segment.start,
+                    min: 0.,
+                    max: 1.,
+                }
+            }
+        }
+    };
+}
+
+impl_parametric_2!(Vec2, f32, Line2, LineRay2, LineSegment2);
+impl_parametric_2!(DVec2, f64, DLine2, DLineRay2, DLineSegment2);
+impl_parametric!(Vec3, f32);
+
+impl From<LineSegment3> for Parametric<Vec3, f32> {
+    fn from(line_segment: LineSegment3) -> Self {
+        Parametric {
+            origin: line_segment.start,
+            direction: line_segment.end - line_segment.start,
+            min: 0.,
+            max: 1.,
+        }
+    }
+}
+
+impl From<Ray3> for Parametric<Vec3, f32> {
+    fn from(ray: Ray3) -> Self {
+        Parametric {
+            origin: ray.start,
+            direction: ray.axis,
+            min: 0.,
+            max: f32::INFINITY,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use glam::Vec3;
+
+use super::Ray3;
+
+// normal.dot(point) = distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub distance: f32,
+}
+
+macro_rules! plane_validity_check {
+    ($plane:expr) => {
+        bodge_assert!($plane.normal.is_normalized());
+        bodge_assert!($plane.distance.is_finite());
+    };
+}
+
+impl Plane {
+    pub fn new(normal: Vec3, distance: f32) -> Plane {
+        let plane = Plane {
+            normal: normal.normalize_or_zero(),
+            distance,
+        };
+        plane_validity_check!(plane);
+        plane
+    }
+
+    pub fn new_from_point_normal(point: Vec3, normal: Vec3) -> Plane {
+        let normal = normal.normalize_or_zero();
+        Plane::new(normal, normal.dot(point))
+    }
+
+    /// The plane through three points, facing the side they appear counterclockwise from.
+    pub fn new_from_points(a: Vec3, b: Vec3, c: Vec3) -> Plane {
+        Plane::new_from_point_normal(a, (b - a).cross(c - a))
+    }
+
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        plane_validity_check!(self);
+        self.normal.dot(point) - self.distance
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        point - self.normal * self.signed_distance(point)
+    }
+
+    /// Distance along the ray at which it crosses this plane.
+    pub fn ray_entry_distance(&self, ray: Ray3) -> Option<f32> {
+        plane_validity_check!(self);
+        let denominator = self.normal.dot(ray.axis);
+        let signed_distance = self.signed_distance(ray.start);
+        if signed_distance == 0. {
+            return Some(0.);
+        }
+        if denominator == 0. {
+            return None;
+        }
+        let t = -signed_distance / denominator;
+        if t >= 0. {
+            Some(t)
+        } else {
+            None
+        }
+    }
+}
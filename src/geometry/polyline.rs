@@ -1,8 +1,9 @@
 use glam::Vec2;
 
-use super::LineSegment2;
+use super::{smoothing, LineSegment2};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Polyline(pub Vec<Vec2>);
 
 impl Polyline {
@@ -46,4 +47,304 @@ impl Polyline {
             closest.1
         }
     }
+
+    pub fn length(&self) -> f32 {
+        self.0
+            .windows(2)
+            .map(|points| points[0].distance(points[1]))
+            .sum()
+    }
+
+    /// The point `distance` along the line from its start, clamped to its ends.
+    pub fn point_at(&self, distance: f32) -> Vec2 {
+        bodge_assert!(!self.0.is_empty());
+        let (index, t) = self.locate(distance);
+        if index + 1 < self.0.len() {
+            self.0[index].lerp(self.0[index + 1], t)
+        } else {
+            self.0[index]
+        }
+    }
+
+    /// The unit direction of the line `distance` along it, or zero if it has no length.
+    pub fn tangent_at(&self, distance: f32) -> Vec2 {
+        bodge_assert!(!self.0.is_empty());
+        let (index, _) = self.locate(distance);
+        let tangent = |index: usize| (self.0[index + 1] - self.0[index]).normalize_or_zero();
+        // step off zero length segments, preferring the direction ahead
+        (index..self.0.len() - 1)
+            .chain((0..index.min(self.0.len() - 1)).rev())
+            .map(tangent)
+            .find(|tangent| *tangent != Vec2::ZERO)
+            .unwrap_or(Vec2::ZERO)
+    }
+
+    /// How far along the line its closest point to `point` lies.
+    pub fn distance_along(&self, point: Vec2) -> f32 {
+        bodge_assert!(!self.0.is_empty());
+        bodge_assert!(point.is_finite());
+        let mut closest = (f32::INFINITY, 0.);
+        let mut travelled = 0.;
+        for line_segment in self.line_segments() {
+            let closest_point = line_segment.closest_point(point);
+            let distance = closest_point.distance(point);
+            if distance < closest.0 {
+                closest = (
+                    distance,
+                    travelled + line_segment.start.distance(closest_point),
+                );
+            }
+            travelled += line_segment.length();
+        }
+        closest.1
+    }
+
+    /// Points spaced `spacing` apart along the line, always keeping both ends.
+    pub fn resampled(&self, spacing: f32) -> Polyline {
+        bodge_assert!(spacing > 0.);
+        if self.0.len() < 2 {
+            return self.clone();
+        }
+        let length = self.length();
+        let count = (length / spacing).floor() as usize;
+        let mut points: Vec<Vec2> = (0..=count)
+            .map(|step| self.point_at(step as f32 * spacing))
+            .collect();
+        if length - count as f32 * spacing > spacing * 1e-3 {
+            points.push(*self.0.last().unwrap());
+        }
+        Polyline(points)
+    }
+
+    /// Splits the line in two at `distance` along it. Both halves share the split point.
+    pub fn split_at(&self, distance: f32) -> (Polyline, Polyline) {
+        bodge_assert!(!self.0.is_empty());
+        let (index, t) = self.locate(distance);
+        let split = self.point_at(distance);
+        let mut before = self.0[..=index].to_vec();
+        if t > 0. {
+            before.push(split);
+        }
+        let mut after = vec![split];
+        after.extend_from_slice(&self.0[(index + 1).min(self.0.len())..]);
+        (Polyline(before), Polyline(after))
+    }
+
+    pub fn reverse(&mut self) {
+        self.0.reverse();
+    }
+
+    pub fn reversed(&self) -> Polyline {
+        let mut polyline = self.clone();
+        polyline.reverse();
+        polyline
+    }
+
+    /// Drops points that lie within `tolerance` of the simplified line, using Douglas-Peucker.
+    /// Both ends are always kept.
+    pub fn simplified(&self, tolerance: f32) -> Polyline {
+        bodge_assert!(tolerance >= 0.);
+        Polyline(douglas_peucker(&self.0, tolerance))
+    }
+
+    /// Rounds the corners off by cutting each segment at a quarter and three quarters of its
+    /// length. The ends are cut back as well unless `pin_endpoints` is set.
+    pub fn chaikin_smoothed(&self, iterations: usize, pin_endpoints: bool) -> Polyline {
+        Polyline(smoothing::chaikin(
+            &self.0,
+            iterations,
+            false,
+            pin_endpoints,
+        ))
+    }
+
+    /// Moves every point `factor` of the way towards the midpoint of its neighbours each iteration.
+    /// Unpinned ends move towards their only neighbour.
+    pub fn laplacian_smoothed(
+        &self,
+        iterations: usize,
+        factor: f32,
+        pin_endpoints: bool,
+    ) -> Polyline {
+        bodge_assert!(factor.is_finite());
+        Polyline(smoothing::laplacian(
+            &self.0,
+            iterations,
+            factor,
+            false,
+            pin_endpoints,
+        ))
+    }
+
+    /// Laplacian smoothing that alternates passes of `lambda` and a negative `mu` to avoid
+    /// shrinking, see [`VertexList2::taubin_smoothed`](super::VertexList2::taubin_smoothed).
+    pub fn taubin_smoothed(
+        &self,
+        iterations: usize,
+        lambda: f32,
+        mu: f32,
+        pin_endpoints: bool,
+    ) -> Polyline {
+        bodge_assert!(lambda.is_finite() && mu.is_finite());
+        Polyline(smoothing::taubin(
+            &self.0,
+            iterations,
+            lambda,
+            mu,
+            false,
+            pin_endpoints,
+        ))
+    }
+
+    // the segment starting at the returned index and how far along it `distance` lands
+    fn locate(&self, distance: f32) -> (usize, f32) {
+        bodge_assert!(distance.is_finite());
+        let mut remaining = distance.max(0.);
+        for index in 0..self.0.len().saturating_sub(1) {
+            let length = self.0[index].distance(self.0[index + 1]);
+            if remaining < length {
+                return (index, remaining / length);
+            }
+            remaining -= length;
+        }
+        (self.0.len() - 1, 0.)
+    }
+}
+
+pub(super) fn douglas_peucker(points: &[Vec2], tolerance: f32) -> Vec<Vec2> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut ranges = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = ranges.pop() {
+        let segment = LineSegment2::new(points[start], points[end]);
+        let farthest = (start + 1..end)
+            .map(|index| {
+                (
+                    index,
+                    segment.closest_point(points[index]).distance(points[index]),
+                )
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((index, distance)) = farthest {
+            if distance > tolerance {
+                keep[index] = true;
+                ranges.push((start, index));
+                ranges.push((index, end));
+            }
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(point, keep)| keep.then_some(*point))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn l_shape() -> Polyline {
+        Polyline(vec![Vec2::ZERO, Vec2::new(3., 0.), Vec2::new(3., 4.)])
+    }
+
+    #[test]
+    fn polyline_arc_length() {
+        let polyline = l_shape();
+        assert_eq!(polyline.length(), 7.);
+        assert_eq!(polyline.point_at(-1.), Vec2::ZERO);
+        assert_eq!(polyline.point_at(2.), Vec2::new(2., 0.));
+        assert_eq!(polyline.point_at(5.), Vec2::new(3., 2.));
+        assert_eq!(polyline.point_at(9.), Vec2::new(3., 4.));
+        assert_eq!(polyline.tangent_at(2.), Vec2::X);
+        assert_eq!(polyline.tangent_at(3.), Vec2::Y);
+        assert_eq!(polyline.tangent_at(7.), Vec2::Y);
+        assert_eq!(polyline.distance_along(Vec2::new(5., 1.)), 4.);
+        assert_eq!(polyline.distance_along(Vec2::new(1., -1.)), 1.);
+        assert_eq!(
+            polyline.reversed().0,
+            vec![Vec2::new(3., 4.), Vec2::new(3., 0.), Vec2::ZERO]
+        );
+    }
+
+    #[test]
+    fn polyline_resample_and_split() {
+        let polyline = l_shape();
+        let resampled = polyline.resampled(2.);
+        assert_eq!(
+            resampled.0,
+            vec![
+                Vec2::ZERO,
+                Vec2::new(2., 0.),
+                Vec2::new(3., 1.),
+                Vec2::new(3., 3.),
+                Vec2::new(3., 4.),
+            ]
+        );
+        assert_eq!(polyline.resampled(3.5).0.len(), 3);
+
+        let (before, after) = polyline.split_at(5.);
+        assert_eq!(
+            before.0,
+            vec![Vec2::ZERO, Vec2::new(3., 0.), Vec2::new(3., 2.)]
+        );
+        assert_eq!(after.0, vec![Vec2::new(3., 2.), Vec2::new(3., 4.)]);
+        let (before, after) = polyline.split_at(3.);
+        assert_eq!(before.0, vec![Vec2::ZERO, Vec2::new(3., 0.)]);
+        assert_eq!(after.0, vec![Vec2::new(3., 0.), Vec2::new(3., 4.)]);
+        assert_eq!(before.length() + after.length(), polyline.length());
+    }
+
+    #[test]
+    fn polyline_simplification() {
+        let noisy = Polyline(
+            (0..=20)
+                .map(|x| Vec2::new(x as f32, if x % 2 == 0 { 0. } else { 0.05 }))
+                .chain([Vec2::new(20., 10.)])
+                .collect(),
+        );
+        assert_eq!(
+            noisy.simplified(0.1).0,
+            vec![Vec2::ZERO, Vec2::new(20., 0.), Vec2::new(20., 10.)]
+        );
+        assert_eq!(noisy.simplified(0.).0.len(), 22);
+    }
+
+    #[test]
+    fn polyline_smoothing() {
+        let zigzag = Polyline(
+            (0..9)
+                .map(|x| Vec2::new(x as f32, (x % 2) as f32))
+                .collect(),
+        );
+
+        let chaikin = zigzag.chaikin_smoothed(2, true);
+        assert_eq!(chaikin.0.len(), 2 + 2 * (2 + 2 * 8 - 1));
+        assert_eq!(chaikin.0[0], Vec2::ZERO);
+        assert_eq!(*chaikin.0.last().unwrap(), Vec2::new(8., 0.));
+        let unpinned = zigzag.chaikin_smoothed(1, false);
+        assert_eq!(unpinned.0.len(), 16);
+        assert_eq!(unpinned.0[0], Vec2::new(0.25, 0.25));
+
+        let laplacian = zigzag.laplacian_smoothed(10, 0.5, true);
+        assert_eq!(laplacian.0[0], Vec2::ZERO);
+        assert_eq!(laplacian.0[8], Vec2::new(8., 0.));
+        assert!(laplacian.0[4].y > 0.25 && laplacian.0[4].y < 0.75);
+        assert!(zigzag.laplacian_smoothed(10, 0.5, false).0[0] != Vec2::ZERO);
+        assert_eq!(zigzag.taubin_smoothed(3, 0.5, -0.53, true).0.len(), 9);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn polyline_deserialize() {
+        let polyline = Polyline(vec![Vec2::ZERO, Vec2::X, Vec2::ONE]);
+        assert_eq!(
+            ron::from_str::<Polyline>(&ron::to_string(&polyline).unwrap()).unwrap(),
+            polyline
+        );
+    }
 }
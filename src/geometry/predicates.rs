@@ -0,0 +1,257 @@
+use glam::{DVec2, Vec2};
+
+// Adaptive precision predicates following Shewchuk, "Adaptive Precision Floating-Point Arithmetic
+// and Fast Robust Geometric Predicates". The determinant is first evaluated in plain f64 and only
+// recomputed exactly, with floating point expansions, when the error bound can't vouch for its sign.
+
+const EPSILON: f64 = f64::EPSILON * 0.5;
+const ORIENT2D_ERROR_BOUND: f64 = (3. + 16. * EPSILON) * EPSILON;
+const INCIRCLE_ERROR_BOUND: f64 = (10. + 96. * EPSILON) * EPSILON;
+
+/// Twice the signed area of the triangle `a`, `b`, `c`.
+///
+/// Positive when the points are counterclockwise, negative when clockwise and exactly zero when
+/// they are collinear.
+pub fn orient2d(a: Vec2, b: Vec2, c: Vec2) -> f64 {
+    dorient2d(a.as_dvec2(), b.as_dvec2(), c.as_dvec2())
+}
+
+/// [`orient2d`] over double precision points.
+pub fn dorient2d(a: DVec2, b: DVec2, c: DVec2) -> f64 {
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let determinant = left - right;
+    let bound = ORIENT2D_ERROR_BOUND * (left.abs() + right.abs());
+    if determinant.abs() > bound {
+        return determinant;
+    }
+
+    let (acx, acy) = (two_diff(a.x, c.x), two_diff(a.y, c.y));
+    let (bcx, bcy) = (two_diff(b.x, c.x), two_diff(b.y, c.y));
+    let left = expansion_product(&acx, &bcy);
+    let right = expansion_product(&acy, &bcx);
+    estimate(&expansion_diff(&left, &right))
+}
+
+/// Positive when `d` lies inside the circle through `a`, `b`, `c`, negative when it lies outside
+/// and exactly zero when the four points are cocircular.
+///
+/// `a`, `b`, `c` must be counterclockwise, otherwise the sign is flipped.
+pub fn incircle(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> f64 {
+    dincircle(a.as_dvec2(), b.as_dvec2(), c.as_dvec2(), d.as_dvec2())
+}
+
+/// [`incircle`] over double precision points.
+pub fn dincircle(a: DVec2, b: DVec2, c: DVec2, d: DVec2) -> f64 {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let (bc_cross, ca_cross, ab_cross) = (
+        bd.x * cd.y - cd.x * bd.y,
+        cd.x * ad.y - ad.x * cd.y,
+        ad.x * bd.y - bd.x * ad.y,
+    );
+    let (a_lift, b_lift, c_lift) = (
+        ad.length_squared(),
+        bd.length_squared(),
+        cd.length_squared(),
+    );
+    let determinant = a_lift * bc_cross + b_lift * ca_cross + c_lift * ab_cross;
+    let permanent = a_lift * ((bd.x * cd.y).abs() + (cd.x * bd.y).abs())
+        + b_lift * ((cd.x * ad.y).abs() + (ad.x * cd.y).abs())
+        + c_lift * ((ad.x * bd.y).abs() + (bd.x * ad.y).abs());
+    if determinant.abs() > INCIRCLE_ERROR_BOUND * permanent {
+        return determinant;
+    }
+
+    let (adx, ady) = (two_diff(a.x, d.x), two_diff(a.y, d.y));
+    let (bdx, bdy) = (two_diff(b.x, d.x), two_diff(b.y, d.y));
+    let (cdx, cdy) = (two_diff(c.x, d.x), two_diff(c.y, d.y));
+    let cross = |px: &[f64], py: &[f64], qx: &[f64], qy: &[f64]| {
+        expansion_diff(&expansion_product(px, qy), &expansion_product(qx, py))
+    };
+    let lift =
+        |x: &[f64], y: &[f64]| expansion_sum(&expansion_product(x, x), &expansion_product(y, y));
+    let a_term = expansion_product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy));
+    let b_term = expansion_product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady));
+    let c_term = expansion_product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy));
+    estimate(&expansion_sum(&expansion_sum(&a_term, &b_term), &c_term))
+}
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+// expansions are stored smallest magnitude first, without zero components
+fn two_diff(a: f64, b: f64) -> Vec<f64> {
+    let (difference, error) = two_sum(a, -b);
+    [error, difference]
+        .into_iter()
+        .filter(|component| *component != 0.)
+        .collect()
+}
+
+fn grow_expansion(expansion: &[f64], value: f64) -> Vec<f64> {
+    let mut grown = Vec::with_capacity(expansion.len() + 1);
+    let mut carry = value;
+    for component in expansion.iter() {
+        let (sum, error) = two_sum(carry, *component);
+        if error != 0. {
+            grown.push(error);
+        }
+        carry = sum;
+    }
+    if carry != 0. {
+        grown.push(carry);
+    }
+    grown
+}
+
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(e.to_vec(), |sum, component| {
+        grow_expansion(&sum, *component)
+    })
+}
+
+fn expansion_diff(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(e.to_vec(), |sum, component| {
+        grow_expansion(&sum, -component)
+    })
+}
+
+fn scale_expansion(expansion: &[f64], scale: f64) -> Vec<f64> {
+    let mut scaled = vec![];
+    for component in expansion.iter() {
+        let (product, error) = two_product(*component, scale);
+        scaled = grow_expansion(&scaled, error);
+        scaled = grow_expansion(&scaled, product);
+    }
+    scaled
+}
+
+fn expansion_product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(vec![], |product, component| {
+        expansion_sum(&product, &scale_expansion(e, *component))
+    })
+}
+
+// the largest component of a nonoverlapping expansion carries its sign
+fn estimate(expansion: &[f64]) -> f64 {
+    expansion.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{collision::Colliding, geometry::LineSegment2};
+
+    // all coordinates used below are multiples of 2^-25, so scaling them gives exact integers
+    fn exact(value: f32) -> i128 {
+        (value as f64 * (1u64 << 25) as f64) as i128
+    }
+
+    fn sign(value: f64) -> i128 {
+        if value == 0. {
+            0
+        } else {
+            value.signum() as i128
+        }
+    }
+
+    fn exact_orient2d(a: Vec2, b: Vec2, c: Vec2) -> i128 {
+        (exact(a.x) - exact(c.x)) * (exact(b.y) - exact(c.y))
+            - (exact(a.y) - exact(c.y)) * (exact(b.x) - exact(c.x))
+    }
+
+    fn exact_incircle(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> i128 {
+        let offset = |p: Vec2| (exact(p.x) - exact(d.x), exact(p.y) - exact(d.y));
+        let ((adx, ady), (bdx, bdy), (cdx, cdy)) = (offset(a), offset(b), offset(c));
+        (adx * adx + ady * ady) * (bdx * cdy - cdx * bdy)
+            + (bdx * bdx + bdy * bdy) * (cdx * ady - adx * cdy)
+            + (cdx * cdx + cdy * cdy) * (adx * bdy - bdx * ady)
+    }
+
+    #[test]
+    fn orient2d_is_exact_near_collinear() {
+        let ulp = f32::EPSILON * 0.5;
+        let (b, c) = (Vec2::splat(12.), Vec2::splat(24.));
+        for i in 0..64 {
+            for j in 0..64 {
+                let a = Vec2::new(0.5 + i as f32 * ulp, 0.5 + j as f32 * ulp);
+                let expected = exact_orient2d(a, b, c).signum();
+                assert_eq!(sign(orient2d(a, b, c)), expected);
+                assert_eq!(sign(orient2d(b, c, a)), expected);
+                assert_eq!(sign(orient2d(c, b, a)), -expected);
+            }
+        }
+        assert!(orient2d(Vec2::ZERO, Vec2::X, Vec2::Y) > 0.);
+        assert_eq!(orient2d(Vec2::ZERO, Vec2::X, Vec2::X * 2.), 0.);
+    }
+
+    #[test]
+    fn incircle_is_exact_near_cocircular() {
+        let ulp = f32::EPSILON * 0.5;
+        let (a, b, c) = (Vec2::X, Vec2::Y, Vec2::NEG_X);
+        for i in 0..32 {
+            for j in 0..32 {
+                let d = Vec2::new(i as f32 * ulp - 16. * ulp, j as f32 * ulp - 1. - 16. * ulp);
+                let expected = exact_incircle(a, b, c, d).signum();
+                assert_eq!(sign(incircle(a, b, c, d)), expected);
+                assert_eq!(sign(incircle(b, a, c, d)), -expected);
+            }
+        }
+        assert_eq!(incircle(a, b, c, Vec2::NEG_Y), 0.);
+        assert!(incircle(a, b, c, Vec2::ZERO) > 0.);
+        assert!(incircle(a, b, c, Vec2::splat(2.)) < 0.);
+    }
+
+    // segments collide when each reaches both sides of, or onto, the other's line, or when they
+    // are collinear with overlapping extents
+    fn exact_colliding(a: LineSegment2, b: LineSegment2) -> bool {
+        let (a_start, a_end) = (
+            exact_orient2d(b.start, b.end, a.start),
+            exact_orient2d(b.start, b.end, a.end),
+        );
+        let (b_start, b_end) = (
+            exact_orient2d(a.start, a.end, b.start),
+            exact_orient2d(a.start, a.end, b.end),
+        );
+        if [a_start, a_end, b_start, b_end] == [0; 4] {
+            return (0..2).all(|axis| {
+                let (a_start, a_end) = (exact(a.start[axis]), exact(a.end[axis]));
+                let (b_start, b_end) = (exact(b.start[axis]), exact(b.end[axis]));
+                a_start.min(a_end) <= b_start.max(b_end) && b_start.min(b_end) <= a_start.max(a_end)
+            });
+        }
+        a_start.signum() * a_end.signum() <= 0 && b_start.signum() * b_end.signum() <= 0
+    }
+
+    #[test]
+    fn segments_colliding_is_exact_near_collinear() {
+        let ulp = f32::EPSILON * 0.5;
+        let segment = LineSegment2::new(Vec2::splat(12.), Vec2::splat(24.));
+        let mut collisions = 0;
+        for i in 0..32 {
+            for j in 0..32 {
+                let start = Vec2::new(0.5 + i as f32 * ulp, 0.5 + j as f32 * ulp);
+                // passes a hair beside the segment, or runs along it when i == j
+                let passing = LineSegment2::new(start, Vec2::splat(36.));
+                let expected = exact_colliding(passing, segment);
+                assert_eq!(passing.colliding(&segment), expected);
+                assert_eq!(segment.colliding(&passing), expected);
+                collisions += expected as usize;
+                // shares an end point with the segment
+                let touching = LineSegment2::new(start, Vec2::splat(12.));
+                assert!(exact_colliding(touching, segment));
+                assert!(touching.colliding(&segment) && segment.colliding(&touching));
+            }
+        }
+        assert_eq!(collisions, 32);
+    }
+}
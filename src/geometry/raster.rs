@@ -0,0 +1,400 @@
+use glam::{IVec2, Vec2};
+
+use super::{Circle, LineSegment2, Triangle2, VertexList2};
+
+/// Which grid cells a shape covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RasterMode {
+    /// Cells whose center lies inside the shape.
+    #[default]
+    Center,
+    /// Cells the shape overlaps at all, not counting ones it only touches. Lines without
+    /// thickness cover the cells they pass through.
+    Conservative,
+}
+
+/// Converts shapes into the grid cells they cover. Cell `(x, y)` spans from
+/// `origin + Vec2::new(x, y) * cell_size` to one `cell_size` further.
+///
+/// Cells come back sorted by row, then column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Raster {
+    pub origin: Vec2,
+    pub cell_size: Vec2,
+    pub mode: RasterMode,
+}
+
+impl Default for Raster {
+    fn default() -> Raster {
+        Raster {
+            origin: Vec2::ZERO,
+            cell_size: Vec2::ONE,
+            mode: RasterMode::default(),
+        }
+    }
+}
+
+impl Raster {
+    pub fn new(cell_size: Vec2) -> Raster {
+        Raster {
+            cell_size,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_origin(&self, origin: Vec2) -> Raster {
+        Raster { origin, ..*self }
+    }
+
+    pub fn with_mode(&self, mode: RasterMode) -> Raster {
+        Raster { mode, ..*self }
+    }
+
+    pub fn cell_at(&self, point: Vec2) -> IVec2 {
+        ((point - self.origin) / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn cell_center(&self, cell: IVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + 0.5) * self.cell_size
+    }
+
+    /// Fills a polygon with a scanline per row of cells. Edges crossing over each other fill by
+    /// the even-odd rule.
+    pub fn vertex_list(&self, vertex_list: &VertexList2) -> Vec<IVec2> {
+        self.check();
+        let vertices = &vertex_list.0;
+        if vertices.is_empty() {
+            return vec![];
+        }
+        let (min, max) = vertices
+            .iter()
+            .fold((vertices[0], vertices[0]), |(min, max), vertex| {
+                (min.min(*vertex), max.max(*vertex))
+            });
+        let edges = vertex_list.edges();
+        let mut cells = vec![];
+        for y in self.cell_at(min).y..=self.cell_at(max).y {
+            let row = self.cell_center(IVec2::new(0, y)).y;
+            // crossings count edges as half open, so a vertex on the row is only seen once
+            let mut crossings: Vec<f32> = edges
+                .iter()
+                .filter(|edge| (edge.start.y <= row) != (edge.end.y <= row))
+                .map(|edge| x_at(edge, row))
+                .collect();
+            crossings.sort_by(|a, b| a.total_cmp(b));
+            for span in crossings.chunks_exact(2) {
+                cells.extend(self.row_span(y, span[0], span[1]));
+            }
+            if self.mode == RasterMode::Conservative {
+                // cells the outline passes through, which the row's center line can miss
+                let bottom = row - self.cell_size.y * 0.5;
+                let top = bottom + self.cell_size.y;
+                for edge in edges.iter() {
+                    let (low, high) = (edge.start.y.min(edge.end.y), edge.start.y.max(edge.end.y));
+                    if high <= bottom || low >= top {
+                        continue;
+                    }
+                    let (start, end) = if low == high {
+                        (edge.start.x, edge.end.x)
+                    } else {
+                        (x_at(edge, low.max(bottom)), x_at(edge, high.min(top)))
+                    };
+                    cells.extend(self.row_span(y, start.min(end), start.max(end)));
+                }
+            }
+        }
+        sorted(cells)
+    }
+
+    pub fn triangle(&self, triangle: &Triangle2) -> Vec<IVec2> {
+        self.vertex_list(&VertexList2(triangle.vertices.to_vec()))
+    }
+
+    pub fn circle(&self, circle: &Circle) -> Vec<IVec2> {
+        self.check();
+        let radius = circle.radius * 0.5;
+        let (min, max) = (
+            self.cell_at(circle.center - radius),
+            self.cell_at(circle.center + radius),
+        );
+        let mut cells = vec![];
+        for y in min.y..=max.y {
+            let offset = match self.mode {
+                RasterMode::Center => self.cell_center(IVec2::new(0, y)).y - circle.center.y,
+                RasterMode::Conservative => {
+                    // the closest the row gets to the center
+                    let bottom = self.origin.y + y as f32 * self.cell_size.y;
+                    (circle.center.y.clamp(bottom, bottom + self.cell_size.y)) - circle.center.y
+                }
+            };
+            if offset.abs() > radius
+                || (self.mode == RasterMode::Conservative && offset.abs() == radius)
+            {
+                continue;
+            }
+            let half_width = (radius * radius - offset * offset).sqrt();
+            cells.extend(self.row_span(
+                y,
+                circle.center.x - half_width,
+                circle.center.x + half_width,
+            ));
+        }
+        sorted(cells)
+    }
+
+    /// Cells within `thickness / 2` of a segment, which makes a capsule with round ends. A
+    /// thickness of zero gives the cells the segment passes through.
+    pub fn line_segment(&self, line_segment: &LineSegment2, thickness: f32) -> Vec<IVec2> {
+        self.check();
+        bodge_assert!(thickness.is_finite() && thickness >= 0.);
+        let half = thickness * 0.5;
+        let mut cells = vec![];
+        if self.mode == RasterMode::Conservative && half == 0. {
+            return sorted(self.traverse(line_segment.start, line_segment.end));
+        }
+        let (min, max) = (
+            self.cell_at(line_segment.start.min(line_segment.end) - half),
+            self.cell_at(line_segment.start.max(line_segment.end) + half),
+        );
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let cell = IVec2::new(x, y);
+                let center = self.cell_center(cell);
+                let covered = match self.mode {
+                    RasterMode::Center => {
+                        line_segment.closest_point(center).distance(center) <= half
+                    }
+                    RasterMode::Conservative => self.cell_distance(cell, line_segment) < half,
+                };
+                if covered {
+                    cells.push(cell);
+                }
+            }
+        }
+        sorted(cells)
+    }
+
+    /// Every cell a segment passes through in order from `start`, found with a DDA walk. Passing
+    /// exactly through a corner also visits both cells beside it.
+    pub fn traverse(&self, start: Vec2, end: Vec2) -> Vec<IVec2> {
+        self.check();
+        bodge_assert!(start.is_finite() && end.is_finite());
+        let (mut cell, last) = (self.cell_at(start), self.cell_at(end));
+        let mut cells = vec![cell];
+        let delta = (end - start) / self.cell_size;
+        let step = IVec2::new(delta.x.signum() as i32, delta.y.signum() as i32);
+        // how far along the segment the next vertical and horizontal cell borders are
+        let local = (start - self.origin) / self.cell_size;
+        let border = |axis: usize| {
+            if delta[axis] == 0. {
+                return (f32::INFINITY, f32::INFINITY);
+            }
+            // starting on a border going down crosses it straight away
+            let next = if delta[axis] > 0. {
+                local[axis].floor() + 1.
+            } else {
+                local[axis].floor()
+            };
+            ((next - local[axis]) / delta[axis], 1. / delta[axis].abs())
+        };
+        let ((mut next_x, step_x), (mut next_y, step_y)) = (border(0), border(1));
+        while cell != last && (next_x <= 1. || next_y <= 1.) {
+            if next_x == next_y {
+                cells.push(cell + IVec2::new(step.x, 0));
+                cells.push(cell + IVec2::new(0, step.y));
+                cell += step;
+                next_x += step_x;
+                next_y += step_y;
+            } else if next_x < next_y {
+                cell.x += step.x;
+                next_x += step_x;
+            } else {
+                cell.y += step.y;
+                next_y += step_y;
+            }
+            cells.push(cell);
+        }
+        cells
+    }
+
+    fn row_span(&self, y: i32, start: f32, end: f32) -> impl Iterator<Item = IVec2> {
+        let (first, last) = match self.mode {
+            // centers within the span
+            RasterMode::Center => (
+                ((start - self.origin.x) / self.cell_size.x - 0.5).ceil() as i32,
+                ((end - self.origin.x) / self.cell_size.x - 0.5).floor() as i32,
+            ),
+            // cells whose inside the span overlaps
+            RasterMode::Conservative => (
+                ((start - self.origin.x) / self.cell_size.x).floor() as i32,
+                ((end - self.origin.x) / self.cell_size.x).ceil() as i32 - 1,
+            ),
+        };
+        (first..=last).map(move |x| IVec2::new(x, y))
+    }
+
+    fn cell_distance(&self, cell: IVec2, line_segment: &LineSegment2) -> f32 {
+        let min = self.origin + cell.as_vec2() * self.cell_size;
+        let max = min + self.cell_size;
+        let inside = |point: Vec2| point.cmpge(min).all() && point.cmple(max).all();
+        if inside(line_segment.start) || inside(line_segment.end) {
+            return 0.;
+        }
+        let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+        let to_box = [line_segment.start, line_segment.end]
+            .into_iter()
+            .map(|point| point.clamp(min, max).distance(point));
+        let to_segment = corners
+            .iter()
+            .map(|corner| line_segment.closest_point(*corner).distance(*corner));
+        let crossing = (0..4).any(|index| {
+            let edge = LineSegment2::new(corners[index], corners[(index + 1) % 4]);
+            crate::collision::Colliding::colliding(&edge, line_segment)
+        });
+        if crossing {
+            0.
+        } else {
+            to_box.chain(to_segment).fold(f32::INFINITY, f32::min)
+        }
+    }
+
+    fn check(&self) {
+        bodge_assert!(self.origin.is_finite());
+        bodge_assert!(self.cell_size.is_finite() && self.cell_size.cmpgt(Vec2::ZERO).all());
+    }
+}
+
+/// The cells of an 8-connected line between two cells, both included, with Bresenham's
+/// algorithm.
+pub fn bresenham(start: IVec2, end: IVec2) -> Vec<IVec2> {
+    let delta = (end - start).abs();
+    let step = (end - start).signum();
+    let mut error = delta.x - delta.y;
+    let mut cell = start;
+    let mut cells = vec![cell];
+    while cell != end {
+        let doubled = error * 2;
+        if doubled > -delta.y {
+            error -= delta.y;
+            cell.x += step.x;
+        }
+        if doubled < delta.x {
+            error += delta.x;
+            cell.y += step.y;
+        }
+        cells.push(cell);
+    }
+    cells
+}
+
+fn x_at(edge: &LineSegment2, y: f32) -> f32 {
+    let t = (y - edge.start.y) / (edge.end.y - edge.start.y);
+    edge.start.x + (edge.end.x - edge.start.x) * t
+}
+
+fn sorted(mut cells: Vec<IVec2>) -> Vec<IVec2> {
+    cells.sort_by_key(|cell| (cell.y, cell.x));
+    cells.dedup();
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{collision::Colliding, geometry::Aabb};
+
+    use super::*;
+
+    // every cell whose slightly shrunk square collides with the shape
+    fn overlapped<S: Colliding<Aabb>>(raster: &Raster, shape: &S, range: i32) -> Vec<IVec2> {
+        let mut cells = vec![];
+        for y in -range..range {
+            for x in -range..range {
+                let cell = IVec2::new(x, y);
+                let aabb = Aabb::new(raster.cell_center(cell), raster.cell_size - 1e-3);
+                if shape.colliding(&aabb) {
+                    cells.push(cell);
+                }
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn raster_fills_shapes() {
+        let raster = Raster::new(Vec2::ONE);
+        let conservative = raster.with_mode(RasterMode::Conservative);
+        let triangle = Triangle2::new(Vec2::ZERO, Vec2::new(4., 0.), Vec2::new(0., 4.));
+        let cells = raster.triangle(&triangle);
+        assert_eq!(cells.len(), 4 + 3 + 2 + 1);
+        assert!(cells
+            .iter()
+            .all(|cell| triangle.contains_point(raster.cell_center(*cell))));
+        assert_eq!(
+            conservative.triangle(&triangle),
+            overlapped(&raster, &triangle, 8)
+        );
+
+        let l_shape = VertexList2(vec![
+            Vec2::new(-2.3, -1.7),
+            Vec2::new(3.6, -1.2),
+            Vec2::new(3.1, 0.4),
+            Vec2::new(0.2, 0.9),
+            Vec2::new(0.5, 4.2),
+            Vec2::new(-1.8, 3.3),
+        ]);
+        let cells = raster.vertex_list(&l_shape);
+        assert!(cells
+            .iter()
+            .all(|cell| l_shape.contains_point(raster.cell_center(*cell))));
+        assert_eq!(
+            conservative.vertex_list(&l_shape),
+            overlapped(&raster, &l_shape, 8)
+        );
+
+        let circle = Circle::new(Vec2::ZERO, 4.);
+        assert_eq!(raster.circle(&circle).len(), 12);
+        assert_eq!(conservative.circle(&circle).len(), 16);
+        let circle = Circle::new(Vec2::new(0.3, -0.6), 5.);
+        assert!(raster
+            .circle(&circle)
+            .iter()
+            .all(|cell| circle.contains_point(raster.cell_center(*cell))));
+        assert_eq!(
+            conservative.circle(&circle),
+            overlapped(&raster, &circle, 8)
+        );
+
+        let segment = LineSegment2::new(Vec2::new(0.5, 0.5), Vec2::new(5.5, 0.5));
+        assert_eq!(raster.line_segment(&segment, 0.).len(), 6);
+        assert_eq!(raster.line_segment(&segment, 2.).len(), 6 * 3 + 2);
+        assert_eq!(conservative.line_segment(&segment, 0.).len(), 6);
+        assert_eq!(conservative.line_segment(&segment, 1.4).len(), 8 + 6 * 2);
+    }
+
+    #[test]
+    fn raster_line_traversal() {
+        assert_eq!(
+            bresenham(IVec2::ZERO, IVec2::new(4, 2)),
+            vec![
+                IVec2::new(0, 0),
+                IVec2::new(1, 0),
+                IVec2::new(2, 1),
+                IVec2::new(3, 1),
+                IVec2::new(4, 2),
+            ]
+        );
+        assert_eq!(bresenham(IVec2::ONE, IVec2::ONE), vec![IVec2::ONE]);
+
+        let raster = Raster::new(Vec2::splat(2.));
+        let cells = raster.traverse(Vec2::new(0.5, 1.), Vec2::new(7.5, 3.5));
+        assert_eq!(cells[0], IVec2::ZERO);
+        assert_eq!(*cells.last().unwrap(), IVec2::new(3, 1));
+        assert!(cells
+            .windows(2)
+            .all(|pair| (pair[1] - pair[0]).abs().dot(IVec2::ONE) == 1));
+        let backwards = raster.traverse(Vec2::new(7.5, 3.5), Vec2::new(0.5, 1.));
+        assert_eq!(sorted(backwards), sorted(cells));
+        // through the corner between four cells
+        assert_eq!(raster.traverse(Vec2::ONE, Vec2::splat(3.)).len(), 4);
+    }
+}
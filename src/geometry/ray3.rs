@@ -0,0 +1,44 @@
+use glam::Vec3;
+
+use super::LineSegment3;
+
+/// The 3D counterpart of [`LineRay2`](super::LineRay2).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray3 {
+    pub start: Vec3,
+    pub axis: Vec3,
+}
+
+macro_rules! ray_3_validity_check {
+    ($ray_3:expr) => {
+        bodge_assert!($ray_3.start.is_finite());
+        bodge_assert!($ray_3.axis.is_finite());
+        bodge_assert!($ray_3.axis.is_normalized());
+    };
+}
+
+impl Ray3 {
+    pub fn new(start: Vec3, axis: Vec3) -> Ray3 {
+        let ray = Ray3 {
+            start,
+            axis: axis.normalize_or_zero(),
+        };
+        ray_3_validity_check!(ray);
+        ray
+    }
+
+    pub fn point_at(&self, distance: f32) -> Vec3 {
+        ray_3_validity_check!(self);
+        self.start + self.axis * distance
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        ray_3_validity_check!(self);
+        self.start + (point - self.start).dot(self.axis).max(0.) * self.axis
+    }
+
+    pub fn segment(&self, length: f32) -> LineSegment3 {
+        ray_3_validity_check!(self);
+        LineSegment3::new(self.start, self.point_at(length))
+    }
+}
@@ -0,0 +1,241 @@
+use std::f32::consts::{SQRT_2, TAU};
+
+use glam::{UVec2, Vec2};
+use rand::Rng;
+
+use super::{Aabb, Bounded, Circle, LineSegment2, Triangle2, VertexList2};
+
+// candidates tried around each point before it stops spawning new ones, as suggested by Bridson
+const POISSON_DISK_ATTEMPTS: usize = 30;
+
+/// Random points inside or on the boundary of a shape, drawn from a caller supplied generator.
+pub trait RandomPoints: Bounded {
+    /// A uniformly distributed point inside the shape.
+    fn random_point_inside<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2;
+
+    /// A uniformly distributed point along the outline of the shape.
+    fn random_point_on_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2;
+
+    /// Whether a sampled point counts as inside the shape, boundary included.
+    fn contains_sample(&self, point: Vec2) -> bool;
+
+    /// Blue noise points inside the shape, no two closer than `distance`, using Bridson's Poisson
+    /// disk sampling.
+    fn poisson_disk_points<R: Rng + ?Sized>(&self, distance: f32, rng: &mut R) -> Vec<Vec2> {
+        bodge_assert!(distance.is_finite() && distance > 0.);
+        let aabb = self.aabb();
+        // each cell is small enough to hold at most one point
+        let cell_size = distance / SQRT_2;
+        let cells = (aabb.size / cell_size).ceil().as_uvec2() + 1;
+        let cell = |point: Vec2| ((point - aabb.min()) / cell_size).as_uvec2().min(cells - 1);
+        let mut grid: Vec<Option<usize>> = vec![None; (cells.x * cells.y) as usize];
+
+        let mut points = vec![self.random_point_inside(rng)];
+        let first = cell(points[0]);
+        grid[(first.y * cells.x + first.x) as usize] = Some(0);
+        let mut active = vec![0];
+        while !active.is_empty() {
+            let active_index = rng.gen_range(0..active.len());
+            let center = points[active[active_index]];
+            let candidate = (0..POISSON_DISK_ATTEMPTS).find_map(|_| {
+                let offset = Vec2::from_angle(rng.gen_range(0. ..TAU));
+                let candidate = center + offset * distance * rng.gen_range(1f32..2.).sqrt();
+                if !self.contains_sample(candidate) {
+                    return None;
+                }
+                let candidate_cell = cell(candidate);
+                let min = UVec2::new(
+                    candidate_cell.x.saturating_sub(2),
+                    candidate_cell.y.saturating_sub(2),
+                );
+                let max = (candidate_cell + 2).min(cells - 1);
+                let crowded = (min.y..=max.y).any(|y| {
+                    (min.x..=max.x).any(|x| {
+                        grid[(y * cells.x + x) as usize]
+                            .is_some_and(|other| points[other].distance(candidate) < distance)
+                    })
+                });
+                (!crowded).then_some((candidate, candidate_cell))
+            });
+            if let Some((candidate, candidate_cell)) = candidate {
+                grid[(candidate_cell.y * cells.x + candidate_cell.x) as usize] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+            } else {
+                active.swap_remove(active_index);
+            }
+        }
+        points
+    }
+}
+
+// a uniformly distributed point along a chain of segments
+fn random_point_on_segments<R: Rng + ?Sized>(segments: &[LineSegment2], rng: &mut R) -> Vec2 {
+    let total: f32 = segments.iter().map(|segment| segment.length()).sum();
+    let mut remaining = rng.gen_range(0. ..=total);
+    for segment in segments.iter() {
+        let length = segment.length();
+        if remaining <= length && length > 0. {
+            return segment.start.lerp(segment.end, remaining / length);
+        }
+        remaining -= length;
+    }
+    segments.last().unwrap().end
+}
+
+impl RandomPoints for Aabb {
+    fn random_point_inside<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        self.min() + self.size * Vec2::new(rng.gen(), rng.gen())
+    }
+
+    fn random_point_on_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let corners = self.corners();
+        let edges: Vec<LineSegment2> = (0..4)
+            .map(|index| LineSegment2::new(corners[index], corners[(index + 1) % 4]))
+            .collect();
+        random_point_on_segments(&edges, rng)
+    }
+
+    fn contains_sample(&self, point: Vec2) -> bool {
+        self.closest_point(point) == point
+    }
+}
+
+impl RandomPoints for Circle {
+    fn random_point_inside<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        // the square root spreads points evenly over the area rather than the radius
+        let distance = self.radius * 0.5 * rng.gen::<f32>().sqrt();
+        self.center + Vec2::from_angle(rng.gen_range(0. ..TAU)) * distance
+    }
+
+    fn random_point_on_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        self.center + Vec2::from_angle(rng.gen_range(0. ..TAU)) * self.radius * 0.5
+    }
+
+    fn contains_sample(&self, point: Vec2) -> bool {
+        self.contains_point(point)
+    }
+}
+
+impl RandomPoints for Triangle2 {
+    fn random_point_inside<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let (mut u, mut v): (f32, f32) = (rng.gen(), rng.gen());
+        // fold the far half of the parallelogram back onto the triangle
+        if u + v > 1. {
+            (u, v) = (1. - u, 1. - v);
+        }
+        self.a() + (self.b() - self.a()) * u + (self.c() - self.a()) * v
+    }
+
+    fn random_point_on_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        random_point_on_segments(&self.edges(), rng)
+    }
+
+    fn contains_sample(&self, point: Vec2) -> bool {
+        self.contains_point(point)
+    }
+}
+
+impl RandomPoints for VertexList2 {
+    fn random_point_inside<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        let triangles = self.triangulate();
+        bodge_assert!(!triangles.is_empty());
+        let area = |triangle: &Triangle2| {
+            (triangle.b() - triangle.a())
+                .perp_dot(triangle.c() - triangle.a())
+                .abs()
+        };
+        let mut remaining = rng.gen_range(0. ..=triangles.iter().map(area).sum::<f32>());
+        for triangle in triangles.iter() {
+            if remaining <= area(triangle) {
+                return triangle.random_point_inside(rng);
+            }
+            remaining -= area(triangle);
+        }
+        triangles.last().unwrap().random_point_inside(rng)
+    }
+
+    fn random_point_on_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        bodge_assert!(!self.0.is_empty());
+        random_point_on_segments(&self.edges(), rng)
+    }
+
+    fn contains_sample(&self, point: Vec2) -> bool {
+        self.contains_point(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn check_poisson_disk<S: RandomPoints>(shape: &S, distance: f32, minimum_count: usize) {
+        let mut rng = StdRng::seed_from_u64(7);
+        let points = shape.poisson_disk_points(distance, &mut rng);
+        assert!(
+            points.len() >= minimum_count,
+            "only {} points",
+            points.len()
+        );
+        assert!(points.iter().all(|point| shape.contains_sample(*point)));
+        for (index, point) in points.iter().enumerate() {
+            assert!(points[index + 1..]
+                .iter()
+                .all(|other| other.distance(*point) >= distance));
+        }
+    }
+
+    #[test]
+    fn poisson_disk_points_are_spread_out() {
+        // a maximal packing covers roughly half of what the distance allows
+        check_poisson_disk(&Aabb::new(Vec2::ZERO, Vec2::new(20., 10.)), 1., 100);
+        check_poisson_disk(&Circle::new(Vec2::ONE, 20.), 1., 150);
+        check_poisson_disk(
+            &Triangle2::new(Vec2::ZERO, Vec2::new(20., 0.), Vec2::new(0., 20.)),
+            1.,
+            100,
+        );
+        let l_shape = VertexList2(vec![
+            Vec2::ZERO,
+            Vec2::new(20., 0.),
+            Vec2::new(20., 5.),
+            Vec2::new(5., 5.),
+            Vec2::new(5., 20.),
+            Vec2::new(0., 20.),
+        ]);
+        check_poisson_disk(&l_shape, 1., 80);
+    }
+
+    #[test]
+    fn random_points_land_inside_and_on_boundary() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let triangle = Triangle2::new(Vec2::ZERO, Vec2::new(4., 0.), Vec2::new(0., 4.));
+        let circle = Circle::new(Vec2::new(-3., 2.), 4.);
+        let aabb = Aabb::new(Vec2::new(5., 5.), Vec2::new(2., 6.));
+        let mut left_half = 0;
+        for _ in 0..1000 {
+            let point = triangle.random_point_inside(&mut rng);
+            assert!(triangle.contains_sample(point));
+            let point = triangle.random_point_on_boundary(&mut rng);
+            assert!(triangle
+                .edges()
+                .iter()
+                .any(|edge| edge.closest_point(point).distance(point) < 1e-5));
+
+            let point = circle.random_point_inside(&mut rng);
+            assert!(circle.contains_sample(point));
+            if point.x < circle.center.x {
+                left_half += 1;
+            }
+            let point = circle.random_point_on_boundary(&mut rng);
+            assert!((point.distance(circle.center) - 2.).abs() < 1e-5);
+
+            let point = aabb.random_point_on_boundary(&mut rng);
+            let offset = (point - aabb.position).abs();
+            assert!((offset.x - 1.).abs() < 1e-5 || (offset.y - 3.).abs() < 1e-5);
+        }
+        assert!((400..600).contains(&left_half));
+    }
+}
@@ -0,0 +1,470 @@
+use glam::Vec2;
+
+use super::{delaunay, LineSegment2, VertexList2};
+
+// nodes closer than this are merged, since simultaneous events land on the same spot
+const MERGE_DISTANCE: f32 = 1e-4;
+// events this far before the current time still count, to absorb rounding
+const TIME_TOLERANCE: f32 = 1e-5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkeletonVertex {
+    pub position: Vec2,
+    /// How far the vertex lies inside the polygon, zero for its own corners.
+    pub distance: f32,
+}
+
+/// A skeleton of a polygon as a graph of straight edges between vertices.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SkeletonGraph {
+    pub vertices: Vec<SkeletonVertex>,
+    /// Pairs of indices into `vertices`.
+    pub edges: Vec<[usize; 2]>,
+}
+
+impl SkeletonGraph {
+    pub fn line_segments(&self) -> Vec<LineSegment2> {
+        self.edges
+            .iter()
+            .map(|[start, end]| {
+                LineSegment2::new(self.vertices[*start].position, self.vertices[*end].position)
+            })
+            .collect()
+    }
+
+    pub fn neighbours(&self, vertex: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter_map(|[start, end]| match vertex {
+                _ if *start == vertex => Some(*end),
+                _ if *end == vertex => Some(*start),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn add_vertex(&mut self, position: Vec2, distance: f32) -> usize {
+        if let Some(index) = self.vertices.iter().position(|vertex| {
+            vertex.distance > 0. && vertex.position.distance(position) < MERGE_DISTANCE
+        }) {
+            return index;
+        }
+        self.vertices.push(SkeletonVertex { position, distance });
+        self.vertices.len() - 1
+    }
+
+    fn add_edge(&mut self, start: usize, end: usize) {
+        if start != end
+            && !self.edges.contains(&[start, end])
+            && !self.edges.contains(&[end, start])
+        {
+            self.edges.push([start, end]);
+        }
+    }
+}
+
+// a corner of the shrinking polygon, which sits where the offset lines of two input edges meet
+#[derive(Debug, Clone, Copy)]
+struct Wavefront {
+    // where the corner would have been at time zero
+    base: Vec2,
+    velocity: Vec2,
+    node: usize,
+    previous_edge: usize,
+    next_edge: usize,
+}
+
+impl Wavefront {
+    fn new(lines: &[Line], position: Vec2, time: f32, node: usize, edges: [usize; 2]) -> Self {
+        let (previous, next) = (lines[edges[0]].normal, lines[edges[1]].normal);
+        let determinant = previous.perp_dot(next);
+        // moves one unit away from both edges per unit of time
+        let velocity = if determinant.abs() > 1e-6 {
+            Vec2::new(next.y - previous.y, previous.x - next.x) / determinant
+        } else if previous.dot(next) > 0. {
+            previous
+        } else {
+            // opposite edges have met, so the polygon is collapsing here
+            Vec2::ZERO
+        };
+        Wavefront {
+            base: position - velocity * time,
+            velocity,
+            node,
+            previous_edge: edges[0],
+            next_edge: edges[1],
+        }
+    }
+
+    fn at(&self, time: f32) -> Vec2 {
+        self.base + self.velocity * time
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Line {
+    start: Vec2,
+    direction: Vec2,
+    // points inside the polygon
+    normal: Vec2,
+}
+
+enum Event {
+    // the wavefront edge after `corner` shrinks to nothing
+    Edge {
+        polygon: usize,
+        corner: usize,
+    },
+    // a reflex corner runs into the wavefront edge after `corner`
+    Split {
+        polygon: usize,
+        reflex: usize,
+        corner: usize,
+    },
+}
+
+// Shrinks the polygon with every edge moving inward at the same speed and records where its
+// corners travel. All pending events are searched again after each one, which is cubic in the
+// number of vertices but keeps the bookkeeping simple.
+pub(super) fn straight_skeleton(vertex_list: &VertexList2) -> SkeletonGraph {
+    bodge_assert!(vertex_list.0.len() >= 3);
+    let mut vertices = vertex_list.0.clone();
+    if vertex_list.signed_area() < 0. {
+        vertices.reverse();
+    }
+    let count = vertices.len();
+    let lines: Vec<Line> = (0..count)
+        .map(|index| {
+            let (start, end) = (vertices[index], vertices[(index + 1) % count]);
+            let direction = (end - start).normalize_or_zero();
+            Line {
+                start,
+                direction,
+                normal: direction.perp(),
+            }
+        })
+        .collect();
+
+    let mut graph = SkeletonGraph::default();
+    let mut polygons = vec![(0..count)
+        .map(|index| {
+            graph.vertices.push(SkeletonVertex {
+                position: vertices[index],
+                distance: 0.,
+            });
+            let edges = [(index + count - 1) % count, index];
+            Wavefront::new(&lines, vertices[index], 0., index, edges)
+        })
+        .collect::<Vec<_>>()];
+
+    let mut time = 0.;
+    // every event removes a corner or splits a polygon, so this only guards against rounding
+    for _ in 0..count * count * 4 + 16 {
+        if polygons.is_empty() {
+            break;
+        }
+        let Some((event_time, event)) = next_event(&lines, &polygons, time) else {
+            // nothing left to meet, which only happens to slivers lost to rounding
+            for polygon in polygons.drain(..) {
+                let center = polygon.iter().map(|corner| corner.at(time)).sum::<Vec2>()
+                    / polygon.len() as f32;
+                let node = graph.add_vertex(center, time);
+                for corner in polygon {
+                    graph.add_edge(corner.node, node);
+                }
+            }
+            break;
+        };
+        time = event_time.max(time);
+        match event {
+            Event::Edge { polygon, corner } => {
+                let corners = &mut polygons[polygon];
+                let next = (corner + 1) % corners.len();
+                let (a, b) = (corners[corner], corners[next]);
+                let node = graph.add_vertex((a.at(time) + b.at(time)) * 0.5, time);
+                if corners.len() == 3 {
+                    for remaining in polygons.swap_remove(polygon) {
+                        graph.add_edge(remaining.node, node);
+                    }
+                    continue;
+                }
+                graph.add_edge(a.node, node);
+                graph.add_edge(b.node, node);
+                let position = graph.vertices[node].position;
+                let edges = [a.previous_edge, b.next_edge];
+                corners[corner] = Wavefront::new(&lines, position, time, node, edges);
+                corners.remove(next);
+            }
+            Event::Split {
+                polygon,
+                reflex,
+                corner,
+            } => {
+                let corners = polygons.swap_remove(polygon);
+                let count = corners.len();
+                let r = corners[reflex];
+                let edge = corners[corner].next_edge;
+                let node = graph.add_vertex(r.at(time), time);
+                graph.add_edge(r.node, node);
+                let position = graph.vertices[node].position;
+                // one side runs from after the reflex corner round to the edge it hit, the
+                // other from the far end of that edge back round to before the reflex corner
+                let mut first = vec![Wavefront::new(
+                    &lines,
+                    position,
+                    time,
+                    node,
+                    [edge, r.next_edge],
+                )];
+                let mut index = (reflex + 1) % count;
+                loop {
+                    first.push(corners[index]);
+                    if index == corner {
+                        break;
+                    }
+                    index = (index + 1) % count;
+                }
+                let mut second = vec![Wavefront::new(
+                    &lines,
+                    position,
+                    time,
+                    node,
+                    [r.previous_edge, edge],
+                )];
+                let mut index = (corner + 1) % count;
+                while index != reflex {
+                    second.push(corners[index]);
+                    index = (index + 1) % count;
+                }
+                for side in [first, second] {
+                    if side.len() >= 3 {
+                        polygons.push(side);
+                    } else if side.len() == 2 {
+                        graph.add_edge(side[0].node, side[1].node);
+                    }
+                }
+            }
+        }
+    }
+    graph
+}
+
+fn next_event(lines: &[Line], polygons: &[Vec<Wavefront>], time: f32) -> Option<(f32, Event)> {
+    let mut best: Option<(f32, Event)> = None;
+    let mut consider = |event_time: f32, event: Event| {
+        if event_time.is_finite()
+            && event_time >= time - TIME_TOLERANCE
+            && best
+                .as_ref()
+                .is_none_or(|(best_time, _)| event_time < *best_time)
+        {
+            best = Some((event_time, event));
+        }
+    };
+    for (polygon, corners) in polygons.iter().enumerate() {
+        let count = corners.len();
+        for corner in 0..count {
+            let (a, b) = (corners[corner], corners[(corner + 1) % count]);
+            let direction = lines[a.next_edge].direction;
+            let closing = (a.velocity - b.velocity).dot(direction);
+            if closing > 1e-9 {
+                consider(
+                    (b.base - a.base).dot(direction) / closing,
+                    Event::Edge { polygon, corner },
+                );
+            }
+        }
+        for reflex in 0..count {
+            let r = corners[reflex];
+            if lines[r.previous_edge]
+                .direction
+                .perp_dot(lines[r.next_edge].direction)
+                >= 0.
+            {
+                continue;
+            }
+            for corner in 0..count {
+                let next = (corner + 1) % count;
+                let line = lines[corners[corner].next_edge];
+                if corner == reflex
+                    || next == reflex
+                    || corners[corner].next_edge == r.previous_edge
+                    || corners[corner].next_edge == r.next_edge
+                {
+                    continue;
+                }
+                let approach = 1. - r.velocity.dot(line.normal);
+                if approach <= 1e-9 {
+                    continue;
+                }
+                let hit_time = (r.base - line.start).dot(line.normal) / approach;
+                // the hit has to land on the edge as it is at that time, not just its line
+                let hit = r.at(hit_time);
+                let (start, end) = (corners[corner].at(hit_time), corners[next].at(hit_time));
+                let along = (hit - start).dot(end - start) / (end - start).length_squared();
+                // hitting an end of the edge means meeting another corner head on, which splits
+                // the same way
+                if (-1e-6..=1. + 1e-6).contains(&along) {
+                    consider(
+                        hit_time,
+                        Event::Split {
+                            polygon,
+                            reflex,
+                            corner,
+                        },
+                    );
+                }
+            }
+        }
+    }
+    best
+}
+
+// The Voronoi vertices of the boundary samples are the circumcenters of their Delaunay triangles
+pub(super) fn medial_axis(vertex_list: &VertexList2, spacing: f32) -> SkeletonGraph {
+    bodge_assert!(vertex_list.0.len() >= 3);
+    bodge_assert!(spacing.is_finite() && spacing > 0.);
+    let edges = vertex_list.edges();
+    let mut samples = vec![];
+    for edge in edges.iter() {
+        let steps = (edge.length() / spacing).ceil().max(1.) as usize;
+        samples
+            .extend((0..steps).map(|step| edge.start.lerp(edge.end, step as f32 / steps as f32)));
+    }
+    let count = samples.len();
+    let triangles = delaunay(&samples);
+
+    let mut graph = SkeletonGraph::default();
+    let nodes: Vec<Option<usize>> = triangles
+        .iter()
+        .map(|triangle| {
+            let [a, b, c] = triangle.map(|index| samples[index]);
+            let center = super::Triangle2::new(a, b, c).circumcenter()?;
+            if !vertex_list.contains_point(center) {
+                return None;
+            }
+            let distance = edges
+                .iter()
+                .map(|edge| edge.closest_point(center).distance(center))
+                .fold(f32::INFINITY, f32::min);
+            Some(graph.add_vertex(center, distance))
+        })
+        .collect();
+
+    // Voronoi edges cross the Delaunay edges shared by two triangles, except those joining
+    // neighbouring samples, which just point out at the boundary
+    let mut shared = std::collections::HashMap::new();
+    for (index, triangle) in triangles.iter().enumerate() {
+        for side in 0..3 {
+            let (start, end) = (triangle[side], triangle[(side + 1) % 3]);
+            if (start + 1) % count == end || (end + 1) % count == start {
+                continue;
+            }
+            if let Some(other) = shared.remove(&(end, start)) {
+                if let (Some(a), Some(b)) = (nodes[index], nodes[other]) {
+                    graph.add_edge(a, b);
+                }
+            } else {
+                shared.insert((start, end), index);
+            }
+        }
+    }
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_tree(graph: &SkeletonGraph, polygon: &VertexList2) {
+        assert_eq!(graph.edges.len(), graph.vertices.len() - 1);
+        let mut reached = vec![false; graph.vertices.len()];
+        let mut stack = vec![0];
+        while let Some(vertex) = stack.pop() {
+            if !std::mem::replace(&mut reached[vertex], true) {
+                stack.extend(graph.neighbours(vertex));
+            }
+        }
+        assert!(reached.iter().all(|reached| *reached));
+        for vertex in graph.vertices.iter().filter(|vertex| vertex.distance > 0.) {
+            assert!(polygon.contains_point(vertex.position));
+            let distance = polygon
+                .edges()
+                .iter()
+                .map(|edge| {
+                    edge.closest_point(vertex.position)
+                        .distance(vertex.position)
+                })
+                .fold(f32::INFINITY, f32::min);
+            assert!((distance - vertex.distance).abs() < 1e-3, "{vertex:?}");
+        }
+    }
+
+    #[test]
+    fn straight_skeleton_of_convex_and_reflex_polygons() {
+        let rectangle = VertexList2(vec![
+            Vec2::ZERO,
+            Vec2::new(4., 0.),
+            Vec2::new(4., 2.),
+            Vec2::new(0., 2.),
+        ]);
+        let graph = rectangle.straight_skeleton();
+        check_tree(&graph, &rectangle);
+        assert_eq!(graph.vertices.len(), 6);
+        let ridge: Vec<Vec2> = graph.vertices[4..]
+            .iter()
+            .map(|vertex| vertex.position)
+            .collect();
+        assert!(ridge.contains(&Vec2::new(1., 1.)) && ridge.contains(&Vec2::new(3., 1.)));
+
+        // clockwise, with a notch that splits the shrinking polygon in two
+        let notched = VertexList2(vec![
+            Vec2::ZERO,
+            Vec2::new(0., 4.),
+            Vec2::new(4.5, 4.),
+            Vec2::new(5., 1.5),
+            Vec2::new(5.5, 4.),
+            Vec2::new(10., 4.),
+            Vec2::new(10., 0.),
+        ]);
+        let graph = notched.straight_skeleton();
+        check_tree(&graph, &notched);
+        let l_shape = VertexList2(vec![
+            Vec2::ZERO,
+            Vec2::new(6., 0.),
+            Vec2::new(6., 2.),
+            Vec2::new(2., 2.),
+            Vec2::new(2., 5.),
+            Vec2::new(0., 5.),
+        ]);
+        check_tree(&l_shape.straight_skeleton(), &l_shape);
+    }
+
+    #[test]
+    fn medial_axis_follows_the_middle() {
+        let rectangle = VertexList2(vec![
+            Vec2::ZERO,
+            Vec2::new(4., 0.),
+            Vec2::new(4., 2.),
+            Vec2::new(0., 2.),
+        ]);
+        let graph = rectangle.medial_axis(0.1);
+        assert!(!graph.edges.is_empty());
+        for vertex in graph.vertices.iter() {
+            assert!(rectangle.contains_point(vertex.position));
+            assert!(vertex.distance <= 1. + 1e-4);
+        }
+        assert!(graph
+            .vertices
+            .iter()
+            .any(|vertex| vertex.position.distance(Vec2::new(2., 1.)) < 0.1));
+        // the ridge along the middle is all at the same depth
+        let ridge = graph
+            .vertices
+            .iter()
+            .filter(|vertex| (1.2..2.8).contains(&vertex.position.x));
+        assert!(ridge
+            .into_iter()
+            .all(|vertex| (vertex.position.y - 1.).abs() < 0.05));
+    }
+}
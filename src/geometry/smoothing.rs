@@ -0,0 +1,86 @@
+use glam::Vec2;
+
+// Shared by `Polyline` and `VertexList2`. Closed curves wrap around, open curves either pin their
+// ends in place or let them follow their single neighbour.
+
+pub(super) fn chaikin(points: &[Vec2], iterations: usize, closed: bool, pin: bool) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+    for _ in 0..iterations {
+        if points.len() < 3 {
+            break;
+        }
+        let edge_count = if closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        let mut cut = Vec::with_capacity(edge_count * 2 + 2);
+        if !closed && pin {
+            cut.push(points[0]);
+        }
+        for index in 0..edge_count {
+            let (start, end) = (points[index], points[(index + 1) % points.len()]);
+            cut.push(start.lerp(end, 0.25));
+            cut.push(start.lerp(end, 0.75));
+        }
+        if !closed && pin {
+            cut.push(*points.last().unwrap());
+        }
+        points = cut;
+    }
+    points
+}
+
+pub(super) fn laplacian(
+    points: &[Vec2],
+    iterations: usize,
+    factor: f32,
+    closed: bool,
+    pin: bool,
+) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+    for _ in 0..iterations {
+        points = relax(&points, factor, closed, pin);
+    }
+    points
+}
+
+pub(super) fn taubin(
+    points: &[Vec2],
+    iterations: usize,
+    lambda: f32,
+    mu: f32,
+    closed: bool,
+    pin: bool,
+) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+    for _ in 0..iterations {
+        points = relax(&points, lambda, closed, pin);
+        points = relax(&points, mu, closed, pin);
+    }
+    points
+}
+
+// moves every point `factor` of the way towards the average of its neighbours
+fn relax(points: &[Vec2], factor: f32, closed: bool, pin: bool) -> Vec<Vec2> {
+    let count = points.len();
+    if count < 2 {
+        return points.to_vec();
+    }
+    (0..count)
+        .map(|index| {
+            let point = points[index];
+            let target = if closed {
+                (points[(index + count - 1) % count] + points[(index + 1) % count]) * 0.5
+            } else if index == 0 || index == count - 1 {
+                if pin {
+                    return point;
+                }
+                points[if index == 0 { 1 } else { count - 2 }]
+            } else {
+                (points[index - 1] + points[index + 1]) * 0.5
+            };
+            point + (target - point) * factor
+        })
+        .collect()
+}
@@ -0,0 +1,57 @@
+use glam::Vec3;
+
+use super::Ray3;
+
+/// The 3D counterpart of [`Circle`](super::Circle). Like it, `radius` spans the full width.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+macro_rules! sphere_validity_check {
+    ($sphere:expr) => {
+        bodge_assert!($sphere.center.is_finite());
+        bodge_assert!($sphere.radius.is_finite());
+        bodge_assert!($sphere.radius > 0.);
+    };
+}
+
+impl Sphere {
+    pub fn new(center: Vec3, radius: f32) -> Sphere {
+        let sphere = Sphere { center, radius };
+        sphere_validity_check!(sphere);
+        sphere
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        if self.contains_point(point) {
+            point
+        } else {
+            self.center + (point - self.center).normalize() * self.radius * 0.5
+        }
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        sphere_validity_check!(self);
+        self.center.distance(point) <= self.radius * 0.5
+    }
+
+    /// Distance along the ray at which it enters this sphere, or zero if it starts inside.
+    pub fn ray_entry_distance(&self, ray: Ray3) -> Option<f32> {
+        sphere_validity_check!(self);
+        let offset = ray.start - self.center;
+        let half_radius = self.radius * 0.5;
+        let c = offset.length_squared() - half_radius * half_radius;
+        if c <= 0. {
+            return Some(0.);
+        }
+        let b = offset.dot(ray.axis);
+        let discriminant = b * b - c;
+        if b > 0. || discriminant < 0. {
+            None
+        } else {
+            Some(-b - discriminant.sqrt())
+        }
+    }
+}
@@ -1,19 +1,43 @@
-use glam::{Mat3, Vec2};
+use glam::Vec2;
 
-use super::{Circle, Line2, LineSegment2};
+use super::{incircle, orient2d, Circle, Line2, LineSegment2};
 
 /// A 2D triangle represented by three distinct vertices.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Triangle2Unchecked"))]
 pub struct Triangle2 {
     pub vertices: [Vec2; 3],
 }
 
 macro_rules! triangle_2_validity_check {
     ($triangle_2:expr) => {
-        bodge_assert!($triangle_2.vertices[0].is_finite());
-        bodge_assert!($triangle_2.vertices[1].is_finite());
-        bodge_assert!($triangle_2.vertices[2].is_finite());
+        triangle_2_validity_check!($triangle_2, bodge_assert);
     };
+    ($triangle_2:expr, $check:ident) => {
+        $check!($triangle_2.vertices[0].is_finite());
+        $check!($triangle_2.vertices[1].is_finite());
+        $check!($triangle_2.vertices[2].is_finite());
+    };
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Triangle2Unchecked {
+    vertices: [Vec2; 3],
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Triangle2Unchecked> for Triangle2 {
+    type Error = &'static str;
+
+    fn try_from(unchecked: Triangle2Unchecked) -> Result<Triangle2, &'static str> {
+        let triangle = Triangle2 {
+            vertices: unchecked.vertices,
+        };
+        triangle_2_validity_check!(triangle, bodge_validate);
+        Ok(triangle)
+    }
 }
 
 impl Triangle2 {
@@ -164,25 +188,40 @@ impl Triangle2 {
     }
 
     pub fn contains_point(&self, point: Vec2) -> bool {
-        fn sign(p1: Vec2, p2: Vec2, p3: Vec2) -> f32 {
-            (p1.x - p3.x) * (p2.y - p3.y) - (p2.x - p3.x) * (p1.y - p3.y)
-        }
-        let d1 = sign(point, self.a(), self.b());
-        let d2 = sign(point, self.b(), self.c());
-        let d3 = sign(point, self.c(), self.a());
+        let d1 = orient2d(point, self.a(), self.b());
+        let d2 = orient2d(point, self.b(), self.c());
+        let d3 = orient2d(point, self.c(), self.a());
         let has_neg = (d1 < 0.) || (d2 < 0.) || (d3 < 0.);
         let has_pos = (d1 > 0.) || (d2 > 0.) || (d3 > 0.);
         !(has_neg && has_pos)
     }
 
-    pub fn is_clockwise(&self) -> bool {
-        // TODO: simplify this function
-        Mat3 {
-            x_axis: self.a().extend(1.),
-            y_axis: self.b().extend(1.),
-            z_axis: self.c().extend(1.),
+    /// Whether a point lies strictly inside the circumcircle, decided exactly.
+    pub fn circumcircle_contains_point(&self, point: Vec2) -> bool {
+        let determinant = incircle(self.a(), self.b(), self.c(), point);
+        if self.is_clockwise() {
+            determinant < 0.
+        } else {
+            determinant > 0.
         }
-        .determinant()
-            < 0.
+    }
+
+    pub fn is_clockwise(&self) -> bool {
+        orient2d(self.a(), self.b(), self.c()) < 0.
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triangle_deserialize() {
+        let triangle = Triangle2::new(Vec2::ZERO, Vec2::X, Vec2::new(0.5, 2.));
+        assert_eq!(
+            ron::from_str::<Triangle2>(&ron::to_string(&triangle).unwrap()).unwrap(),
+            triangle
+        );
+        assert!(ron::from_str::<Triangle2>("(vertices: ((0., 0.), (1., 0.), (NaN, 1.)))").is_err());
     }
 }
@@ -0,0 +1,146 @@
+use glam::Vec3;
+
+use super::{LineSegment3, Plane, Ray3};
+
+/// The 3D counterpart of [`Triangle2`](super::Triangle2).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle3 {
+    pub vertices: [Vec3; 3],
+}
+
+macro_rules! triangle_3_validity_check {
+    ($triangle_3:expr) => {
+        bodge_assert!($triangle_3.vertices[0].is_finite());
+        bodge_assert!($triangle_3.vertices[1].is_finite());
+        bodge_assert!($triangle_3.vertices[2].is_finite());
+    };
+}
+
+impl Triangle3 {
+    pub fn new(a: Vec3, b: Vec3, c: Vec3) -> Triangle3 {
+        let triangle = Triangle3 {
+            vertices: [a, b, c],
+        };
+        triangle_3_validity_check!(triangle);
+        triangle
+    }
+
+    pub fn a(&self) -> Vec3 {
+        triangle_3_validity_check!(self);
+        self.vertices[0]
+    }
+
+    pub fn b(&self) -> Vec3 {
+        triangle_3_validity_check!(self);
+        self.vertices[1]
+    }
+
+    pub fn c(&self) -> Vec3 {
+        triangle_3_validity_check!(self);
+        self.vertices[2]
+    }
+
+    pub fn edges(&self) -> [LineSegment3; 3] {
+        triangle_3_validity_check!(self);
+        [
+            LineSegment3::new(self.a(), self.b()),
+            LineSegment3::new(self.b(), self.c()),
+            LineSegment3::new(self.c(), self.a()),
+        ]
+    }
+
+    /// Unit normal facing the side the vertices appear counterclockwise from, or zero if the
+    /// triangle is degenerate.
+    pub fn normal(&self) -> Vec3 {
+        triangle_3_validity_check!(self);
+        (self.b() - self.a())
+            .cross(self.c() - self.a())
+            .normalize_or_zero()
+    }
+
+    pub fn plane(&self) -> Plane {
+        Plane::new_from_point_normal(self.a(), self.normal())
+    }
+
+    pub fn centroid(&self) -> Vec3 {
+        triangle_3_validity_check!(self);
+        (self.a() + self.b() + self.c()) / 3.
+    }
+
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        triangle_3_validity_check!(self);
+        bodge_assert!(point.is_finite());
+        // Ericson, Real-Time Collision Detection 5.1.5
+        let (a, b, c) = (self.a(), self.b(), self.c());
+        let (ab, ac, ap) = (b - a, c - a, point - a);
+        let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+        if d1 <= 0. && d2 <= 0. {
+            return a;
+        }
+        let bp = point - b;
+        let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+        if d3 >= 0. && d4 <= d3 {
+            return b;
+        }
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0. && d1 >= 0. && d3 <= 0. {
+            return a + ab * (d1 / (d1 - d3));
+        }
+        let cp = point - c;
+        let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+        if d6 >= 0. && d5 <= d6 {
+            return c;
+        }
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0. && d2 >= 0. && d6 <= 0. {
+            return a + ac * (d2 / (d2 - d6));
+        }
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0. && d4 - d3 >= 0. && d5 - d6 >= 0. {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+        let denominator = va + vb + vc;
+        if denominator == 0. {
+            // degenerate triangle, fall back to its edges
+            return self
+                .edges()
+                .map(|edge| edge.closest_point(point))
+                .into_iter()
+                .min_by(|x, y| {
+                    x.distance_squared(point)
+                        .total_cmp(&y.distance_squared(point))
+                })
+                .unwrap();
+        }
+        a + ab * (vb / denominator) + ac * (vc / denominator)
+    }
+
+    /// Distance along the ray at which it hits either face of this triangle.
+    pub fn ray_entry_distance(&self, ray: Ray3) -> Option<f32> {
+        triangle_3_validity_check!(self);
+        // Möller-Trumbore
+        let (ab, ac) = (self.b() - self.a(), self.c() - self.a());
+        let p = ray.axis.cross(ac);
+        let determinant = ab.dot(p);
+        if determinant == 0. {
+            return None;
+        }
+        let inverse = 1. / determinant;
+        let offset = ray.start - self.a();
+        let u = offset.dot(p) * inverse;
+        if !(0. ..=1.).contains(&u) {
+            return None;
+        }
+        let q = offset.cross(ab);
+        let v = ray.axis.dot(q) * inverse;
+        if v < 0. || u + v > 1. {
+            return None;
+        }
+        let t = ac.dot(q) * inverse;
+        if t >= 0. {
+            Some(t)
+        } else {
+            None
+        }
+    }
+}
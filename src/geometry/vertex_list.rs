@@ -2,8 +2,13 @@ use glam::Vec2;
 
 use crate::collision::Colliding;
 
-use super::LineSegment2;
+use super::{
+    orient2d, polyline::douglas_peucker, skeleton, smoothing, LineSegment2, SkeletonGraph,
+    Triangle2,
+};
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexList2(pub Vec<Vec2>);
 
 impl VertexList2 {
@@ -11,6 +16,36 @@ impl VertexList2 {
         VertexList2(Vec::new())
     }
 
+    /// The convex hull of `points`, counterclockwise in the sense of [`orient2d`](super::orient2d)
+    /// and without collinear vertices.
+    pub fn new_convex_hull(points: &[Vec2]) -> VertexList2 {
+        let mut sorted = points.to_vec();
+        sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        sorted.dedup();
+        if sorted.len() < 3 {
+            return VertexList2(sorted);
+        }
+        // Andrew's monotone chain, building the lower hull forwards and the upper hull backwards
+        let mut hull: Vec<Vec2> = Vec::with_capacity(sorted.len() * 2);
+        for (pass, chain) in [sorted.clone(), sorted.into_iter().rev().collect()]
+            .into_iter()
+            .enumerate()
+        {
+            let floor = if pass == 0 { 0 } else { hull.len() - 1 };
+            for point in chain {
+                while hull.len() >= floor + 2
+                    && orient2d(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.
+                {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+        }
+        // the upper hull ends back on the first point
+        hull.pop();
+        VertexList2(hull)
+    }
+
     pub fn edges(&self) -> Vec<LineSegment2> {
         // TODO: make this an iterator instead?
         let mut edges = vec![];
@@ -41,14 +76,35 @@ impl VertexList2 {
         true
     }
 
-    pub fn is_clockwise(&self) -> bool {
-        let mut area = 0.;
-        for i in 0..self.0.len() {
-            let j = (i + 1) % self.0.len();
-            area += self.0[i].x * self.0[j].y;
-            area -= self.0[j].x * self.0[i].y;
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        bodge_assert!(point.is_finite());
+        let mut inside = false;
+        for point_index in 0..self.0.len() {
+            let start = self.0[point_index];
+            let end = self.0[(point_index + 1) % self.0.len()];
+            if (start.y > point.y) != (end.y > point.y)
+                && point.x < start.x + (point.y - start.y) / (end.y - start.y) * (end.x - start.x)
+            {
+                inside = !inside;
+            }
         }
-        area / 2. > 0.
+        inside
+    }
+
+    /// The enclosed area, positive when counterclockwise and negative when clockwise in the sense
+    /// of [`orient2d`](super::orient2d).
+    pub fn signed_area(&self) -> f32 {
+        let vertices = &self.0;
+        let area: f64 = (1..vertices.len().saturating_sub(1))
+            .map(|index| orient2d(vertices[0], vertices[index], vertices[index + 1]))
+            .sum();
+        (area * 0.5) as f32
+    }
+
+    /// Clockwise with y pointing down, which is counterclockwise in the sense of
+    /// [`orient2d`](super::orient2d).
+    pub fn is_clockwise(&self) -> bool {
+        self.signed_area() > 0.
     }
 
     pub fn make_clockwise(&mut self) {
@@ -70,4 +126,175 @@ impl VertexList2 {
             self.0 = new_points;
         }
     }
+
+    /// Splits a simple polygon into triangles by ear clipping, in either winding.
+    pub fn triangulate(&self) -> Vec<Triangle2> {
+        let mut vertices = self.0.clone();
+        // work counterclockwise in the sense of orient2d, where ears turn left
+        if self.signed_area() < 0. {
+            vertices.reverse();
+        }
+        let mut triangles = vec![];
+        while vertices.len() > 3 {
+            let count = vertices.len();
+            let ear = (0..count).find(|index| {
+                let (a, b, c) = (
+                    vertices[(index + count - 1) % count],
+                    vertices[*index],
+                    vertices[(index + 1) % count],
+                );
+                orient2d(a, b, c) > 0.
+                    && vertices.iter().all(|point| {
+                        [a, b, c].contains(point)
+                            || orient2d(a, b, *point) < 0.
+                            || orient2d(b, c, *point) < 0.
+                            || orient2d(c, a, *point) < 0.
+                    })
+            });
+            // degenerate leftovers, such as a run of collinear points, have no ears
+            let Some(index) = ear else {
+                break;
+            };
+            triangles.push(Triangle2::new(
+                vertices[(index + count - 1) % count],
+                vertices[index],
+                vertices[(index + 1) % count],
+            ));
+            vertices.remove(index);
+        }
+        if vertices.len() == 3 && orient2d(vertices[0], vertices[1], vertices[2]) != 0. {
+            triangles.push(Triangle2::new(vertices[0], vertices[1], vertices[2]));
+        }
+        triangles
+    }
+
+    /// Drops points that lie within `tolerance` of the simplified outline, using Douglas-Peucker
+    /// split at the point farthest from the first one.
+    pub fn simplified(&self, tolerance: f32) -> VertexList2 {
+        bodge_assert!(tolerance >= 0.);
+        if self.0.len() < 4 {
+            return self.clone();
+        }
+        let farthest = (1..self.0.len())
+            .max_by(|a, b| {
+                let distance = |index: usize| self.0[0].distance_squared(self.0[index]);
+                distance(*a).total_cmp(&distance(*b))
+            })
+            .unwrap();
+        let mut closed = self.0.clone();
+        closed.push(self.0[0]);
+        let mut points = douglas_peucker(&closed[..=farthest], tolerance);
+        points.pop();
+        points.extend(douglas_peucker(&closed[farthest..], tolerance));
+        points.pop();
+        VertexList2(points)
+    }
+
+    /// Rounds the corners off by cutting each edge at a quarter and three quarters of its length,
+    /// doubling the point count every iteration.
+    pub fn chaikin_smoothed(&self, iterations: usize) -> VertexList2 {
+        VertexList2(smoothing::chaikin(&self.0, iterations, true, false))
+    }
+
+    /// Moves every point `factor` of the way towards the midpoint of its neighbours each iteration.
+    /// Repeated passes also shrink the polygon, see [`VertexList2::taubin_smoothed`].
+    pub fn laplacian_smoothed(&self, iterations: usize, factor: f32) -> VertexList2 {
+        bodge_assert!(factor.is_finite());
+        VertexList2(smoothing::laplacian(
+            &self.0, iterations, factor, true, false,
+        ))
+    }
+
+    /// Alternates a shrinking Laplacian pass of `lambda` with an inflating one of `mu`, which
+    /// should be negative and slightly larger in magnitude, e.g. `0.5` and `-0.53`.
+    pub fn taubin_smoothed(&self, iterations: usize, lambda: f32, mu: f32) -> VertexList2 {
+        bodge_assert!(lambda.is_finite() && mu.is_finite());
+        VertexList2(smoothing::taubin(
+            &self.0, iterations, lambda, mu, true, false,
+        ))
+    }
+
+    /// The straight skeleton of a simple polygon, as used for hip roofs. Vertex distances are how
+    /// far each edge had moved inward to reach them.
+    pub fn straight_skeleton(&self) -> SkeletonGraph {
+        skeleton::straight_skeleton(self)
+    }
+
+    /// An approximate medial axis from the Voronoi diagram of boundary points at most `spacing`
+    /// apart. Smaller spacings follow the true axis more closely but grow more short branches.
+    pub fn medial_axis(&self, spacing: f32) -> SkeletonGraph {
+        skeleton::medial_axis(self, spacing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vertex_list_triangulate() {
+        // an L shape, in both windings
+        let mut l_shape = VertexList2(vec![
+            Vec2::ZERO,
+            Vec2::new(2., 0.),
+            Vec2::new(2., 1.),
+            Vec2::new(1., 1.),
+            Vec2::new(1., 2.),
+            Vec2::new(0., 2.),
+        ]);
+        for _ in 0..2 {
+            let triangles = l_shape.triangulate();
+            assert_eq!(triangles.len(), 4);
+            let total: f32 = triangles
+                .iter()
+                .map(|triangle| (triangle.b() - triangle.a()).perp_dot(triangle.c() - triangle.a()))
+                .sum();
+            assert_eq!(total * 0.5, 3.);
+            assert!(triangles
+                .iter()
+                .all(|triangle| !triangle.contains_point(Vec2::splat(1.5))));
+            l_shape.0.reverse();
+        }
+    }
+
+    #[test]
+    fn vertex_list_smoothing() {
+        let square = VertexList2(vec![
+            Vec2::new(-1., -1.),
+            Vec2::new(1., -1.),
+            Vec2::new(1., 1.),
+            Vec2::new(-1., 1.),
+        ]);
+        let chaikin = square.chaikin_smoothed(3);
+        assert_eq!(chaikin.0.len(), 32);
+        assert!(chaikin
+            .0
+            .iter()
+            .all(|point| point.abs().max_element() <= 1.));
+        assert!(chaikin.signed_area() > 3. && chaikin.signed_area() < 4.);
+
+        let circle = VertexList2(
+            (0..32)
+                .map(|index| Vec2::from_angle(index as f32 / 32. * std::f32::consts::TAU))
+                .collect(),
+        );
+        let laplacian = circle.laplacian_smoothed(50, 0.5);
+        let taubin = circle.taubin_smoothed(50, 0.5, -0.53);
+        assert_eq!(taubin.0.len(), 32);
+        assert!(laplacian.0.iter().all(|point| point.length() < 0.7));
+        assert!(taubin
+            .0
+            .iter()
+            .all(|point| (point.length() - 1.).abs() < 0.05));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn vertex_list_deserialize() {
+        let vertex_list = VertexList2(vec![Vec2::ZERO, Vec2::X, Vec2::ONE]);
+        assert_eq!(
+            ron::from_str::<VertexList2>(&ron::to_string(&vertex_list).unwrap()).unwrap(),
+            vertex_list
+        );
+    }
 }
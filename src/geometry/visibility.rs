@@ -0,0 +1,208 @@
+use std::f32::consts::{PI, TAU};
+
+use glam::Vec2;
+
+use super::{LineIntersection, LineRay2, LineSegment2, VertexList2};
+
+// how far to either side of an occluder's end point to look past it
+const ANGLE_OFFSET: f32 = 1e-4;
+
+/// Settings for computing the region visible from a viewpoint among occluding segments.
+///
+/// Rays are cast towards every occluder end point and just past it, plus `segments` evenly spaced
+/// rays to round off the `max_radius` circle where nothing is in the way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisibilityPolygon {
+    pub max_radius: f32,
+    /// Limits the view to within `cone_half_angle` radians of `cone_direction`. Anything from
+    /// `PI` up sees all around.
+    pub cone_direction: Vec2,
+    pub cone_half_angle: f32,
+    pub segments: u8,
+}
+
+impl Default for VisibilityPolygon {
+    fn default() -> VisibilityPolygon {
+        VisibilityPolygon {
+            max_radius: 1000.,
+            cone_direction: Vec2::X,
+            cone_half_angle: PI,
+            segments: 64,
+        }
+    }
+}
+
+impl VisibilityPolygon {
+    pub fn new(max_radius: f32) -> VisibilityPolygon {
+        VisibilityPolygon {
+            max_radius,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_cone(&self, direction: Vec2, half_angle: f32) -> VisibilityPolygon {
+        VisibilityPolygon {
+            cone_direction: direction,
+            cone_half_angle: half_angle,
+            ..*self
+        }
+    }
+
+    pub fn with_segments(&self, segments: u8) -> VisibilityPolygon {
+        VisibilityPolygon { segments, ..*self }
+    }
+
+    /// The region visible from `viewpoint`, counterclockwise in the sense of
+    /// [`orient2d`](super::orient2d). A view cone adds the viewpoint itself as a vertex.
+    pub fn compute(&self, viewpoint: Vec2, occluders: &[LineSegment2]) -> VertexList2 {
+        bodge_assert!(viewpoint.is_finite());
+        bodge_assert!(self.max_radius.is_finite() && self.max_radius > 0.);
+        bodge_assert!(self.cone_direction.is_finite() && self.cone_direction != Vec2::ZERO);
+        bodge_assert!(self.cone_half_angle > 0.);
+        let cone_angle = self.cone_direction.y.atan2(self.cone_direction.x);
+        let is_cone = self.cone_half_angle < PI;
+        let half_angle = self.cone_half_angle.min(PI);
+        let relative = |point: Vec2| {
+            let offset = point - viewpoint;
+            wrap(offset.y.atan2(offset.x) - cone_angle)
+        };
+
+        let mut angles = vec![];
+        for occluder in occluders.iter() {
+            for end in [occluder.start, occluder.end] {
+                let angle = relative(end);
+                angles.extend([
+                    wrap(angle - ANGLE_OFFSET),
+                    angle,
+                    wrap(angle + ANGLE_OFFSET),
+                ]);
+            }
+            // where the occluder leaves the view circle
+            for point in circle_crossings(viewpoint, self.max_radius, *occluder) {
+                angles.push(relative(point));
+            }
+        }
+        let segments = self.segments.max(3) as usize;
+        angles.extend((0..segments).map(|index| wrap(index as f32 / segments as f32 * TAU)));
+        angles.retain(|angle| angle.abs() <= half_angle);
+        if is_cone {
+            angles.extend([-half_angle, half_angle]);
+        }
+        angles.sort_by(|a, b| a.total_cmp(b));
+        angles.dedup();
+
+        let mut points = vec![];
+        if is_cone {
+            points.push(viewpoint);
+        }
+        for angle in angles {
+            let ray = LineRay2::new(viewpoint, Vec2::from_angle(angle + cone_angle));
+            let distance = occluders
+                .iter()
+                .filter_map(|occluder| match ray.intersection_segment(*occluder) {
+                    LineIntersection::Point(point) => Some(point.distance(viewpoint)),
+                    _ => None,
+                })
+                .fold(self.max_radius, f32::min);
+            let point = viewpoint + ray.axis * distance;
+            if points
+                .last()
+                .is_none_or(|last: &Vec2| last.distance_squared(point) > 1e-10)
+            {
+                points.push(point);
+            }
+        }
+        VertexList2(points)
+    }
+
+    /// [`VisibilityPolygon::compute`] with the edges of polygons as occluders.
+    pub fn compute_polygons(&self, viewpoint: Vec2, occluders: &[VertexList2]) -> VertexList2 {
+        let edges: Vec<LineSegment2> = occluders
+            .iter()
+            .flat_map(|occluder| occluder.edges())
+            .collect();
+        self.compute(viewpoint, &edges)
+    }
+}
+
+fn wrap(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+fn circle_crossings(center: Vec2, radius: f32, segment: LineSegment2) -> Vec<Vec2> {
+    let (start, axis) = (segment.start - center, segment.end - segment.start);
+    let a = axis.length_squared();
+    if a == 0. {
+        return vec![];
+    }
+    let b = start.dot(axis);
+    let discriminant = b * b - a * (start.length_squared() - radius * radius);
+    if discriminant < 0. {
+        return vec![];
+    }
+    let root = discriminant.sqrt();
+    [(-b - root) / a, (-b + root) / a]
+        .into_iter()
+        .filter(|t| (0. ..=1.).contains(t))
+        .map(|t| segment.start + axis * t)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use super::*;
+
+    #[test]
+    fn visibility_polygon_hides_behind_walls() {
+        let wall = LineSegment2::new(Vec2::new(2., -1.), Vec2::new(2., 1.));
+        let visible = VisibilityPolygon::new(5.).compute(Vec2::ZERO, &[wall]);
+        assert!(visible.contains_point(Vec2::new(1., 0.)));
+        assert!(visible.contains_point(Vec2::new(0., 3.)));
+        assert!(visible.contains_point(Vec2::new(-4.5, 0.)));
+        assert!(!visible.contains_point(Vec2::new(3., 0.)));
+        assert!(!visible.contains_point(Vec2::new(4., 1.9)));
+        assert!(visible.contains_point(Vec2::new(4., 2.1)));
+        assert!(!visible.contains_point(Vec2::new(0., 5.1)));
+
+        let square = VertexList2(vec![
+            Vec2::new(2., -1.),
+            Vec2::new(4., -1.),
+            Vec2::new(4., 1.),
+            Vec2::new(2., 1.),
+        ]);
+        let visible = VisibilityPolygon::new(10.).compute_polygons(Vec2::ZERO, &[square]);
+        assert!(!visible.contains_point(Vec2::new(3., 0.)));
+        assert!(!visible.contains_point(Vec2::new(8., 0.)));
+        assert!(visible.contains_point(Vec2::new(1.9, 0.)));
+    }
+
+    #[test]
+    fn visibility_polygon_wraps_round_behind() {
+        // walls ending level with or a hair below the viewpoint, straight behind the cone
+        // direction, so the rays just above their ends wrap round to the other end of the angles
+        for step in 0..40 {
+            let top = step as f32 * -5e-6;
+            let wall = LineSegment2::new(Vec2::new(-2., top), Vec2::new(-2., -3.));
+            let visible = VisibilityPolygon::new(5.)
+                .with_segments(15)
+                .compute(Vec2::ZERO, &[wall]);
+            assert!(visible.contains_point(Vec2::new(-4.5, 0.05)));
+            assert!(!visible.contains_point(Vec2::new(-3., -1.)));
+            assert!(visible.contains_point(Vec2::new(-1., -1.)));
+        }
+    }
+
+    #[test]
+    fn visibility_polygon_view_cone() {
+        let visible = VisibilityPolygon::new(5.)
+            .with_cone(Vec2::X, FRAC_PI_4)
+            .compute(Vec2::ONE, &[]);
+        assert_eq!(visible.0[0], Vec2::ONE);
+        assert!(visible.contains_point(Vec2::new(4., 1.5)));
+        assert!(!visible.contains_point(Vec2::new(1., 4.)));
+        assert!(!visible.contains_point(Vec2::new(0., 1.)));
+        assert!(!visible.contains_point(Vec2::new(3., 4.)));
+    }
+}
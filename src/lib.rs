@@ -4,7 +4,9 @@ mod macros;
 pub mod collision;
 pub mod control;
 pub mod geometry;
+pub mod navigation;
 pub mod prelude;
+pub mod svg;
 pub mod utility;
 
 #[cfg(feature = "bevy")]
@@ -6,3 +6,15 @@ macro_rules! bodge_assert {
 macro_rules! bodge_assert {
     ($($arg:tt)*) => {};
 }
+
+// A validity check for untrusted data such as deserialized values, returning an error from the
+// surrounding function rather than asserting, and running whether or not `bodge_assert` is on.
+#[cfg(feature = "serde")]
+macro_rules! bodge_validate {
+    ($condition:expr) => {
+        let valid: bool = $condition;
+        if !valid {
+            return Err(concat!("failed validity check: ", stringify!($condition)));
+        }
+    };
+}
@@ -0,0 +1,5 @@
+mod nav_grid;
+mod nav_mesh;
+
+pub use nav_grid::*;
+pub use nav_mesh::*;
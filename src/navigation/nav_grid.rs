@@ -0,0 +1,478 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use glam::{IVec2, UVec2, Vec2};
+
+use crate::{
+    collision::Colliding,
+    geometry::{Aabb, LineSegment2, Polyline, Raster},
+    utility::SortableWith,
+};
+
+const ORTHOGONAL: [IVec2; 4] = [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
+const DIAGONAL: [IVec2; 4] = [
+    IVec2::new(1, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+    IVec2::new(1, -1),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GridConnectivity {
+    Four,
+    #[default]
+    Eight,
+}
+
+/// When a diagonal step may pass the corner of a blocked cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CornerCutting {
+    /// Diagonal steps ignore the two cells they pass between.
+    Allow,
+    /// At least one of the two cells passed between must be open.
+    IfOneOpen,
+    /// Both cells passed between must be open.
+    #[default]
+    Never,
+}
+
+/// A grid of walkable cells with a cost per cell, for A* paths and Dijkstra flow fields.
+///
+/// Stepping into a cell costs its cost times the length of the step. Cell `(x, y)` covers
+/// `origin + Vec2::new(x, y) * cell_size` to one `cell_size` further.
+#[derive(Debug, Clone)]
+pub struct NavGrid {
+    pub origin: Vec2,
+    pub cell_size: Vec2,
+    pub connectivity: GridConnectivity,
+    pub corner_cutting: CornerCutting,
+    width: u32,
+    height: u32,
+    costs: Vec<Option<f32>>,
+}
+
+/// Directions towards one goal for every cell of a [`NavGrid`], made by [`NavGrid::flow_field`].
+#[derive(Debug, Clone)]
+pub struct FlowField {
+    goal: UVec2,
+    width: u32,
+    distances: Vec<f32>,
+    next: Vec<Option<UVec2>>,
+    origin: Vec2,
+    cell_size: Vec2,
+}
+
+impl NavGrid {
+    /// A grid with every cell open at a cost of one.
+    pub fn new(width: u32, height: u32, cell_size: Vec2) -> NavGrid {
+        bodge_assert!(cell_size.is_finite() && cell_size.cmpgt(Vec2::ZERO).all());
+        NavGrid {
+            origin: Vec2::ZERO,
+            cell_size,
+            connectivity: GridConnectivity::default(),
+            corner_cutting: CornerCutting::default(),
+            width,
+            height,
+            costs: vec![Some(1.); (width * height) as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The cost of stepping into a cell, or `None` if it is blocked or outside the grid.
+    pub fn cost(&self, cell: UVec2) -> Option<f32> {
+        self.index(cell.as_ivec2())
+            .and_then(|index| self.costs[index])
+    }
+
+    /// Sets the cost of stepping into a cell, or blocks it with `None`.
+    pub fn set_cost(&mut self, cell: UVec2, cost: Option<f32>) {
+        bodge_assert!(cost.is_none_or(|cost| cost.is_finite() && cost >= 0.));
+        let index = self.index(cell.as_ivec2()).unwrap();
+        self.costs[index] = cost;
+    }
+
+    pub fn is_blocked(&self, cell: UVec2) -> bool {
+        self.cost(cell).is_none()
+    }
+
+    pub fn cell_at(&self, point: Vec2) -> Option<UVec2> {
+        bodge_assert!(point.is_finite());
+        let cell = ((point - self.origin) / self.cell_size).floor();
+        if cell.cmpge(Vec2::ZERO).all() && cell.x < self.width as f32 && cell.y < self.height as f32
+        {
+            Some(cell.as_uvec2())
+        } else {
+            None
+        }
+    }
+
+    pub fn cell_center(&self, cell: UVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + 0.5) * self.cell_size
+    }
+
+    pub fn cell_aabb(&self, cell: UVec2) -> Aabb {
+        Aabb::new(self.cell_center(cell), self.cell_size)
+    }
+
+    /// Whether a straight line between two points stays clear of blocked cells and the outside
+    /// of the grid. Grazing the corner or edge of a blocked cell counts as blocked.
+    pub fn line_of_sight(&self, start: Vec2, end: Vec2) -> bool {
+        if self.cell_at(start).is_none() || self.cell_at(end).is_none() {
+            return false;
+        }
+        let segment = LineSegment2::new(start, end);
+        let mut cells = Raster::new(self.cell_size)
+            .with_origin(self.origin)
+            .traverse(start, end);
+        // the walk only takes the cells above or to the right of a border the line runs along
+        let local = (start - self.origin) / self.cell_size;
+        for (axis, offset) in [(0, IVec2::NEG_X), (1, IVec2::NEG_Y)] {
+            if start[axis] == end[axis] && local[axis].fract() == 0. {
+                let neighbours: Vec<IVec2> = cells.iter().map(|cell| *cell + offset).collect();
+                cells.extend(neighbours);
+            }
+        }
+        !cells.into_iter().any(|cell| {
+            self.index(cell).is_some_and(|index| {
+                self.costs[index].is_none() && segment.colliding(&self.cell_aabb(cell.as_uvec2()))
+            })
+        })
+    }
+
+    /// The cheapest chain of cells from `start` to `goal` with A*, both included.
+    pub fn find_cells(&self, start: UVec2, goal: UVec2) -> Option<Vec<UVec2>> {
+        let (start_index, goal_index) =
+            (self.index(start.as_ivec2())?, self.index(goal.as_ivec2())?);
+        if self.costs[start_index].is_none() || self.costs[goal_index].is_none() {
+            return None;
+        }
+        // scaling by the cheapest cell keeps the estimate from overshooting
+        let cheapest = self
+            .costs
+            .iter()
+            .flatten()
+            .fold(f32::INFINITY, |a, b| a.min(*b));
+        let estimate = |cell: UVec2| self.heuristic(cell, goal) * cheapest;
+
+        let mut costs = vec![f32::INFINITY; self.costs.len()];
+        let mut previous = vec![None; self.costs.len()];
+        let mut open = BinaryHeap::new();
+        costs[start_index] = 0.;
+        open.push(Reverse(SortableWith(estimate(start), start)));
+        while let Some(Reverse(SortableWith(_, cell))) = open.pop() {
+            if cell == goal {
+                let mut cells = vec![goal];
+                while let Some(before) =
+                    previous[self.index(cells.last().unwrap().as_ivec2()).unwrap()]
+                {
+                    cells.push(before);
+                }
+                cells.reverse();
+                return Some(cells);
+            }
+            let cell_cost = costs[self.index(cell.as_ivec2()).unwrap()];
+            for (neighbour, length) in self.steps(cell) {
+                let index = self.index(neighbour.as_ivec2()).unwrap();
+                let cost = cell_cost + length * self.costs[index].unwrap();
+                if cost < costs[index] {
+                    costs[index] = cost;
+                    previous[index] = Some(cell);
+                    open.push(Reverse(SortableWith(cost + estimate(neighbour), neighbour)));
+                }
+            }
+        }
+        None
+    }
+
+    /// A path between two points through cell centers, shortened by skipping every cell center
+    /// that is in line of sight. Skipping only looks at blocked cells, so the result may cut
+    /// across expensive ones.
+    pub fn find_path(&self, start: Vec2, goal: Vec2) -> Option<Polyline> {
+        let cells = self.find_cells(self.cell_at(start)?, self.cell_at(goal)?)?;
+        let mut points: Vec<Vec2> = cells.iter().map(|cell| self.cell_center(*cell)).collect();
+        points[0] = start;
+        *points.last_mut().unwrap() = goal;
+        Some(Polyline(self.smoothed(&points)))
+    }
+
+    /// The cheapest way to `goal` from every cell, found with Dijkstra's algorithm.
+    pub fn flow_field(&self, goal: UVec2) -> FlowField {
+        let mut field = FlowField {
+            goal,
+            width: self.width,
+            distances: vec![f32::INFINITY; self.costs.len()],
+            next: vec![None; self.costs.len()],
+            origin: self.origin,
+            cell_size: self.cell_size,
+        };
+        let Some(goal_index) = self
+            .index(goal.as_ivec2())
+            .filter(|index| self.costs[*index].is_some())
+        else {
+            return field;
+        };
+        field.distances[goal_index] = 0.;
+        let mut open = BinaryHeap::new();
+        open.push(Reverse(SortableWith(0., goal)));
+        while let Some(Reverse(SortableWith(distance, cell))) = open.pop() {
+            let index = self.index(cell.as_ivec2()).unwrap();
+            if distance > field.distances[index] {
+                continue;
+            }
+            // steps are symmetric, so stepping back from `cell` finds everything that reaches it
+            for (neighbour, length) in self.steps(cell) {
+                let neighbour_index = self.index(neighbour.as_ivec2()).unwrap();
+                let distance = distance + length * self.costs[index].unwrap();
+                if distance < field.distances[neighbour_index] {
+                    field.distances[neighbour_index] = distance;
+                    field.next[neighbour_index] = Some(cell);
+                    open.push(Reverse(SortableWith(distance, neighbour)));
+                }
+            }
+        }
+        field
+    }
+
+    fn index(&self, cell: IVec2) -> Option<usize> {
+        if cell.cmpge(IVec2::ZERO).all()
+            && (cell.x as u32) < self.width
+            && (cell.y as u32) < self.height
+        {
+            Some((cell.y as u32 * self.width + cell.x as u32) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn open(&self, cell: IVec2) -> bool {
+        self.index(cell)
+            .is_some_and(|index| self.costs[index].is_some())
+    }
+
+    // open neighbours reachable in one step and how long that step is
+    fn steps(&self, cell: UVec2) -> Vec<(UVec2, f32)> {
+        let cell = cell.as_ivec2();
+        let mut directions = ORTHOGONAL.to_vec();
+        if self.connectivity == GridConnectivity::Eight {
+            directions.extend(DIAGONAL.iter().filter(|direction| {
+                let sides = [
+                    self.open(cell + IVec2::new(direction.x, 0)),
+                    self.open(cell + IVec2::new(0, direction.y)),
+                ];
+                match self.corner_cutting {
+                    CornerCutting::Allow => true,
+                    CornerCutting::IfOneOpen => sides[0] || sides[1],
+                    CornerCutting::Never => sides[0] && sides[1],
+                }
+            }));
+        }
+        directions
+            .into_iter()
+            .filter_map(|direction| {
+                let neighbour = cell + direction;
+                self.open(neighbour).then(|| {
+                    let length = (direction.as_vec2() * self.cell_size).length();
+                    (neighbour.as_uvec2(), length)
+                })
+            })
+            .collect()
+    }
+
+    fn heuristic(&self, cell: UVec2, goal: UVec2) -> f32 {
+        let delta = (cell.as_ivec2() - goal.as_ivec2()).abs().as_vec2();
+        match self.connectivity {
+            GridConnectivity::Four => delta.dot(self.cell_size),
+            GridConnectivity::Eight => {
+                let diagonal = delta.min_element();
+                diagonal * self.cell_size.length() + (delta - diagonal).dot(self.cell_size)
+            }
+        }
+    }
+
+    // keeps only the points the path has to turn at to stay in line of sight
+    fn smoothed(&self, points: &[Vec2]) -> Vec<Vec2> {
+        let mut smoothed = vec![points[0]];
+        let mut anchor = 0;
+        for index in 1..points.len() - 1 {
+            if !self.line_of_sight(points[anchor], points[index + 1]) {
+                smoothed.push(points[index]);
+                anchor = index;
+            }
+        }
+        if points.len() > 1 {
+            smoothed.push(*points.last().unwrap());
+        }
+        smoothed
+    }
+}
+
+impl FlowField {
+    pub fn goal(&self) -> UVec2 {
+        self.goal
+    }
+
+    /// The cost of the cheapest path from a cell to the goal, `None` if there is none.
+    pub fn distance(&self, cell: UVec2) -> Option<f32> {
+        Some(self.distances[self.index(cell)?]).filter(|distance| distance.is_finite())
+    }
+
+    /// The cell to step into next from `cell`, `None` at the goal or when it is unreachable.
+    pub fn next(&self, cell: UVec2) -> Option<UVec2> {
+        self.next[self.index(cell)?]
+    }
+
+    /// The unit direction from a cell's center towards the next cell's center.
+    pub fn direction(&self, cell: UVec2) -> Option<Vec2> {
+        let next = self.next(cell)?;
+        Some(((next.as_vec2() - cell.as_vec2()) * self.cell_size).normalize())
+    }
+
+    /// The cell centers from `cell` to the goal, both included.
+    pub fn path(&self, cell: UVec2) -> Option<Polyline> {
+        self.distance(cell)?;
+        let center = |cell: UVec2| self.origin + (cell.as_vec2() + 0.5) * self.cell_size;
+        let mut points = vec![center(cell)];
+        let mut current = cell;
+        while let Some(next) = self.next(current) {
+            points.push(center(next));
+            current = next;
+        }
+        Some(Polyline(points))
+    }
+
+    fn index(&self, cell: UVec2) -> Option<usize> {
+        let index = (cell.y * self.width + cell.x) as usize;
+        (cell.x < self.width && index < self.distances.len()).then_some(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 10 by 10 grid with a wall along x = 5, open only at y = 8
+    fn walled() -> NavGrid {
+        let mut nav_grid = NavGrid::new(10, 10, Vec2::ONE);
+        for y in 0..10 {
+            if y != 8 {
+                nav_grid.set_cost(UVec2::new(5, y), None);
+            }
+        }
+        nav_grid
+    }
+
+    #[test]
+    fn nav_grid_find_path_through_gap() {
+        let mut nav_grid = walled();
+        let cells = nav_grid
+            .find_cells(UVec2::new(1, 1), UVec2::new(8, 1))
+            .unwrap();
+        assert!(cells.contains(&UVec2::new(5, 8)));
+        assert!(cells
+            .windows(2)
+            .all(|pair| (pair[0].as_ivec2() - pair[1].as_ivec2())
+                .abs()
+                .max_element()
+                == 1));
+
+        let path = nav_grid
+            .find_path(Vec2::new(1.5, 1.5), Vec2::new(8.5, 1.5))
+            .unwrap();
+        assert_eq!(path.0.len(), 4);
+        assert!(path
+            .line_segments()
+            .iter()
+            .all(|segment| nav_grid.line_of_sight(segment.start, segment.end)));
+
+        nav_grid.connectivity = GridConnectivity::Four;
+        let four = nav_grid
+            .find_cells(UVec2::new(1, 1), UVec2::new(8, 1))
+            .unwrap();
+        assert_eq!(four.len(), 7 + 7 + 7 + 1);
+        assert!(four
+            .windows(2)
+            .all(|pair| (pair[0].as_ivec2() - pair[1].as_ivec2())
+                .abs()
+                .dot(IVec2::ONE)
+                == 1));
+
+        nav_grid.set_cost(UVec2::new(5, 8), None);
+        assert!(nav_grid
+            .find_cells(UVec2::new(1, 1), UVec2::new(8, 1))
+            .is_none());
+    }
+
+    #[test]
+    fn nav_grid_line_of_sight() {
+        let nav_grid = walled();
+        assert!(nav_grid.line_of_sight(Vec2::new(0.5, 0.5), Vec2::new(4.5, 9.5)));
+        assert!(!nav_grid.line_of_sight(Vec2::new(0.5, 0.5), Vec2::new(9.5, 0.5)));
+        assert!(nav_grid.line_of_sight(Vec2::new(0.5, 8.5), Vec2::new(9.5, 8.5)));
+        // through the corner where the gap meets the wall
+        assert!(!nav_grid.line_of_sight(Vec2::new(4.5, 9.5), Vec2::new(6.5, 7.5)));
+        assert!(!nav_grid.line_of_sight(Vec2::new(0.5, 0.5), Vec2::new(10.5, 0.5)));
+        // along the edges of the wall, through the gap and past its sides
+        assert!(!nav_grid.line_of_sight(Vec2::new(6., 0.5), Vec2::new(6., 7.5)));
+        assert!(!nav_grid.line_of_sight(Vec2::new(0.5, 8.), Vec2::new(9.5, 8.)));
+        assert!(!nav_grid.line_of_sight(Vec2::new(0.5, 9.), Vec2::new(9.5, 9.)));
+        assert!(nav_grid.line_of_sight(Vec2::new(8., 0.5), Vec2::new(8., 9.5)));
+    }
+
+    #[test]
+    fn nav_grid_corner_cutting() {
+        let mut nav_grid = NavGrid::new(2, 2, Vec2::ONE);
+        nav_grid.set_cost(UVec2::new(1, 0), None);
+        let diagonal = (UVec2::ZERO, UVec2::ONE);
+        assert_eq!(
+            nav_grid.find_cells(diagonal.0, diagonal.1).unwrap().len(),
+            3
+        );
+        nav_grid.corner_cutting = CornerCutting::IfOneOpen;
+        assert_eq!(
+            nav_grid.find_cells(diagonal.0, diagonal.1).unwrap().len(),
+            2
+        );
+        nav_grid.set_cost(UVec2::new(0, 1), None);
+        assert!(nav_grid.find_cells(diagonal.0, diagonal.1).is_none());
+        nav_grid.corner_cutting = CornerCutting::Allow;
+        assert_eq!(
+            nav_grid.find_cells(diagonal.0, diagonal.1).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn nav_grid_weighted_costs_and_flow_field() {
+        let mut nav_grid = walled();
+        // a swamp right behind the gap is cheaper to skip by cutting past the wall's corner
+        nav_grid.corner_cutting = CornerCutting::Allow;
+        nav_grid.set_cost(UVec2::new(6, 8), Some(100.));
+        let cells = nav_grid
+            .find_cells(UVec2::new(1, 1), UVec2::new(8, 1))
+            .unwrap();
+        assert!(!cells.contains(&UVec2::new(6, 8)));
+
+        let field = nav_grid.flow_field(UVec2::new(8, 1));
+        assert_eq!(field.distance(UVec2::new(8, 1)), Some(0.));
+        assert_eq!(field.distance(UVec2::new(5, 1)), None);
+        assert_eq!(field.direction(UVec2::new(8, 2)), Some(Vec2::NEG_Y));
+        // the flow field agrees with A* on the cost of the cheapest path
+        let cost: f32 = cells
+            .windows(2)
+            .map(|pair| {
+                pair[0].as_vec2().distance(pair[1].as_vec2()) * nav_grid.cost(pair[1]).unwrap()
+            })
+            .sum();
+        let distance = field.distance(UVec2::new(1, 1)).unwrap();
+        assert!((distance - cost).abs() < 1e-3);
+        let path = field.path(UVec2::new(1, 1)).unwrap();
+        assert_eq!(path.0[0], Vec2::splat(1.5));
+        assert_eq!(*path.0.last().unwrap(), Vec2::new(8.5, 1.5));
+    }
+}
@@ -0,0 +1,296 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use glam::Vec2;
+
+use crate::{
+    collision::{AabbTree, POINT_TOLERANCE},
+    geometry::{orient2d, Bounded, Polyline, Triangle2},
+    utility::SortableWith,
+};
+
+/// Identifies a triangle of a [`NavMesh`], in the order they were given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NavMeshKey(pub usize);
+
+/// Walkable area made of triangles, such as the output of ear clipping or a Delaunay
+/// triangulation. Triangles are neighbours when they share an edge with identical end points.
+#[derive(Debug, Clone)]
+pub struct NavMesh {
+    // counterclockwise, neighbour `i` lies across the edge from vertex `i` to vertex `i + 1`
+    triangles: Vec<Triangle2>,
+    neighbours: Vec<[Option<usize>; 3]>,
+    tree: AabbTree<usize>,
+}
+
+impl NavMesh {
+    pub fn new(triangles: &[Triangle2]) -> NavMesh {
+        let triangles: Vec<Triangle2> = triangles
+            .iter()
+            .map(|triangle| {
+                if orient2d(triangle.a(), triangle.b(), triangle.c()) < 0. {
+                    Triangle2::new(triangle.a(), triangle.c(), triangle.b())
+                } else {
+                    *triangle
+                }
+            })
+            .collect();
+
+        let bits = |point: Vec2| (point.x.to_bits(), point.y.to_bits());
+        let mut edges: HashMap<_, (usize, usize)> = HashMap::new();
+        let mut neighbours = vec![[None; 3]; triangles.len()];
+        for (index, triangle) in triangles.iter().enumerate() {
+            for edge in 0..3 {
+                let (start, end) = (triangle.vertices[edge], triangle.vertices[(edge + 1) % 3]);
+                // a neighbour walks the shared edge the other way round
+                if let Some((other, other_edge)) = edges.remove(&(bits(end), bits(start))) {
+                    neighbours[index][edge] = Some(other);
+                    neighbours[other][other_edge] = Some(index);
+                } else {
+                    edges.insert((bits(start), bits(end)), (index, edge));
+                }
+            }
+        }
+
+        let mut tree = AabbTree::new(0.);
+        for (index, triangle) in triangles.iter().enumerate() {
+            tree.insert(triangle.aabb().expanded(POINT_TOLERANCE), index);
+        }
+        NavMesh {
+            triangles,
+            neighbours,
+            tree,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// The triangle, wound counterclockwise.
+    pub fn triangle(&self, key: NavMeshKey) -> Triangle2 {
+        self.triangles[key.0]
+    }
+
+    pub fn neighbours(&self, key: NavMeshKey) -> Vec<NavMeshKey> {
+        self.neighbours[key.0]
+            .iter()
+            .flatten()
+            .map(|index| NavMeshKey(*index))
+            .collect()
+    }
+
+    /// The triangle containing `point`, the lowest key when it lies on a shared edge.
+    pub fn locate(&self, point: Vec2) -> Option<NavMeshKey> {
+        bodge_assert!(point.is_finite());
+        self.tree
+            .query_point(point)
+            .into_iter()
+            .map(|key| *self.tree.get(key).unwrap())
+            .filter(|index| {
+                let vertices = self.triangles[*index].vertices;
+                (0..3).all(|edge| orient2d(vertices[edge], vertices[(edge + 1) % 3], point) >= 0.)
+            })
+            .min()
+            .map(NavMeshKey)
+    }
+
+    /// The triangles an agent of `radius` walks through from `start` to `goal`, found with A*
+    /// over the triangle centroids. Shared edges shorter than the agent is wide can't be crossed.
+    pub fn find_corridor(&self, start: Vec2, goal: Vec2, radius: f32) -> Option<Vec<NavMeshKey>> {
+        bodge_assert!(radius >= 0.);
+        let (start_index, goal_index) = (self.locate(start)?.0, self.locate(goal)?.0);
+        let centroid = |index: usize| {
+            let vertices = self.triangles[index].vertices;
+            (vertices[0] + vertices[1] + vertices[2]) / 3.
+        };
+        let position = |index: usize| match index {
+            _ if index == start_index => start,
+            _ if index == goal_index => goal,
+            _ => centroid(index),
+        };
+
+        let mut costs = vec![f32::INFINITY; self.triangles.len()];
+        let mut previous = vec![None; self.triangles.len()];
+        let mut open = BinaryHeap::new();
+        costs[start_index] = 0.;
+        open.push(Reverse(SortableWith(start.distance(goal), start_index)));
+        while let Some(Reverse(SortableWith(_, index))) = open.pop() {
+            if index == goal_index {
+                let mut corridor = vec![NavMeshKey(index)];
+                while let Some(before) = previous[corridor.last().unwrap().0] {
+                    corridor.push(NavMeshKey(before));
+                }
+                corridor.reverse();
+                return Some(corridor);
+            }
+            for edge in 0..3 {
+                let Some(neighbour) = self.neighbours[index][edge] else {
+                    continue;
+                };
+                let (left, right) = self.portal(index, edge);
+                if left.distance(right) < radius * 2. {
+                    continue;
+                }
+                let cost = costs[index] + position(index).distance(position(neighbour));
+                if cost < costs[neighbour] {
+                    costs[neighbour] = cost;
+                    previous[neighbour] = Some(index);
+                    let estimate = cost + position(neighbour).distance(goal);
+                    open.push(Reverse(SortableWith(estimate, neighbour)));
+                }
+            }
+        }
+        None
+    }
+
+    /// The shortest path for an agent of `radius` from `start` to `goal`, pulled tight through
+    /// the corridor with the simple stupid funnel algorithm. Portals are narrowed by `radius` at
+    /// both ends to keep the agent off the corners it turns around.
+    pub fn find_path(&self, start: Vec2, goal: Vec2, radius: f32) -> Option<Polyline> {
+        let corridor = self.find_corridor(start, goal, radius)?;
+        let mut portals = vec![(start, start)];
+        for pair in corridor.windows(2) {
+            let edge = (0..3)
+                .find(|edge| self.neighbours[pair[0].0][*edge] == Some(pair[1].0))
+                .unwrap();
+            let (left, right) = self.portal(pair[0].0, edge);
+            let inset = (right - left).normalize_or_zero() * radius;
+            portals.push((left + inset, right - inset));
+        }
+        portals.push((goal, goal));
+        Some(Polyline(funnel(&portals)))
+    }
+
+    // the edge leading out of a triangle as seen by an agent walking through it
+    fn portal(&self, index: usize, edge: usize) -> (Vec2, Vec2) {
+        let vertices = self.triangles[index].vertices;
+        (vertices[(edge + 1) % 3], vertices[edge])
+    }
+}
+
+fn funnel(portals: &[(Vec2, Vec2)]) -> Vec<Vec2> {
+    let mut path = vec![portals[0].0];
+    let (mut apex, mut left, mut right) = (portals[0].0, portals[0].0, portals[0].1);
+    let (mut left_index, mut right_index) = (0, 0);
+    let mut index = 1;
+    while index < portals.len() {
+        let (portal_left, portal_right) = portals[index];
+        // narrow the funnel from the right, unless that crosses over the left side
+        if orient2d(apex, right, portal_right) >= 0. {
+            if apex == right || orient2d(apex, left, portal_right) < 0. {
+                right = portal_right;
+                right_index = index;
+            } else {
+                if *path.last().unwrap() != left {
+                    path.push(left);
+                }
+                apex = left;
+                right = apex;
+                right_index = left_index;
+                index = left_index + 1;
+                continue;
+            }
+        }
+        if orient2d(apex, left, portal_left) <= 0. {
+            if apex == left || orient2d(apex, right, portal_left) > 0. {
+                left = portal_left;
+                left_index = index;
+            } else {
+                if *path.last().unwrap() != right {
+                    path.push(right);
+                }
+                apex = right;
+                left = apex;
+                left_index = right_index;
+                index = right_index + 1;
+                continue;
+            }
+        }
+        index += 1;
+    }
+    let goal = portals.last().unwrap().0;
+    if *path.last().unwrap() != goal {
+        path.push(goal);
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 3 by 3 grid of unit squares with the middle one missing, split into triangles
+    fn ring() -> NavMesh {
+        let mut triangles = vec![];
+        for y in 0..3 {
+            for x in 0..3 {
+                if (x, y) == (1, 1) {
+                    continue;
+                }
+                let corner = Vec2::new(x as f32, y as f32);
+                triangles.push(Triangle2::new(corner, corner + Vec2::X, corner + Vec2::ONE));
+                triangles.push(Triangle2::new(corner, corner + Vec2::Y, corner + Vec2::ONE));
+            }
+        }
+        NavMesh::new(&triangles)
+    }
+
+    #[test]
+    fn nav_mesh_adjacency_and_location() {
+        let nav_mesh = ring();
+        assert_eq!(nav_mesh.len(), 16);
+        let total: usize = (0..16)
+            .map(|index| nav_mesh.neighbours(NavMeshKey(index)).len())
+            .sum();
+        // 16 triangles with 48 edges, of which the 12 outer and 4 inner border edges are unshared
+        assert_eq!(total, 48 - 16);
+        assert_eq!(nav_mesh.locate(Vec2::new(0.75, 0.25)), Some(NavMeshKey(0)));
+        assert_eq!(nav_mesh.locate(Vec2::new(0.5, 0.5)), Some(NavMeshKey(0)));
+        assert_eq!(nav_mesh.locate(Vec2::splat(1.5)), None);
+        assert_eq!(nav_mesh.locate(Vec2::splat(3.5)), None);
+    }
+
+    #[test]
+    fn nav_mesh_path_goes_around_the_hole() {
+        let nav_mesh = ring();
+        let path = nav_mesh
+            .find_path(Vec2::new(0.5, 1.5), Vec2::new(2.5, 1.5), 0.)
+            .unwrap();
+        assert_eq!(path.0.len(), 4);
+        assert_eq!(path.0[0], Vec2::new(0.5, 1.5));
+        assert_eq!(path.0[3], Vec2::new(2.5, 1.5));
+        // hugging the corners of the hole on whichever side it picked
+        assert!(path.0[1].y == path.0[2].y && (path.0[1].y == 1. || path.0[1].y == 2.));
+        assert_eq!(path.0[1].x, 1.);
+        assert_eq!(path.0[2].x, 2.);
+
+        let wide = nav_mesh
+            .find_path(Vec2::new(0.5, 1.5), Vec2::new(2.5, 1.5), 0.25)
+            .unwrap();
+        let corners = [
+            Vec2::splat(1.),
+            Vec2::new(1., 2.),
+            Vec2::new(2., 1.),
+            Vec2::splat(2.),
+        ];
+        assert!(wide
+            .0
+            .iter()
+            .all(|point| corners.iter().all(|corner| corner.distance(*point) > 0.24)));
+        assert!(nav_mesh
+            .find_path(Vec2::new(0.5, 1.5), Vec2::new(2.5, 1.5), 0.6)
+            .is_none());
+
+        let straight = nav_mesh
+            .find_path(Vec2::new(0.2, 0.5), Vec2::new(2.8, 0.5), 0.)
+            .unwrap();
+        assert_eq!(straight.0, vec![Vec2::new(0.2, 0.5), Vec2::new(2.8, 0.5)]);
+    }
+}
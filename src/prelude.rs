@@ -1,4 +1,8 @@
 pub use crate::{
-    bevy::{AddScenes, DebugDrawable},
+    bevy::{AddScenes, DebugDrawable, DebugDrawable3},
     collision::Colliding,
+    geometry::Bounded,
 };
+
+#[cfg(feature = "rand")]
+pub use crate::geometry::RandomPoints;
@@ -0,0 +1,366 @@
+use std::fmt;
+
+use glam::Vec2;
+
+use crate::geometry::{Aabb, Bounded, Circle, LineSegment2, Obb, Polyline, Triangle2, VertexList2};
+
+#[cfg(feature = "bevy")]
+use crate::bevy::{DebugDraw, DebugDrawMesh, DebugDrawStyle};
+
+/// How a shape is painted in an [`SvgDocument`], mirroring the parts of a `DebugDrawStyle` that
+/// SVG can express.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgStyle {
+    /// Red, green, blue and alpha in sRGB, from 0 to 1.
+    pub color: [f32; 4],
+    pub outline: bool,
+    pub thickness: f32,
+    pub depth: f32,
+}
+
+impl Default for SvgStyle {
+    fn default() -> SvgStyle {
+        SvgStyle {
+            color: [0., 0., 0., 1.],
+            outline: false,
+            thickness: 1.,
+            depth: 0.,
+        }
+    }
+}
+
+impl SvgStyle {
+    pub fn new(color: [f32; 4]) -> SvgStyle {
+        SvgStyle {
+            color,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_outline(color: [f32; 4]) -> SvgStyle {
+        SvgStyle {
+            color,
+            outline: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_color(&self, color: [f32; 4]) -> SvgStyle {
+        SvgStyle { color, ..*self }
+    }
+
+    pub fn with_outline(&self, outline: bool) -> SvgStyle {
+        SvgStyle { outline, ..*self }
+    }
+
+    pub fn with_thickness(&self, thickness: f32) -> SvgStyle {
+        SvgStyle { thickness, ..*self }
+    }
+
+    pub fn with_depth(&self, depth: f32) -> SvgStyle {
+        SvgStyle { depth, ..*self }
+    }
+
+    fn hex(&self) -> String {
+        let [red, green, blue] =
+            [0, 1, 2].map(|index| (self.color[index].clamp(0., 1.) * 255.).round() as u8);
+        format!("#{red:02x}{green:02x}{blue:02x}")
+    }
+
+    // the paint attributes, with lines always stroked whatever `outline` says
+    fn attributes(&self, stroked: bool) -> String {
+        let (hex, alpha) = (self.hex(), self.color[3].clamp(0., 1.));
+        if stroked {
+            format!(
+                r#"fill="none" stroke="{hex}" stroke-opacity="{alpha}" stroke-width="{}" stroke-linejoin="round""#,
+                self.thickness
+            )
+        } else {
+            format!(r#"fill="{hex}" fill-opacity="{alpha}""#)
+        }
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl From<DebugDrawStyle> for SvgStyle {
+    fn from(style: DebugDrawStyle) -> SvgStyle {
+        SvgStyle {
+            color: style.color.as_rgba_f32(),
+            outline: style.outline,
+            thickness: style.thickness,
+            depth: style.depth,
+        }
+    }
+}
+
+/// A shape that can be written as a single SVG element.
+pub trait SvgShape: Bounded {
+    fn svg_element(&self, style: &SvgStyle) -> String;
+}
+
+fn points_attribute(points: &[Vec2]) -> String {
+    points
+        .iter()
+        .map(|point| format!("{},{}", point.x, point.y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn polygon_element(points: &[Vec2], style: &SvgStyle) -> String {
+    format!(
+        r#"<polygon points="{}" {}/>"#,
+        points_attribute(points),
+        style.attributes(style.outline)
+    )
+}
+
+impl SvgShape for Aabb {
+    fn svg_element(&self, style: &SvgStyle) -> String {
+        let min = self.min();
+        format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" {}/>"#,
+            min.x,
+            min.y,
+            self.size.x,
+            self.size.y,
+            style.attributes(style.outline)
+        )
+    }
+}
+
+impl SvgShape for Obb {
+    fn svg_element(&self, style: &SvgStyle) -> String {
+        polygon_element(&self.corners(), style)
+    }
+}
+
+impl SvgShape for Circle {
+    fn svg_element(&self, style: &SvgStyle) -> String {
+        format!(
+            r#"<circle cx="{}" cy="{}" r="{}" {}/>"#,
+            self.center.x,
+            self.center.y,
+            self.radius * 0.5,
+            style.attributes(style.outline)
+        )
+    }
+}
+
+impl SvgShape for LineSegment2 {
+    fn svg_element(&self, style: &SvgStyle) -> String {
+        format!(
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {}/>"#,
+            self.start.x,
+            self.start.y,
+            self.end.x,
+            self.end.y,
+            style.attributes(true)
+        )
+    }
+}
+
+impl SvgShape for Triangle2 {
+    fn svg_element(&self, style: &SvgStyle) -> String {
+        polygon_element(&self.vertices, style)
+    }
+}
+
+impl SvgShape for Polyline {
+    fn svg_element(&self, style: &SvgStyle) -> String {
+        format!(
+            r#"<polyline points="{}" {}/>"#,
+            points_attribute(&self.0),
+            style.attributes(true)
+        )
+    }
+}
+
+impl SvgShape for VertexList2 {
+    fn svg_element(&self, style: &SvgStyle) -> String {
+        polygon_element(&self.0, style)
+    }
+}
+
+/// Collects shapes and debug draw meshes into an SVG document, painted in order of depth like the
+/// debug renderer does. Printing it with `to_string` gives the document, framed around everything
+/// in it.
+#[derive(Debug, Clone)]
+pub struct SvgDocument {
+    /// Mirrors the picture vertically, so y points up as in Bevy rather than down as in SVG.
+    pub flip_y: bool,
+    /// Space left around the shapes.
+    pub margin: f32,
+    elements: Vec<(f32, String)>,
+    bounds: Option<(Vec2, Vec2)>,
+}
+
+impl Default for SvgDocument {
+    fn default() -> SvgDocument {
+        SvgDocument {
+            flip_y: true,
+            margin: 1.,
+            elements: vec![],
+            bounds: None,
+        }
+    }
+}
+
+impl SvgDocument {
+    pub fn new() -> SvgDocument {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn add<S: SvgShape>(&mut self, shape: &S, style: SvgStyle) {
+        let aabb = shape.aabb();
+        let reach = if style.outline {
+            style.thickness * 0.5
+        } else {
+            0.
+        };
+        self.include(aabb.min() - reach, aabb.max() + reach);
+        self.elements.push((style.depth, shape.svg_element(&style)));
+    }
+
+    /// Adds a mesh as one triangle per three indices, each taking the color of its first vertex.
+    #[cfg(feature = "bevy")]
+    pub fn add_mesh(&mut self, mesh: &DebugDrawMesh) {
+        if mesh.vertices.is_empty() {
+            return;
+        }
+        // without anti-aliasing the seams between neighbouring triangles don't show
+        let mut group = String::from(r#"<g shape-rendering="crispEdges">"#);
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [0, 1, 2].map(|corner| mesh.vertices[triangle[corner] as usize]);
+            let style = SvgStyle::new(vertices[0].color.as_rgba_f32());
+            group.push_str(&polygon_element(
+                &vertices.map(|vertex| vertex.position),
+                &style,
+            ));
+        }
+        group.push_str("</g>");
+        let (min, max) = mesh.vertices.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), vertex| (min.min(vertex.position), max.max(vertex.position)),
+        );
+        self.include(min, max);
+        self.elements.push((mesh.depth, group));
+    }
+
+    /// Adds every mesh drawn so far this frame.
+    #[cfg(feature = "bevy")]
+    pub fn add_debug_draw(&mut self, debug_draw: &DebugDraw) {
+        for mesh in debug_draw.meshes() {
+            self.add_mesh(mesh);
+        }
+    }
+
+    fn include(&mut self, min: Vec2, max: Vec2) {
+        self.bounds = Some(match self.bounds {
+            Some((old_min, old_max)) => (old_min.min(min), old_max.max(max)),
+            None => (min, max),
+        });
+    }
+}
+
+impl fmt::Display for SvgDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min, max) = self.bounds.unwrap_or((Vec2::ZERO, Vec2::ZERO));
+        let (min, max) = (min - self.margin, max + self.margin);
+        let size = max - min;
+        // flipping maps y onto -y, which moves the top of the frame to -max.y
+        let top = if self.flip_y { -max.y } else { min.y };
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            min.x, top, size.x, size.y
+        )?;
+        if self.flip_y {
+            writeln!(f, r#"<g transform="scale(1,-1)">"#)?;
+        }
+        // a stable sort keeps shapes at the same depth in the order they were added
+        let mut order: Vec<&(f32, String)> = self.elements.iter().collect();
+        order.sort_by(|a, b| a.0.total_cmp(&b.0));
+        for (_, element) in order {
+            writeln!(f, "{element}")?;
+        }
+        if self.flip_y {
+            writeln!(f, "</g>")?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::svg::SvgImport;
+
+    use super::*;
+
+    #[test]
+    fn svg_document_round_trip() {
+        let mut document = SvgDocument::new();
+        document.flip_y = false;
+        let square = VertexList2(vec![
+            Vec2::ZERO,
+            Vec2::new(4., 0.),
+            Vec2::new(4., 4.),
+            Vec2::new(0., 4.),
+        ]);
+        document.add(&square, SvgStyle::new([1., 0., 0., 0.5]).with_depth(2.));
+        document.add(&Circle::new(Vec2::new(10., 0.), 4.), SvgStyle::default());
+        document.add(
+            &Polyline(vec![Vec2::ZERO, Vec2::new(-5., 5.)]),
+            SvgStyle::new_outline([0., 0., 1., 1.]).with_thickness(2.),
+        );
+        let svg = document.to_string();
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-7 -3 20 10">"#)
+        );
+        assert!(svg.contains(r##"fill="#ff0000" fill-opacity="0.5""##));
+        // the square was added first but is drawn last, being the deepest
+        assert!(svg.find("<circle").unwrap() < svg.find("<polygon").unwrap());
+
+        let shapes = SvgImport::new(0.01).document(&svg).unwrap();
+        assert_eq!(
+            shapes.open,
+            vec![Polyline(vec![Vec2::ZERO, Vec2::new(-5., 5.)])]
+        );
+        assert_eq!(shapes.closed.len(), 2);
+        assert!(shapes.closed[0]
+            .0
+            .iter()
+            .all(|point| (point.distance(Vec2::new(10., 0.)) - 2.).abs() < 1e-3));
+        assert_eq!(shapes.closed[1], square);
+    }
+
+    #[cfg(feature = "bevy")]
+    #[test]
+    fn svg_document_from_debug_draw() {
+        use bevy::prelude::Color;
+
+        use crate::bevy::DebugDrawable;
+
+        let mut debug_draw = DebugDraw::default();
+        Triangle2::new(Vec2::ZERO, Vec2::X, Vec2::Y).draw(
+            &mut debug_draw,
+            DebugDrawStyle::new(Color::rgb(0., 1., 0.)).with_depth(1.),
+        );
+        Aabb::new(Vec2::ZERO, Vec2::splat(4.)).draw(&mut debug_draw, DebugDrawStyle::default());
+        let mut document = SvgDocument::new();
+        document.add_debug_draw(&debug_draw);
+        assert_eq!(document.len(), 2);
+        let svg = document.to_string();
+        assert!(svg.contains(r#"<g transform="scale(1,-1)">"#));
+        assert!(svg.contains(r#"viewBox="-3 -3 6 6""#));
+        assert_eq!(svg.matches("<polygon").count(), 3);
+        assert!(svg.rfind("#000000").unwrap() < svg.find("#00ff00").unwrap());
+    }
+}
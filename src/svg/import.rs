@@ -0,0 +1,602 @@
+use std::fmt;
+
+use glam::Vec2;
+
+use crate::geometry::{Polyline, VertexList2};
+
+// how often a curve may be halved before it is taken as flat regardless
+const MAX_SUBDIVISIONS: u32 = 16;
+
+/// Settings for reading outlines out of SVG path data and documents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgImport {
+    /// How far flattened curves and arcs may stray from the real ones.
+    pub tolerance: f32,
+    /// Negates y, since SVG points y down where Bevy points it up.
+    pub flip_y: bool,
+}
+
+/// Closed subpaths and shapes become polygons, everything else lines.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SvgShapes {
+    pub closed: Vec<VertexList2>,
+    pub open: Vec<Polyline>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SvgError {
+    /// Path data held something other than a command or number at this byte offset.
+    UnexpectedCharacter(usize),
+    /// A command ran out of numbers at this byte offset of the path data.
+    MissingNumber(usize),
+    /// An element lacked an attribute it needs, or it wasn't a number.
+    InvalidAttribute {
+        element: &'static str,
+        attribute: &'static str,
+    },
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgError::UnexpectedCharacter(offset) => {
+                write!(f, "unexpected character in path data at {offset}")
+            }
+            SvgError::MissingNumber(offset) => {
+                write!(f, "expected a number in path data at {offset}")
+            }
+            SvgError::InvalidAttribute { element, attribute } => {
+                write!(f, "<{element}> is missing a valid {attribute} attribute")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+impl Default for SvgImport {
+    fn default() -> SvgImport {
+        SvgImport {
+            tolerance: 0.25,
+            flip_y: false,
+        }
+    }
+}
+
+impl SvgImport {
+    pub fn new(tolerance: f32) -> SvgImport {
+        SvgImport {
+            tolerance,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_tolerance(&self, tolerance: f32) -> SvgImport {
+        SvgImport { tolerance, ..*self }
+    }
+
+    pub fn with_flip_y(&self, flip_y: bool) -> SvgImport {
+        SvgImport { flip_y, ..*self }
+    }
+
+    /// Reads the `d` attribute of a `<path>`, supporting every command in both their absolute and
+    /// relative forms.
+    pub fn path(&self, data: &str) -> Result<SvgShapes, SvgError> {
+        bodge_assert!(self.tolerance.is_finite() && self.tolerance > 0.);
+        let mut builder = Builder {
+            settings: self,
+            shapes: SvgShapes::default(),
+            points: vec![],
+        };
+        let mut cursor = Cursor {
+            data: data.as_bytes(),
+            index: 0,
+        };
+        let (mut current, mut start) = (Vec2::ZERO, Vec2::ZERO);
+        // the last control point and whether it was cubic, mirrored by the smooth curve commands
+        let mut control: Option<(bool, Vec2)> = None;
+        let mut command = None;
+        while let Some(next) = cursor.command()? {
+            // commands repeat as long as more numbers follow, and a move repeats as a line
+            let letter = match next {
+                Some(letter) => letter,
+                None => match command {
+                    Some(b'M') => b'L',
+                    Some(b'm') => b'l',
+                    Some(letter) if !matches!(letter, b'Z' | b'z') => letter,
+                    _ => return Err(SvgError::UnexpectedCharacter(cursor.index)),
+                },
+            };
+            command = Some(letter);
+            let relative = letter.is_ascii_lowercase();
+            let offset = if relative { current } else { Vec2::ZERO };
+            if builder.points.is_empty() && !matches!(letter, b'M' | b'm') {
+                builder.points.push(current);
+            }
+            let mirrored = |cubic: bool| match control {
+                Some((was_cubic, control)) if was_cubic == cubic => current * 2. - control,
+                _ => current,
+            };
+            let mut smooth = None;
+            match letter.to_ascii_uppercase() {
+                b'M' => {
+                    builder.finish(false);
+                    current = offset + cursor.point()?;
+                    start = current;
+                    builder.points.push(current);
+                }
+                b'L' => {
+                    current = offset + cursor.point()?;
+                    builder.points.push(current);
+                }
+                b'H' => {
+                    current.x = offset.x + cursor.number()?;
+                    builder.points.push(current);
+                }
+                b'V' => {
+                    current.y = offset.y + cursor.number()?;
+                    builder.points.push(current);
+                }
+                b'C' | b'S' => {
+                    let first = if letter.eq_ignore_ascii_case(&b'C') {
+                        offset + cursor.point()?
+                    } else {
+                        mirrored(true)
+                    };
+                    let (second, end) = (offset + cursor.point()?, offset + cursor.point()?);
+                    builder.cubic(current, first, second, end, MAX_SUBDIVISIONS);
+                    smooth = Some((true, second));
+                    current = end;
+                }
+                b'Q' | b'T' => {
+                    let middle = if letter.eq_ignore_ascii_case(&b'Q') {
+                        offset + cursor.point()?
+                    } else {
+                        mirrored(false)
+                    };
+                    let end = offset + cursor.point()?;
+                    // raised to the cubic with the same shape
+                    builder.cubic(
+                        current,
+                        current + (middle - current) * (2. / 3.),
+                        end + (middle - end) * (2. / 3.),
+                        end,
+                        MAX_SUBDIVISIONS,
+                    );
+                    smooth = Some((false, middle));
+                    current = end;
+                }
+                b'A' => {
+                    let radii = cursor.point()?;
+                    let rotation = cursor.number()?;
+                    let (large_arc, sweep) = (cursor.flag()?, cursor.flag()?);
+                    let end = offset + cursor.point()?;
+                    builder.arc(current, radii, rotation, large_arc, sweep, end);
+                    current = end;
+                }
+                b'Z' => {
+                    builder.finish(true);
+                    current = start;
+                }
+                _ => return Err(SvgError::UnexpectedCharacter(cursor.index - 1)),
+            }
+            control = smooth;
+        }
+        builder.finish(false);
+        Ok(builder.shapes)
+    }
+
+    /// Reads every `<path>`, `<polygon>`, `<polyline>`, `<rect>` and `<circle>` in a document.
+    /// Transforms, styles and units other than plain numbers are ignored.
+    pub fn document(&self, svg: &str) -> Result<SvgShapes, SvgError> {
+        let mut shapes = SvgShapes::default();
+        let mut rest = svg;
+        while let Some(open) = rest.find('<') {
+            rest = &rest[open + 1..];
+            let end = rest.find('>').unwrap_or(rest.len());
+            let tag = &rest[..end];
+            rest = &rest[end..];
+            let name_end = tag
+                .find(|c: char| c.is_whitespace() || c == '/')
+                .unwrap_or(tag.len());
+            let attributes = &tag[name_end..];
+            let data = match &tag[..name_end] {
+                "path" => attribute(attributes, "d").unwrap_or("").to_string(),
+                "polygon" => points_path(attributes, "polygon", "Z")?,
+                "polyline" => points_path(attributes, "polyline", "")?,
+                "rect" => {
+                    let number = |name| number_attribute(attributes, "rect", name);
+                    let (x, y) = (number("x").unwrap_or(0.), number("y").unwrap_or(0.));
+                    let (width, height) = (number("width")?, number("height")?);
+                    // a missing corner radius takes the other one
+                    let (rx, ry) = match (number("rx").ok(), number("ry").ok()) {
+                        (None, None) => (0., 0.),
+                        (rx, ry) => (rx.or(ry).unwrap(), ry.or(rx).unwrap()),
+                    };
+                    let (rx, ry) = (rx.min(width * 0.5), ry.min(height * 0.5));
+                    format!(
+                        "M{},{y} H{} A{rx},{ry} 0 0 1 {},{} V{} A{rx},{ry} 0 0 1 {},{} H{} \
+                         A{rx},{ry} 0 0 1 {x},{} V{} A{rx},{ry} 0 0 1 {},{y} Z",
+                        x + rx,
+                        x + width - rx,
+                        x + width,
+                        y + ry,
+                        y + height - ry,
+                        x + width - rx,
+                        y + height,
+                        x + rx,
+                        y + height - ry,
+                        y + ry,
+                        x + rx,
+                    )
+                }
+                "circle" => {
+                    let number = |name| number_attribute(attributes, "circle", name);
+                    let (cx, cy) = (number("cx").unwrap_or(0.), number("cy").unwrap_or(0.));
+                    let r = number("r")?;
+                    format!(
+                        "M{},{cy} A{r},{r} 0 1 0 {},{cy} A{r},{r} 0 1 0 {},{cy} Z",
+                        cx - r,
+                        cx + r,
+                        cx - r
+                    )
+                }
+                _ => continue,
+            };
+            let found = self.path(&data)?;
+            shapes.closed.extend(found.closed);
+            shapes.open.extend(found.open);
+        }
+        Ok(shapes)
+    }
+}
+
+struct Builder<'a> {
+    settings: &'a SvgImport,
+    shapes: SvgShapes,
+    points: Vec<Vec2>,
+}
+
+impl Builder<'_> {
+    fn finish(&mut self, closed: bool) {
+        let mut points = std::mem::take(&mut self.points);
+        points.dedup();
+        if self.settings.flip_y {
+            for point in points.iter_mut() {
+                point.y = -point.y;
+            }
+        }
+        if closed {
+            if points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            if points.len() >= 3 {
+                self.shapes.closed.push(VertexList2(points));
+            }
+        } else if points.len() >= 2 {
+            self.shapes.open.push(Polyline(points));
+        }
+    }
+
+    // halves the curve until its control points lie within the tolerance of its chord
+    fn cubic(&mut self, start: Vec2, first: Vec2, second: Vec2, end: Vec2, depth: u32) {
+        let chord = end - start;
+        let distance = |point: Vec2| match chord.length() {
+            length if length > f32::EPSILON => chord.perp_dot(point - start).abs() / length,
+            _ => point.distance(start),
+        };
+        if depth == 0 || distance(first).max(distance(second)) <= self.settings.tolerance {
+            self.points.push(end);
+            return;
+        }
+        let (a, b, c) = (
+            start.lerp(first, 0.5),
+            first.lerp(second, 0.5),
+            second.lerp(end, 0.5),
+        );
+        let (d, e) = (a.lerp(b, 0.5), b.lerp(c, 0.5));
+        let middle = d.lerp(e, 0.5);
+        self.cubic(start, a, d, middle, depth - 1);
+        self.cubic(middle, e, c, end, depth - 1);
+    }
+
+    // follows the endpoint to center conversion in the SVG specification's implementation notes
+    fn arc(
+        &mut self,
+        start: Vec2,
+        radii: Vec2,
+        rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        end: Vec2,
+    ) {
+        let mut radii = radii.abs();
+        if start == end {
+            return;
+        }
+        if radii.x == 0. || radii.y == 0. {
+            self.points.push(end);
+            return;
+        }
+        let axis = Vec2::from_angle(rotation.to_radians());
+        // half the chord in the frame of the ellipse's axes
+        let local = Vec2::new(axis.x, -axis.y).rotate((start - end) * 0.5);
+        // radii too small to reach are scaled up just enough
+        let scale = (local / radii).length_squared();
+        if scale > 1. {
+            radii *= scale.sqrt();
+        }
+        let (squared, local_squared) = (radii * radii, local * local);
+        let numerator =
+            squared.x * squared.y - squared.x * local_squared.y - squared.y * local_squared.x;
+        let denominator = squared.x * local_squared.y + squared.y * local_squared.x;
+        let mut factor = (numerator / denominator).max(0.).sqrt();
+        if large_arc == sweep {
+            factor = -factor;
+        }
+        let local_center =
+            Vec2::new(radii.x * local.y / radii.y, -radii.y * local.x / radii.x) * factor;
+        let center = axis.rotate(local_center) + (start + end) * 0.5;
+        let first = (local - local_center) / radii;
+        let mut angle = first.angle_between((-local - local_center) / radii);
+        if sweep && angle < 0. {
+            angle += std::f32::consts::TAU;
+        } else if !sweep && angle > 0. {
+            angle -= std::f32::consts::TAU;
+        }
+        let start_angle = first.y.atan2(first.x);
+        // the widest step whose chord stays within the tolerance of the larger radius
+        let largest = radii.max_element();
+        let step = if self.settings.tolerance < largest {
+            2. * (1. - self.settings.tolerance / largest).acos()
+        } else {
+            std::f32::consts::FRAC_PI_2
+        };
+        let segments = (angle.abs() / step).ceil().max(1.) as usize;
+        for segment in 1..segments {
+            let angle = start_angle + angle * segment as f32 / segments as f32;
+            self.points
+                .push(center + axis.rotate(Vec2::from_angle(angle) * radii));
+        }
+        self.points.push(end);
+    }
+}
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    index: usize,
+}
+
+impl Cursor<'_> {
+    fn skip_separators(&mut self) {
+        while self
+            .data
+            .get(self.index)
+            .is_some_and(|byte| byte.is_ascii_whitespace() || *byte == b',')
+        {
+            self.index += 1;
+        }
+    }
+
+    // the next command letter, `Some(None)` when a number follows instead, `None` at the end
+    fn command(&mut self) -> Result<Option<Option<u8>>, SvgError> {
+        self.skip_separators();
+        match self.data.get(self.index) {
+            None => Ok(None),
+            Some(byte) if byte.is_ascii_alphabetic() && !matches!(byte, b'e' | b'E') => {
+                self.index += 1;
+                Ok(Some(Some(*byte)))
+            }
+            Some(byte) if byte.is_ascii_digit() || matches!(byte, b'.' | b'-' | b'+') => {
+                Ok(Some(None))
+            }
+            Some(_) => Err(SvgError::UnexpectedCharacter(self.index)),
+        }
+    }
+
+    fn number(&mut self) -> Result<f32, SvgError> {
+        self.skip_separators();
+        let start = self.index;
+        let digits = |cursor: &mut Self| {
+            while cursor
+                .data
+                .get(cursor.index)
+                .is_some_and(u8::is_ascii_digit)
+            {
+                cursor.index += 1;
+            }
+        };
+        if matches!(self.data.get(self.index), Some(b'-' | b'+')) {
+            self.index += 1;
+        }
+        digits(self);
+        // a second point starts the next number, as in `0.5.5`
+        if self.data.get(self.index) == Some(&b'.') {
+            self.index += 1;
+            digits(self);
+        }
+        if matches!(self.data.get(self.index), Some(b'e' | b'E')) {
+            let mantissa_end = self.index;
+            self.index += 1;
+            if matches!(self.data.get(self.index), Some(b'-' | b'+')) {
+                self.index += 1;
+            }
+            let exponent_start = self.index;
+            digits(self);
+            if self.index == exponent_start {
+                self.index = mantissa_end;
+            }
+        }
+        std::str::from_utf8(&self.data[start..self.index])
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .filter(|number: &f32| number.is_finite())
+            .ok_or(SvgError::MissingNumber(start))
+    }
+
+    fn point(&mut self) -> Result<Vec2, SvgError> {
+        Ok(Vec2::new(self.number()?, self.number()?))
+    }
+
+    // arc flags are single digits and may run straight into what follows
+    fn flag(&mut self) -> Result<bool, SvgError> {
+        self.skip_separators();
+        let flag = match self.data.get(self.index) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(SvgError::MissingNumber(self.index)),
+        };
+        self.index += 1;
+        Ok(flag)
+    }
+}
+
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(equals) = rest.find('=') {
+        let key = rest[..equals].trim();
+        let value = rest[equals + 1..].trim_start();
+        let quote = value.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            return None;
+        }
+        let end = value[1..].find(quote)? + 1;
+        // the key is whatever word sits right before the equals sign
+        if key.rsplit(char::is_whitespace).next() == Some(name) {
+            return Some(&value[1..end]);
+        }
+        rest = &value[end + 1..];
+    }
+    None
+}
+
+// a point list is a line-to with an implicit move at its start
+fn points_path(attributes: &str, element: &'static str, close: &str) -> Result<String, SvgError> {
+    let points = attribute(attributes, "points").ok_or(SvgError::InvalidAttribute {
+        element,
+        attribute: "points",
+    })?;
+    Ok(format!("M{points}{close}"))
+}
+
+fn number_attribute(
+    attributes: &str,
+    element: &'static str,
+    name: &'static str,
+) -> Result<f32, SvgError> {
+    attribute(attributes, name)
+        .and_then(|value| value.trim().trim_end_matches("px").parse().ok())
+        .filter(|number: &f32| number.is_finite())
+        .ok_or(SvgError::InvalidAttribute {
+            element,
+            attribute: name,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_path_commands() {
+        let shapes = SvgImport::default()
+            .path("M10 10 h 20 v20 H10 z m 40 0 l10-5 5.5.5")
+            .unwrap();
+        assert_eq!(
+            shapes.closed,
+            vec![VertexList2(vec![
+                Vec2::new(10., 10.),
+                Vec2::new(30., 10.),
+                Vec2::new(30., 30.),
+                Vec2::new(10., 30.),
+            ])]
+        );
+        assert_eq!(
+            shapes.open,
+            vec![Polyline(vec![
+                Vec2::new(50., 10.),
+                Vec2::new(60., 5.),
+                Vec2::new(65.5, 5.5),
+            ])]
+        );
+
+        let import = SvgImport::new(0.01);
+        let curve = &import.path("M0 0 C 0 10 10 10 10 0").unwrap().open[0];
+        assert!(curve.0.len() > 8);
+        let bezier = |t: f32| {
+            let u = 1. - t;
+            Vec2::new(0., 30. * u * u * t)
+                + Vec2::new(10., 10.) * 3. * u * t * t
+                + Vec2::new(10., 0.) * t * t * t
+        };
+        let samples: Vec<Vec2> = (0..=50).map(|step| bezier(step as f32 / 50.)).collect();
+        assert!(samples
+            .iter()
+            .all(|sample| curve.closest_point(*sample).distance(*sample) < 0.01));
+        let smooth = &import.path("M0 0 Q 5 10 10 0 T 20 0").unwrap().open[0];
+        assert!(smooth.0.iter().any(|point| point.y < -4.9));
+
+        let arc = &import.path("M0 0 A10 10 0 0 1 20 0").unwrap().open[0];
+        assert_eq!(*arc.0.last().unwrap(), Vec2::new(20., 0.));
+        assert!(arc
+            .0
+            .iter()
+            .all(|point| (point.distance(Vec2::new(10., 0.)) - 10.).abs() < 1e-3));
+        assert!(arc.0.iter().all(|point| point.y <= 1e-4));
+        let flipped = &import
+            .with_flip_y(true)
+            .path("M0 0 a10 10 0 0 1 20 0")
+            .unwrap()
+            .open[0];
+        assert!(flipped.0.iter().any(|point| point.y > 9.9));
+
+        assert_eq!(
+            SvgImport::default().path("M 0 0 L 5"),
+            Err(SvgError::MissingNumber(9))
+        );
+        assert_eq!(
+            SvgImport::default().path("M 0 0 X 1"),
+            Err(SvgError::UnexpectedCharacter(6))
+        );
+    }
+
+    #[test]
+    fn svg_document_elements() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+            <rect x="10" y="10" width="20" height="10"/>
+            <rect width="20" height="20" rx="5" />
+            <circle cx='50' cy='50' r='10'></circle>
+            <polygon points="0,0 10,0 10,10"/>
+            <polyline points="0 0, 5 5, 10 0" />
+            <path d="M 0 0 L 10 10" />
+        </svg>"#;
+        let shapes = SvgImport::default()
+            .with_flip_y(true)
+            .document(svg)
+            .unwrap();
+        assert_eq!(shapes.closed.len(), 4);
+        assert_eq!(shapes.open.len(), 2);
+        assert_eq!(
+            shapes.closed[0],
+            VertexList2(vec![
+                Vec2::new(10., -10.),
+                Vec2::new(30., -10.),
+                Vec2::new(30., -20.),
+                Vec2::new(10., -20.),
+            ])
+        );
+        assert!(shapes.closed[1].0.len() > 8);
+        assert!(shapes.closed[2]
+            .0
+            .iter()
+            .all(|point| (point.distance(Vec2::new(50., -50.)) - 10.).abs() < 1e-3));
+        assert_eq!(shapes.open[0].0.len(), 3);
+        assert_eq!(
+            SvgImport::default().document("<circle cx=\"1\" />"),
+            Err(SvgError::InvalidAttribute {
+                element: "circle",
+                attribute: "r",
+            })
+        );
+    }
+}